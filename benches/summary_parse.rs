@@ -0,0 +1,63 @@
+//! Compare the full torrent parse with the lightweight summary parse used for sorting,
+//! and the full parse with info hash with the peek used for summary lines.
+//!
+//! Run with `cargo bench --bench summary_parse`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use torrentinfo::{Torrent, TorrentSummary};
+
+/// Torrents with a large piece hash blob, which the summary parse skips
+const BENCH_TORRENTS: [&str; 2] = [
+    "tests/ubuntu-24.04.3-desktop-amd64.iso.torrent",
+    "tests/pop-os_24.04_amd64_nvidia_22.iso.torrent",
+];
+const ITERATIONS: u32 = 200;
+
+/// Run the parse function for all benchmark torrents and return the average duration per torrent
+fn time_parse(buffers: &[Vec<u8>], parse: impl Fn(&[u8])) -> Duration {
+    // Warm up caches and the allocator before measuring
+    for buf in buffers {
+        parse(buf);
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for buf in buffers {
+            parse(buf);
+        }
+    }
+    let count = ITERATIONS * u32::try_from(buffers.len()).expect("Too many benchmark torrents");
+    start.elapsed() / count
+}
+
+fn main() {
+    let buffers: Vec<Vec<u8>> = BENCH_TORRENTS
+        .iter()
+        .map(|path| Torrent::read_bytes(path.as_ref()).expect("Failed to read benchmark torrent"))
+        .collect();
+
+    let full = time_parse(&buffers, |buf| {
+        black_box(Torrent::from_buf(buf).expect("Failed to parse torrent"));
+    });
+    let summary = time_parse(&buffers, |buf| {
+        black_box(TorrentSummary::from_buf(buf).expect("Failed to parse torrent summary"));
+    });
+    let full_with_hash = time_parse(&buffers, |buf| {
+        let torrent = Torrent::from_buf(buf).expect("Failed to parse torrent");
+        black_box(torrent.info_hash().expect("Failed to hash torrent"));
+    });
+    let peek = time_parse(&buffers, |buf| {
+        black_box(Torrent::peek_buf(buf).expect("Failed to peek torrent"));
+    });
+
+    println!("full parse:    {full:>10.2?} per torrent");
+    println!("summary parse: {summary:>10.2?} per torrent");
+    println!("speedup:       {:>10.1}x", full.as_secs_f64() / summary.as_secs_f64());
+    println!("full + hash:   {full_with_hash:>10.2?} per torrent");
+    println!("peek:          {peek:>10.2?} per torrent");
+    println!(
+        "speedup:       {:>10.1}x",
+        full_with_hash.as_secs_f64() / peek.as_secs_f64()
+    );
+}
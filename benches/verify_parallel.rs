@@ -0,0 +1,60 @@
+//! Compare serial and parallel verification of a large synthetic dataset.
+//!
+//! Run with `cargo bench --features parallel --bench verify_parallel`.
+//! The data is written once to the target directory and read from the page cache after the first run.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use sha1::{Digest, Sha1};
+use torrentinfo::{Info, Torrent};
+
+/// Size of the synthetic dataset
+const DATA_SIZE: usize = 256 * 1024 * 1024;
+const PIECE_LENGTH: usize = 1024 * 1024;
+const FILE_NAME: &str = "synthetic.bin";
+const ITERATIONS: u32 = 3;
+
+/// Write the synthetic data file and build a torrent with matching piece hashes
+fn synthetic_torrent(root: &Path) -> Torrent {
+    let data: Vec<u8> = (0..DATA_SIZE)
+        .map(|index| (index % 251) as u8 ^ (index >> 20) as u8)
+        .collect();
+    std::fs::create_dir_all(root).expect("Failed to create benchmark directory");
+    std::fs::write(root.join(FILE_NAME), &data).expect("Failed to write benchmark data");
+    let pieces: Vec<u8> = data.chunks(PIECE_LENGTH).flat_map(Sha1::digest).collect();
+    let mut torrent = Torrent::default();
+    torrent.info = Info {
+        name: Some(FILE_NAME.to_string()),
+        length: Some(i64::try_from(DATA_SIZE).expect("Data size should fit in i64")),
+        piece_length: i64::try_from(PIECE_LENGTH).expect("Piece length should fit in i64"),
+        pieces: serde_bytes::ByteBuf::from(pieces),
+        ..Info::default()
+    };
+    torrent
+}
+
+/// Run the verification and return the average duration
+fn time_verify(verify: impl Fn() -> torrentinfo::VerifyReport) -> Duration {
+    // Warm up the page cache before measuring
+    assert!(verify().is_complete());
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        assert!(verify().is_complete());
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("verify_parallel");
+    let torrent = synthetic_torrent(&root);
+
+    let serial = time_verify(|| torrent.verify_files(&root).expect("Failed to verify files"));
+    let parallel = time_verify(|| torrent.verify_files_parallel(&root).expect("Failed to verify files"));
+
+    let throughput = |duration: Duration| DATA_SIZE as f64 / duration.as_secs_f64() / 1e6;
+    println!("threads:  {}", rayon::current_num_threads());
+    println!("serial:   {serial:>10.2?} ({:.0} MB/s)", throughput(serial));
+    println!("parallel: {parallel:>10.2?} ({:.0} MB/s)", throughput(parallel));
+    println!("speedup:  {:>10.2}x", serial.as_secs_f64() / parallel.as_secs_f64());
+}
@@ -0,0 +1,105 @@
+/*
+ * torrentinfo, A torrent file parser
+ * Copyright (C) 2025  Akseli Lukkarila
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+//! Heuristic content categories based on file extensions.
+
+use std::fmt;
+use std::path::Path;
+
+const VIDEO_EXTENSIONS: &[&str] = &[
+    "avi", "m2ts", "m4v", "mkv", "mov", "mp4", "mpeg", "mpg", "ts", "webm", "wmv",
+];
+const AUDIO_EXTENSIONS: &[&str] = &[
+    "aac", "aiff", "alac", "ape", "flac", "m4a", "mp3", "ogg", "opus", "wav", "wma",
+];
+const SOFTWARE_EXTENSIONS: &[&str] = &[
+    "apk", "appimage", "bin", "deb", "dmg", "exe", "img", "iso", "msi", "pkg", "rpm",
+];
+const BOOK_EXTENSIONS: &[&str] = &["azw", "azw3", "cbr", "cbz", "djvu", "epub", "fb2", "mobi", "pdf"];
+
+/// Content category of a torrent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    /// Video files
+    Video,
+    /// Audio files
+    Audio,
+    /// Software or disc images
+    Software,
+    /// E-books and comics
+    Book,
+    /// Files from more than one category
+    Mixed,
+    /// No recognized file extensions
+    Unknown,
+}
+
+impl Category {
+    /// Guess the category from file names.
+    ///
+    /// Files with unrecognized extensions, such as `.nfo` or `.txt`, are ignored.
+    /// If the recognized files span more than one category, the result is [`Category::Mixed`].
+    pub fn from_file_names<'a, I>(names: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut category = Self::Unknown;
+        for name in names {
+            let Some(file_category) = Self::from_file_name(name) else {
+                continue;
+            };
+            category = match category {
+                Self::Unknown => file_category,
+                current if current == file_category => current,
+                _ => return Self::Mixed,
+            };
+        }
+        category
+    }
+
+    /// Get the category for a single file name based on its extension
+    fn from_file_name(name: &str) -> Option<Self> {
+        let extension = Path::new(name).extension()?.to_str()?.to_lowercase();
+        let extension = extension.as_str();
+        if VIDEO_EXTENSIONS.contains(&extension) {
+            Some(Self::Video)
+        } else if AUDIO_EXTENSIONS.contains(&extension) {
+            Some(Self::Audio)
+        } else if SOFTWARE_EXTENSIONS.contains(&extension) {
+            Some(Self::Software)
+        } else if BOOK_EXTENSIONS.contains(&extension) {
+            Some(Self::Book)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Video => "video",
+            Self::Audio => "audio",
+            Self::Software => "software/iso",
+            Self::Book => "book",
+            Self::Mixed => "mixed",
+            Self::Unknown => "unknown",
+        };
+        write!(f, "{name}")
+    }
+}
@@ -1,20 +1,33 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{IsTerminal, Read, Write};
 use std::path::{Path, PathBuf};
 
 use anyhow::Context;
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
 use itertools::Itertools;
-use number_prefix::NumberPrefix;
-use serde_bencode::value::Value;
 
-use torrentinfo::Torrent;
+use torrentinfo::health::{self, Grade};
+use torrentinfo::report::TorrentReport;
+use torrentinfo::{FileMatch, PieceHealth, Torrent, TorrentSummary, Value};
 
-use crate::{Args, utils};
+use crate::{Args, html, utils};
 
-const BYTE_THRESHOLD: usize = 80;
 const COLUMN_WIDTH: usize = 19;
 const INDENT: &str = "    ";
+/// Room left after the pieces bar for the piece count label
+const BAR_LABEL_WIDTH: usize = 32;
+/// Default listening port used in generated announce requests
+const DEFAULT_PORT: u16 = 6881;
+/// Truncated file paths are never shorter than this, even if the line wraps
+const MIN_PATH_WIDTH: usize = 24;
+/// Width of the right-aligned size columns in file listings
+const SIZE_COLUMN_WIDTH: usize = 9;
+/// Pieces printed per torrent with `--piece-map` by default
+pub const DEFAULT_MAX_PIECES: usize = 1000;
+/// Creator shown in `--creator-report` for torrents without a `created by` value
+const UNKNOWN_CREATOR: &str = "(unknown)";
 
 type Dict = HashMap<Vec<u8>, Value>;
 
@@ -25,15 +38,311 @@ pub struct TorrentInfo {
     files: Vec<PathBuf>,
 }
 
+/// Selectable column for the tabular torrent listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Column {
+    /// Torrent name
+    Name,
+    /// Total size
+    Size,
+    /// Number of files
+    Files,
+    /// Info hash
+    Hash,
+    /// Creation date
+    Date,
+    /// Number of trackers
+    Trackers,
+}
+
+/// Info hash version for the hash listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum HashAlgorithm {
+    /// SHA-1 info hash of v1 and hybrid torrents
+    V1,
+    /// SHA-256 info hash of v2 and hybrid torrents
+    V2,
+}
+
+/// Key used for grouping duplicate torrents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DuplicateKey {
+    /// Identical SHA-1 info hash
+    Hash,
+    /// Same name, file paths and sizes, regardless of piece length and trackers
+    Content,
+}
+
+/// Key used for ordering torrents when sorting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SortKey {
+    /// Total size
+    Size,
+    /// Torrent name
+    Name,
+    /// Creation date
+    Date,
+    /// Number of files
+    Files,
+}
+
+/// Short indicator shown after the torrent header with `--badges`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Badge {
+    /// The private flag is set
+    Private,
+    /// No trackers, peers are found through the DHT nodes
+    Dht,
+    /// Content can also be downloaded from web seeds
+    WebSeeds,
+}
+
+/// Tracker and extension filters from the command line, counting the torrents they skip
+struct MetadataFilter<'a> {
+    args: &'a Args,
+    skipped_trackers: usize,
+    skipped_extensions: usize,
+}
+
+impl Badge {
+    /// Get the badges that apply to the torrent in display order
+    fn for_torrent(torrent: &Torrent) -> Vec<Self> {
+        let mut badges = Vec::new();
+        if !torrent.can_use_dht() {
+            badges.push(Self::Private);
+        }
+        if torrent.trackers().is_empty() && torrent.is_trackerless_capable() {
+            badges.push(Self::Dht);
+        }
+        if !torrent.web_seeds().is_empty() {
+            badges.push(Self::WebSeeds);
+        }
+        badges
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Private => "[P]",
+            Self::Dht => "[DHT]",
+            Self::WebSeeds => "[WS]",
+        }
+    }
+
+    fn colored(self) -> ColoredString {
+        match self {
+            Self::Private => self.label().yellow(),
+            Self::Dht => self.label().cyan(),
+            Self::WebSeeds => self.label().magenta(),
+        }
+    }
+}
+
+impl HashAlgorithm {
+    /// Header text for the column
+    const fn header(self) -> &'static str {
+        match self {
+            Self::V1 => "v1",
+            Self::V2 => "v2",
+        }
+    }
+
+    /// Get the info hash as hex, or `-` if the torrent does not have metadata for this version
+    fn hash(self, torrent: &Torrent) -> String {
+        let hash = match self {
+            Self::V1 if torrent.info().is_v1() => torrent.info_hash(),
+            Self::V2 if torrent.info().is_v2() => torrent.info_hash_v2(),
+            _ => return "-".to_string(),
+        };
+        hash.map_or_else(|_| "-".to_string(), |hash| torrentinfo::to_hex(&hash))
+    }
+}
+
+impl DuplicateKey {
+    /// Get the grouping key of a torrent as hex
+    fn key(self, torrent: &Torrent) -> anyhow::Result<String> {
+        match self {
+            Self::Hash => Ok(torrentinfo::to_hex(&torrent.info_hash()?)),
+            Self::Content => Ok(torrent.content_fingerprint()),
+        }
+    }
+}
+
+impl Column {
+    /// Header text for the column
+    const fn header(self) -> &'static str {
+        match self {
+            Self::Name => "NAME",
+            Self::Size => "SIZE",
+            Self::Files => "FILES",
+            Self::Hash => "INFO HASH",
+            Self::Date => "CREATED",
+            Self::Trackers => "TRACKERS",
+        }
+    }
+
+    /// Get the formatted column value for a torrent
+    fn value(self, file: &Path, torrent: &Torrent) -> String {
+        match self {
+            Self::Name => torrent.content_name_or_source(file).into_owned(),
+            Self::Size => utils::format_file_size(torrent.total_size_bytes()),
+            Self::Files => torrent.num_files().to_string(),
+            Self::Hash => torrent
+                .info_hash()
+                .map_or_else(|_| "-".to_string(), |hash| torrentinfo::to_hex(&hash)),
+            Self::Date => torrent
+                .creation_date()
+                .map_or_else(|| "-".to_string(), utils::format_creation_date),
+            Self::Trackers => torrent.trackers().len().to_string(),
+        }
+    }
+
+    /// Pad the value to the column width, numeric columns are right-aligned
+    fn align(self, value: &str, width: usize) -> String {
+        match self {
+            Self::Size | Self::Files | Self::Trackers => format!("{value:>width$}"),
+            Self::Name | Self::Hash | Self::Date => format!("{value:<width$}"),
+        }
+    }
+}
+
+/// Torrent metadata used for sorting, shared by full torrents and lightweight summaries
+trait SortFields {
+    /// Total size in bytes
+    fn size(&self) -> u64;
+    /// Content name, falling back to the torrent filename
+    fn name_or_source<'a>(&'a self, file: &'a Path) -> Cow<'a, str>;
+    /// Creation date as a Unix timestamp
+    fn date(&self) -> Option<i64>;
+    /// Number of files
+    fn file_count(&self) -> usize;
+}
+
+impl SortKey {
+    /// Compare two torrents by this key, falling back to the torrent filename for ties
+    fn compare<T: SortFields>(self, (file_a, torrent_a): (&Path, &T), (file_b, torrent_b): (&Path, &T)) -> Ordering {
+        let ordering = match self {
+            Self::Size => torrent_a.size().cmp(&torrent_b.size()),
+            Self::Name => torrent_a.name_or_source(file_a).cmp(&torrent_b.name_or_source(file_b)),
+            Self::Date => torrent_a.date().cmp(&torrent_b.date()),
+            Self::Files => torrent_a.file_count().cmp(&torrent_b.file_count()),
+        };
+        ordering.then_with(|| file_a.file_name().cmp(&file_b.file_name()))
+    }
+}
+
+impl SortFields for Torrent {
+    fn size(&self) -> u64 {
+        self.total_size_bytes()
+    }
+
+    fn name_or_source<'a>(&'a self, file: &'a Path) -> Cow<'a, str> {
+        self.content_name_or_source(file)
+    }
+
+    fn date(&self) -> Option<i64> {
+        *self.creation_date()
+    }
+
+    fn file_count(&self) -> usize {
+        self.num_files()
+    }
+}
+
+impl SortFields for TorrentSummary {
+    fn size(&self) -> u64 {
+        self.total_size_bytes()
+    }
+
+    fn name_or_source<'a>(&'a self, file: &'a Path) -> Cow<'a, str> {
+        self.content_name_or_source(file)
+    }
+
+    fn date(&self) -> Option<i64> {
+        *self.creation_date()
+    }
+
+    fn file_count(&self) -> usize {
+        self.num_files()
+    }
+}
+
+impl<'a> MetadataFilter<'a> {
+    /// Check if the torrent passes the filters.
+    ///
+    /// The torrent is read without the piece hashes, since the filters only need the metadata.
+    /// Files that can not be parsed are kept so their errors are reported later.
+    fn keep(&mut self, file: &Path) -> bool {
+        let Ok(torrent) = utils::read_torrent_metadata(file) else {
+            return true;
+        };
+        let tracker_filter = (self.args.only_trackerless || self.args.only_tracked).then_some(self.args.only_tracked);
+        if tracker_filter.is_some_and(|tracked| torrent.trackers().is_empty() == tracked) {
+            self.skipped_trackers += 1;
+            false
+        } else if !self.args.contains_ext.is_empty() && !torrent.contains_extension(&self.args.contains_ext) {
+            self.skipped_extensions += 1;
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Log how many torrents each filter skipped
+    fn log_skipped(&self) {
+        if self.skipped_trackers > 0 {
+            let reason = if self.args.only_tracked { "without" } else { "with" };
+            log::info!("Skipped {} torrents {reason} trackers", self.skipped_trackers);
+        }
+        if self.skipped_extensions > 0 {
+            log::info!(
+                "Skipped {} torrents without files matching: {}",
+                self.skipped_extensions,
+                self.args.contains_ext.join(", ")
+            );
+        }
+    }
+
+    /// Create the filter, or `None` if no tracker or extension filter is selected
+    fn new(args: &'a Args) -> Option<Self> {
+        (args.only_trackerless || args.only_tracked || !args.contains_ext.is_empty()).then_some(Self {
+            args,
+            skipped_trackers: 0,
+            skipped_extensions: 0,
+        })
+    }
+}
+
 impl TorrentInfo {
     /// Create a new `TorrentInfo` instance from command line arguments
     pub fn new(args: Args) -> anyhow::Result<Self> {
-        if args.no_colour {
-            colored::control::set_override(false);
+        let input_paths = utils::resolve_input_paths(&args.paths)?;
+        let exclude = utils::build_exclude_set(&args.exclude)?;
+        let mut roots = Vec::with_capacity(input_paths.len());
+        let mut files: Vec<PathBuf> = Vec::new();
+        let mut seen: HashSet<PathBuf> = HashSet::new();
+        for input_path in &input_paths {
+            if utils::is_url(input_path) {
+                files.push(input_path.clone());
+                continue;
+            }
+            let (input_root, input_files) = utils::get_torrent_files(
+                input_path,
+                args.recursive,
+                args.max_depth,
+                args.include_hidden,
+                args.natural_sort,
+                &exclude,
+            )?;
+            roots.push(input_root);
+            files.extend(input_files.into_iter().filter(|file| seen.insert(file.clone())));
         }
 
-        let input_path = utils::resolve_input_path(args.path.as_deref())?;
-        let (root, files) = utils::get_torrent_files(&input_path, args.recursive, args.verbose)?;
+        let root = Self::display_root(&args, &roots, input_paths.len())?;
+
+        if let Some(mut filter) = MetadataFilter::new(&args) {
+            files.retain(|file| filter.keep(file));
+            filter.log_skipped();
+        }
 
         if files.is_empty() {
             anyhow::bail!("No torrent files found");
@@ -42,14 +351,201 @@ impl TorrentInfo {
         Ok(Self { args, root, files })
     }
 
+    /// Print the torrents from the input paths as they are found, without collecting and sorting the paths first.
+    ///
+    /// Output starts immediately and memory use does not grow with the number of torrents,
+    /// which matters for directories with hundreds of thousands of files.
+    /// Only input paths that overlap, such as a directory and one of its subdirectories,
+    /// remember the printed paths to skip duplicates.
+    /// The tracker and extension filters and the selected mode are applied to each torrent as it is found,
+    /// except for modes that report over all torrents, which run once on the collected paths.
+    /// The tradeoff is that torrents are printed in directory order instead of sorted by path.
+    pub fn stream(args: Args) -> anyhow::Result<()> {
+        let input_paths = utils::resolve_input_paths(&args.paths)?;
+        let exclude = utils::build_exclude_set(&args.exclude)?;
+        let roots: Vec<PathBuf> = input_paths
+            .iter()
+            .filter(|path| !utils::is_url(path))
+            .map(|path| {
+                if path.is_file() {
+                    path.parent().map_or_else(|| path.clone(), Path::to_path_buf)
+                } else {
+                    path.clone()
+                }
+            })
+            .collect();
+        let root = Self::display_root(&args, &roots, input_paths.len())?;
+        let mut info = Self {
+            args,
+            root,
+            files: Vec::new(),
+        };
+        let per_torrent = info.prints_per_torrent();
+        let mut filter = MetadataFilter::new(&info.args);
+
+        // Only overlapping inputs can yield the same file twice
+        let overlapping = input_paths.iter().enumerate().any(|(index, path)| {
+            input_paths
+                .iter()
+                .skip(index + 1)
+                .any(|other| path.starts_with(other) || other.starts_with(path))
+        });
+        let mut seen: HashSet<PathBuf> = HashSet::new();
+        let mut count: usize = 0;
+        for input_path in &input_paths {
+            let files: Box<dyn Iterator<Item = PathBuf>> = if utils::is_url(input_path) {
+                Box::new(std::iter::once(input_path.clone()))
+            } else if input_path.is_file() {
+                let (_, files) = utils::get_torrent_files(input_path, false, None, false, false, &exclude)?;
+                Box::new(files.into_iter())
+            } else {
+                Box::new(utils::iter_torrent_files(
+                    input_path,
+                    info.args.recursive,
+                    info.args.max_depth,
+                    info.args.include_hidden,
+                    &exclude,
+                ))
+            };
+            for file in files {
+                if overlapping && !seen.insert(file.clone()) {
+                    continue;
+                }
+                if filter.as_mut().is_some_and(|filter| !filter.keep(&file)) {
+                    continue;
+                }
+                count += 1;
+                info.files.push(file);
+                if per_torrent {
+                    if let Err(e) = info.run() {
+                        log::error!("{e:#}");
+                    }
+                    info.files.clear();
+                }
+            }
+        }
+        if let Some(filter) = &filter {
+            filter.log_skipped();
+        }
+
+        if count == 0 {
+            anyhow::bail!("No torrent files found");
+        }
+        if per_torrent { Ok(()) } else { info.run() }
+    }
+
+    /// Get the directory that torrent paths are displayed relative to.
+    ///
+    /// A single input is displayed relative to itself, or its parent directory for a file,
+    /// and multiple inputs relative to the working directory unless `--relative-to` is given.
+    fn display_root(args: &Args, roots: &[PathBuf], num_inputs: usize) -> anyhow::Result<PathBuf> {
+        match (&args.relative_to, roots, num_inputs) {
+            (Some(base), _, _) => utils::resolve_input_path(Some(base)),
+            (None, [root], 1) => Ok(root.clone()),
+            _ => std::env::current_dir().context("Failed to get current working directory"),
+        }
+    }
+
+    /// Create a `TorrentInfo` for a single torrent that is not read from a file
+    pub const fn standalone(args: Args) -> Self {
+        Self {
+            args,
+            root: PathBuf::new(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Create a `TorrentInfo` for watch mode, where the torrent files are not known up front
+    #[cfg(feature = "watch")]
+    pub const fn watching(args: Args, root: PathBuf) -> Self {
+        Self {
+            args,
+            root,
+            files: Vec::new(),
+        }
+    }
+
+    /// Print the filename header and information for a torrent file that has already been parsed
+    #[cfg(feature = "watch")]
+    pub fn print_parsed_torrent(&self, file: &Path, torrent: &Torrent) -> anyhow::Result<()> {
+        self.print_file_header(1, 1, file, 1, Some(torrent));
+        if self.args.everything {
+            return self.print_raw_data(file, INDENT);
+        }
+        self.print_torrent_sections(torrent);
+        Ok(())
+    }
+
     /// Run the torrent info display
     pub fn run(&self) -> anyhow::Result<()> {
-        self.print_torrent_files()
+        if self.args.dedupe_files {
+            self.print_duplicate_files()
+        } else if let Some(key) = self.args.find_dupes {
+            self.print_duplicate_torrents(key);
+            Ok(())
+        } else if self.args.rename_suggest || self.args.rename {
+            self.rename_torrent_files()
+        } else if self.args.json {
+            self.print_json()
+        } else if self.args.toml {
+            self.print_toml()
+        } else if let Some(output) = &self.args.html {
+            self.write_html(output)
+        } else if let Some(columns) = &self.args.columns {
+            self.print_table(columns)
+        } else if let Some(algorithms) = &self.args.hash_only {
+            self.print_hashes(algorithms)
+        } else if self.args.summary_line {
+            self.print_summary_lines()
+        } else if self.args.announce_url {
+            self.print_announce_urls();
+            Ok(())
+        } else if self.args.magnet || self.args.magnet_file {
+            self.print_magnet_links();
+            Ok(())
+        } else if self.args.stats {
+            self.print_stats();
+            Ok(())
+        } else if self.args.tracker_report {
+            self.print_tracker_report();
+            Ok(())
+        } else if self.args.creator_report {
+            self.print_creator_report();
+            Ok(())
+        } else if self.args.missing_comment || self.args.missing_creator || self.args.missing_date {
+            self.print_missing_metadata();
+            Ok(())
+        } else if self.args.flag_suspicious {
+            self.print_suspicious_torrents();
+            Ok(())
+        } else if let Some(directory) = &self.args.disk_usage {
+            self.print_disk_usage(directory);
+            Ok(())
+        } else if self.args.checksum_self {
+            self.print_health()
+        } else if self.args.piece_map {
+            self.print_piece_maps();
+            Ok(())
+        } else if self.args.recommend_piece_length {
+            self.print_piece_length_recommendations();
+            Ok(())
+        } else if self.args.info_hexdump {
+            self.print_info_hexdumps();
+            Ok(())
+        } else if self.args.pick {
+            self.pick_torrent()
+        } else if let Some(output) = &self.args.dump_pieces {
+            self.dump_pieces(output, false)
+        } else if let Some(output) = &self.args.dump_pieces_hex {
+            self.dump_pieces(output, true)
+        } else {
+            self.print_torrent_files()
+        }
     }
 
     /// Process all torrent files and print their information
     fn print_torrent_files(&self) -> anyhow::Result<()> {
-        if self.args.sort {
+        if self.sort_key().is_some() {
             self.print_torrents_sorted()
         } else {
             self.print_torrents();
@@ -62,89 +558,906 @@ impl TorrentInfo {
         let digits = utils::digit_count(num_files);
 
         for (number, file) in self.files.iter().enumerate() {
-            self.print_file_header(number + 1, num_files, file, digits);
-            if let Err(e) = self.print_single_torrent(file) {
-                eprintln!("{}", format!("Error: {e}").red());
+            if let Err(e) = self.print_single_torrent(number + 1, num_files, file, digits) {
+                log::error!("{e:#}");
+            }
+        }
+    }
+
+    /// Let the user choose one torrent from a numbered list and print its full details.
+    ///
+    /// The list and prompt are only shown when stdin is a terminal.
+    /// Otherwise a single selection is read from stdin,
+    /// and all torrents are printed as usual when no selection is given.
+    fn pick_torrent(&self) -> anyhow::Result<()> {
+        let interactive = std::io::stdin().is_terminal();
+        let count = self.files.len();
+        if interactive {
+            let digits = utils::digit_count(count);
+            for (number, file) in self.files.iter().enumerate() {
+                println!(
+                    "{:>digits$}{INDENT}{}",
+                    (number + 1).to_string().bold(),
+                    self.display_path(file)
+                );
             }
         }
+
+        let mut input = String::new();
+        let index = loop {
+            if interactive {
+                eprint!("Select a torrent [1-{count}]: ");
+                std::io::stderr().flush()?;
+            }
+            input.clear();
+            let bytes_read = std::io::stdin()
+                .read_line(&mut input)
+                .context("Failed to read selection")?;
+            if bytes_read == 0 && interactive {
+                return Ok(());
+            }
+            if input.trim().is_empty() && !interactive {
+                log::info!("No selection given, printing all torrents");
+                return self.print_torrent_files();
+            }
+            match utils::parse_selection(&input, count) {
+                Some(index) => break index,
+                None if interactive => eprintln!("Invalid selection: {}", input.trim()),
+                None => anyhow::bail!(
+                    "Invalid selection: {}, expected a number from 1 to {count}",
+                    input.trim()
+                ),
+            }
+        };
+
+        let file = &self.files[index];
+        println!("{}", self.display_path(file).bold());
+        let torrent = utils::read_torrent(file, self.args.strict)?;
+        self.print_info(&torrent);
+        Self::print_extra_info(&torrent);
+        self.print_files(&torrent);
+        Ok(())
     }
 
-    /// Print the file header with numbering (only shows index when multiple files)
-    fn print_file_header(&self, current: usize, total: usize, file: &Path, width: usize) {
-        let filename = utils::get_relative_path_or_filename(file, &self.root);
-        if total > 1 {
-            println!("{}", format!("{current:>0width$}/{total}: {filename}").bold());
+    /// Print the file header with numbering (only shows index when multiple files),
+    /// followed by the torrent badges with `--badges` if the torrent could be read
+    fn print_file_header(&self, current: usize, total: usize, file: &Path, width: usize, torrent: Option<&Torrent>) {
+        let filename = self.display_path(file);
+        let header = if total > 1 {
+            format!("{current:>0width$}/{total}: {filename}").bold()
         } else {
-            println!("{}", filename.bold());
+            filename.bold()
+        };
+        let badges = match torrent {
+            Some(torrent) if self.args.badges => Badge::for_torrent(torrent).into_iter().map(Badge::colored).join(" "),
+            _ => String::new(),
+        };
+        if badges.is_empty() {
+            println!("{header}");
+        } else {
+            println!("{header} {badges}");
         }
     }
 
+    /// Print the size and name of each torrent in sorted order followed by the total size.
+    ///
+    /// The total size is left out with `--no-size`.
+    /// Uses the lightweight summary parse since the piece hashes are not needed.
     fn print_torrents_sorted(&self) -> anyhow::Result<()> {
         let mut total_size: u64 = 0;
-        self.files
+        let mut torrents = self
+            .files
+            .iter()
+            .map(|file| utils::read_torrent_summary(file, self.args.strict).map(|torrent| (file, torrent)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        self.sort_torrents(&mut torrents);
+        for (file, torrent) in torrents {
+            total_size += torrent.total_size_bytes();
+            let size = utils::format_file_size(torrent.total_size_bytes());
+            let name = torrent.content_name_or_source(file);
+            println!("{:>10}   {name}", size.cyan());
+        }
+
+        if !self.args.no_size {
+            let total_str = utils::format_file_size(total_size);
+            println!("\n{}: {}", "Total size:".bold(), total_str.cyan());
+        }
+
+        Ok(())
+    }
+
+    /// Print a JSON report for each torrent.
+    ///
+    /// Reports are pretty-printed for terminals and written one per line otherwise, unless overridden.
+    fn print_json(&self) -> anyhow::Result<()> {
+        let mut torrents = self.load_torrents()?;
+        self.sort_torrents(&mut torrents);
+        let pretty = self.args.json_pretty || (!self.args.json_compact && std::io::stdout().is_terminal());
+        for (_, torrent) in torrents {
+            let report = TorrentReport::from_torrent(&torrent)?;
+            let json = if pretty {
+                serde_json::to_string_pretty(&report)?
+            } else {
+                serde_json::to_string(&report)?
+            };
+            println!("{json}");
+        }
+        Ok(())
+    }
+
+    /// Print the reports of all torrents as a single TOML document
+    #[cfg(feature = "toml")]
+    fn print_toml(&self) -> anyhow::Result<()> {
+        let mut torrents = self.load_torrents()?;
+        self.sort_torrents(&mut torrents);
+        let reports = torrents
+            .iter()
+            .map(|(_, torrent)| TorrentReport::from_torrent(torrent))
+            .collect::<Result<Vec<_>, _>>()?;
+        print!("{}", torrentinfo::report::to_toml(&reports)?);
+        Ok(())
+    }
+
+    /// TOML output is not available without the `toml` feature
+    #[cfg(not(feature = "toml"))]
+    #[allow(clippy::unused_self)]
+    fn print_toml(&self) -> anyhow::Result<()> {
+        anyhow::bail!("TOML output requires the `toml` feature")
+    }
+
+    /// Write the reports of all torrents as a single HTML page
+    fn write_html(&self, output: &Path) -> anyhow::Result<()> {
+        let mut torrents = self.load_torrents()?;
+        self.sort_torrents(&mut torrents);
+        let reports = torrents
+            .iter()
+            .map(|(_, torrent)| TorrentReport::from_torrent(torrent))
+            .collect::<Result<Vec<_>, _>>()?;
+        let page = html::render(&reports);
+
+        if output == Path::new("-") {
+            print!("{page}");
+        } else {
+            std::fs::write(output, page).with_context(|| format!("Failed to write {}", output.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Print an aligned table containing only the selected columns
+    fn print_table(&self, columns: &[Column]) -> anyhow::Result<()> {
+        let mut torrents = self.load_torrents()?;
+        self.sort_torrents(&mut torrents);
+
+        let rows: Vec<Vec<String>> = torrents
+            .iter()
+            .map(|(file, torrent)| columns.iter().map(|column| column.value(file, torrent)).collect())
+            .collect();
+
+        let widths: Vec<usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                rows.iter()
+                    .map(|row| row[index].chars().count())
+                    .chain(std::iter::once(column.header().len()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let header = columns
+            .iter()
+            .zip(&widths)
+            .map(|(column, width)| column.align(column.header(), *width))
+            .join("  ");
+        println!("{}", header.trim_end().bold());
+        for row in rows {
+            let line = columns
+                .iter()
+                .zip(&widths)
+                .zip(&row)
+                .map(|((column, width), value)| column.align(value, *width))
+                .join("  ");
+            println!("{}", line.trim_end());
+        }
+
+        Ok(())
+    }
+
+    /// Print the requested info hashes of each torrent as tab-separated columns after a header line
+    fn print_hashes(&self, algorithms: &[HashAlgorithm]) -> anyhow::Result<()> {
+        let mut torrents = self.load_torrents()?;
+        self.sort_torrents(&mut torrents);
+
+        let header = algorithms.iter().map(|algorithm| algorithm.header()).join("\t");
+        println!("{header}\tfile");
+        for (file, torrent) in torrents {
+            let hashes = algorithms.iter().map(|algorithm| algorithm.hash(&torrent)).join("\t");
+            println!("{hashes}\t{}", self.display_path(file));
+        }
+
+        Ok(())
+    }
+
+    /// Print a single aligned summary line for each torrent
+    fn print_summary_lines(&self) -> anyhow::Result<()> {
+        let mut torrents = self
+            .files
             .iter()
-            .map(|file| {
-                Torrent::from_file(file)
-                    .map(|torrent| (file, torrent))
-                    .map_err(anyhow::Error::from)
+            .map(|file| utils::read_torrent_peek(file, self.args.strict).map(|torrent| (file, torrent)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        self.sort_torrents(&mut torrents);
+
+        let rows: Vec<[String; 4]> = torrents
+            .iter()
+            .map(|(file, torrent)| {
+                [
+                    torrent.content_name_or_source(file).into_owned(),
+                    utils::format_file_size(torrent.total_size_bytes()),
+                    torrent.num_files().to_string(),
+                    torrent
+                        .info_hash()
+                        .map(|info_hash| torrentinfo::to_hex(&info_hash[..4]))
+                        .unwrap_or_default(),
+                ]
             })
-            .collect::<anyhow::Result<Vec<_>>>()?
+            .collect();
+
+        let name_width = rows.iter().map(|row| row[0].chars().count()).max().unwrap_or(0);
+        let size_width = rows.iter().map(|row| row[1].len()).max().unwrap_or(0);
+        let files_width = rows.iter().map(|row| row[2].len()).max().unwrap_or(0);
+        for [name, size, num_files, info_hash] in rows {
+            println!(
+                "{name:<name_width$} | {:>size_width$} | {num_files:>files_width$} | {info_hash}",
+                size.cyan()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Print files that are shared between multiple torrents.
+    ///
+    /// Files are considered identical when both their relative path and length match.
+    fn print_duplicate_files(&self) -> anyhow::Result<()> {
+        let mut shared_files: BTreeMap<(String, i64), Vec<String>> = BTreeMap::new();
+        for (file, torrent) in self.load_torrents()? {
+            let torrent_name = self.display_path(file);
+            for file in torrent.files_normalized() {
+                let torrents = shared_files.entry((file.path().join("/"), file.length())).or_default();
+                if !torrents.contains(&torrent_name) {
+                    torrents.push(torrent_name.clone());
+                }
+            }
+        }
+
+        let duplicates: Vec<_> = shared_files
             .into_iter()
-            .sorted_by(|(_, a), (_, b)| a.total_size().cmp(&b.total_size()))
-            .for_each(|(file, torrent)| {
-                total_size += torrent.total_size() as u64;
-                let size = utils::format_file_size(torrent.total_size() as f64);
-                let name: Cow<str> = torrent.name().as_deref().map_or_else(
-                    || {
-                        file.file_stem()
-                            .and_then(|s| s.to_str())
-                            .map_or(Cow::Borrowed("unknown"), Cow::Borrowed)
-                    },
-                    Cow::Borrowed,
+            .filter(|(_, torrents)| torrents.len() > 1)
+            .collect();
+
+        if duplicates.is_empty() {
+            println!("No duplicate files found");
+            return Ok(());
+        }
+
+        let mut duplicate_size: u64 = 0;
+        for ((path, length), torrents) in &duplicates {
+            let length = u64::try_from(*length).unwrap_or(0);
+            duplicate_size += length * (torrents.len() as u64 - 1);
+            let size = utils::format_file_size(length);
+            println!("{:>10}   {}", size.cyan(), path.bold());
+            for torrent_name in torrents {
+                println!("{INDENT}{INDENT}{torrent_name}");
+            }
+        }
+
+        let total_str = utils::format_file_size(duplicate_size);
+        println!(
+            "\n{} {} in {} files",
+            "Duplicate size:".bold(),
+            total_str.cyan(),
+            duplicates.len()
+        );
+
+        Ok(())
+    }
+
+    /// Print groups of torrents that share the same info hash or content fingerprint
+    fn print_duplicate_torrents(&self, key: DuplicateKey) {
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for file in &self.files {
+            match utils::read_torrent(file, self.args.strict).and_then(|torrent| key.key(&torrent)) {
+                Ok(group) => groups.entry(group).or_default().push(self.display_path(file)),
+                Err(e) => log::error!("{}: {e:#}", self.display_path(file)),
+            }
+        }
+
+        let duplicates: Vec<_> = groups.into_iter().filter(|(_, torrents)| torrents.len() > 1).collect();
+        if duplicates.is_empty() {
+            println!("No duplicate torrents found");
+            return;
+        }
+        for (group, torrents) in &duplicates {
+            println!("{}", group.bold());
+            for torrent_name in torrents {
+                println!("{INDENT}{torrent_name}");
+            }
+        }
+        println!("\n{} {}", "Duplicate groups:".bold(), duplicates.len());
+    }
+
+    /// Print a rename suggestion for each torrent file based on the torrent name.
+    ///
+    /// Renames the files when the rename option is enabled.
+    /// Existing files are never overwritten, a numbered suffix is added instead.
+    fn rename_torrent_files(&self) -> anyhow::Result<()> {
+        for file in &self.files {
+            let filename = self.display_path(file);
+            let torrent = match utils::read_torrent(file, self.args.strict) {
+                Ok(torrent) => torrent,
+                Err(e) => {
+                    log::error!("{filename}: {e:#}");
+                    continue;
+                }
+            };
+            let Some(safe_name) = torrent.safe_name() else {
+                log::warn!("{filename}: torrent does not have a name");
+                continue;
+            };
+
+            let suggested_name = format!("{safe_name}.{}", utils::TORRENT_EXTENSION);
+            if file.file_name().is_some_and(|name| name == suggested_name.as_str()) {
+                println!("{filename}: {}", "name already matches".green());
+                continue;
+            }
+
+            if self.args.rename {
+                let target = utils::get_unique_path(&file.with_file_name(&suggested_name));
+                std::fs::rename(file, &target)
+                    .with_context(|| format!("Failed to rename {} to {}", file.display(), target.display()))?;
+                let new_name = self.display_path(&target);
+                println!("{filename} -> {}", new_name.green());
+            } else {
+                println!("{filename} -> {}", suggested_name.cyan());
+            }
+        }
+        Ok(())
+    }
+
+    /// Print aggregate statistics over all torrents
+    fn print_stats(&self) {
+        let mut num_torrents: usize = 0;
+        let mut num_files: usize = 0;
+        let mut total_size: u64 = 0;
+        let mut num_private: usize = 0;
+        let mut num_trackerless: usize = 0;
+        let mut num_udp_only: usize = 0;
+        let mut schemes: HashMap<String, usize> = HashMap::new();
+        for file in &self.files {
+            let torrent = match utils::read_torrent(file, self.args.strict) {
+                Ok(torrent) => torrent,
+                Err(e) => {
+                    log::error!("{}: {e:#}", self.display_path(file));
+                    continue;
+                }
+            };
+            num_torrents += 1;
+            num_files += torrent.num_files();
+            total_size += torrent.total_size_bytes();
+            if torrent.info.private().is_some_and(|private| private > 0) {
+                num_private += 1;
+            }
+            let torrent_schemes = torrent.tracker_schemes();
+            if torrent_schemes.is_empty() {
+                num_trackerless += 1;
+            } else if torrent_schemes.keys().all(|scheme| scheme == "udp") {
+                num_udp_only += 1;
+            }
+            for (scheme, count) in torrent_schemes {
+                *schemes.entry(scheme).or_insert(0) += count;
+            }
+        }
+
+        Self::print_line("torrents", &num_torrents);
+        Self::print_line("files", &num_files);
+        Self::print_line("total size", &utils::format_file_size(total_size).cyan());
+        Self::print_line("private", &num_private);
+        Self::print_line("trackerless", &num_trackerless);
+        Self::print_line("udp trackers only", &num_udp_only);
+        if !schemes.is_empty() {
+            Self::print_line("tracker schemes", &format_scheme_counts(&schemes));
+        }
+    }
+
+    /// Print the number of torrents using each tracker URL, sorted by count and then by URL
+    fn print_tracker_report(&self) {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for file in &self.files {
+            match utils::read_torrent(file, self.args.strict) {
+                Ok(torrent) => {
+                    for tracker in torrent.trackers() {
+                        *counts.entry(tracker.to_string()).or_insert(0) += 1;
+                    }
+                }
+                Err(e) => log::error!("{}: {e:#}", self.display_path(file)),
+            }
+        }
+        if counts.is_empty() {
+            log::info!("No trackers found");
+            return;
+        }
+
+        let counts: Vec<(String, usize)> = counts
+            .into_iter()
+            .sorted_by(|(a_url, a_count), (b_url, b_count)| b_count.cmp(a_count).then_with(|| a_url.cmp(b_url)))
+            .collect();
+        let width = counts.first().map_or(1, |(_, count)| utils::digit_count(*count));
+        for (url, count) in counts {
+            println!("{count:>width$}{INDENT}{url}");
+        }
+    }
+
+    /// Print the number of torrents made with each creation tool, sorted by count and then by name.
+    ///
+    /// Torrents without a `created by` value are counted as `(unknown)`.
+    fn print_creator_report(&self) {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for file in &self.files {
+            match utils::read_torrent(file, self.args.strict) {
+                Ok(torrent) => {
+                    let creator = torrent
+                        .created_by()
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|creator| !creator.is_empty())
+                        .unwrap_or(UNKNOWN_CREATOR);
+                    *counts.entry(creator.to_string()).or_insert(0) += 1;
+                }
+                Err(e) => log::error!("{}: {e:#}", self.display_path(file)),
+            }
+        }
+
+        let counts: Vec<(String, usize)> = counts
+            .into_iter()
+            .sorted_by(|(a_name, a_count), (b_name, b_count)| b_count.cmp(a_count).then_with(|| a_name.cmp(b_name)))
+            .collect();
+        let width = counts.first().map_or(1, |(_, count)| utils::digit_count(*count));
+        for (creator, count) in counts {
+            println!("{count:>width$}{INDENT}{creator}");
+        }
+    }
+
+    /// Print the torrents that lack any of the selected metadata fields, along with the missing fields
+    fn print_missing_metadata(&self) {
+        let is_missing = |value: &Option<String>| value.as_deref().is_none_or(|value| value.trim().is_empty());
+        for file in &self.files {
+            let filename = self.display_path(file);
+            let torrent = match utils::read_torrent(file, self.args.strict) {
+                Ok(torrent) => torrent,
+                Err(e) => {
+                    log::error!("{filename}: {e:#}");
+                    continue;
+                }
+            };
+            let missing: Vec<&str> = [
+                (self.args.missing_comment && is_missing(torrent.comment()), "comment"),
+                (
+                    self.args.missing_creator && is_missing(torrent.created_by()),
+                    "created by",
+                ),
+                (
+                    self.args.missing_date && torrent.creation_date().is_none(),
+                    "creation date",
+                ),
+            ]
+            .into_iter()
+            .filter_map(|(missing, field)| missing.then_some(field))
+            .collect();
+            if !missing.is_empty() {
+                println!("{}: {}", filename.bold(), missing.join(", ").yellow());
+            }
+        }
+    }
+
+    /// Print the torrents that look fake or spammy with the traits that flagged them
+    fn print_suspicious_torrents(&self) {
+        for file in &self.files {
+            let filename = self.display_path(file);
+            match utils::read_torrent(file, self.args.strict) {
+                Ok(torrent) => {
+                    let traits = torrent.looks_suspicious();
+                    if !traits.is_empty() {
+                        println!("{}: {}", filename.bold(), traits.iter().join(", ").yellow());
+                    }
+                }
+                Err(e) => log::error!("{filename}: {e:#}"),
+            }
+        }
+    }
+
+    /// Print how much of each torrent is present in the download directory, by file size only
+    fn print_disk_usage(&self, directory: &Path) {
+        let num_files = self.files.len();
+        let digits = utils::digit_count(num_files);
+
+        for (number, file) in self.files.iter().enumerate() {
+            let torrent = utils::read_torrent(file, self.args.strict);
+            self.print_file_header(number + 1, num_files, file, digits, torrent.as_ref().ok());
+            let torrent = match torrent {
+                Ok(torrent) => torrent,
+                Err(e) => {
+                    log::error!("{e:#}");
+                    continue;
+                }
+            };
+            let matches = torrent.match_existing(directory);
+            let expected: u64 = matches.iter().map(|file_match| file_match.length).sum();
+            let present: u64 = matches.iter().map(FileMatch::present_bytes).sum();
+            let complete_files = matches.iter().filter(|file_match| file_match.is_complete()).count();
+            let percentage = if expected == 0 {
+                100.0
+            } else {
+                present as f64 / expected as f64 * 100.0
+            };
+
+            Self::print_line("torrent size", &utils::format_file_size(expected));
+            Self::print_line("on disk", &utils::format_file_size(present));
+            Self::print_line("difference", &utils::format_file_size(expected - present));
+            Self::print_line("files", &format!("{complete_files}/{} complete", matches.len()));
+            let percentage_str = format!("{percentage:.2}%");
+            if present == expected {
+                Self::print_line("complete", &percentage_str.green());
+            } else {
+                Self::print_line("complete", &percentage_str.yellow());
+            }
+        }
+    }
+
+    /// Print the health grade of each torrent file with the problems found,
+    /// failing if any of them is corrupt
+    fn print_health(&self) -> anyhow::Result<()> {
+        let mut corrupt = 0;
+        for file in &self.files {
+            let filename = self.display_path(file);
+            let report = match utils::read_torrent_bytes(file) {
+                Ok(buf) => health::check(&buf),
+                Err(e) => {
+                    log::error!("{e:#}");
+                    corrupt += 1;
+                    continue;
+                }
+            };
+            let grade = format!("{:<10}", report.grade);
+            let grade = match report.grade {
+                Grade::Ok => grade.green(),
+                Grade::Suspicious => grade.yellow(),
+                Grade::Corrupt => grade.red(),
+            };
+            println!("{grade}  {filename}");
+            for problem in &report.problems {
+                println!("{INDENT}{}", problem.dimmed());
+            }
+            if report.grade == Grade::Corrupt {
+                corrupt += 1;
+            }
+        }
+        if corrupt > 0 {
+            anyhow::bail!("{corrupt} of {} torrent files are corrupt", self.files.len());
+        }
+        Ok(())
+    }
+
+    /// Print the files and byte ranges covered by each piece, up to `--max-pieces` pieces per torrent
+    fn print_piece_maps(&self) {
+        let num_files = self.files.len();
+        let digits = utils::digit_count(num_files);
+
+        for (number, file) in self.files.iter().enumerate() {
+            let torrent = utils::read_torrent(file, self.args.strict);
+            self.print_file_header(number + 1, num_files, file, digits, torrent.as_ref().ok());
+            let torrent = match torrent {
+                Ok(torrent) => torrent,
+                Err(e) => {
+                    log::error!("{e:#}");
+                    continue;
+                }
+            };
+            let paths: Vec<String> = torrent
+                .files_normalized()
+                .iter()
+                .map(|file| file.path().join("/"))
+                .collect();
+            let num_pieces = torrent.num_pieces();
+            let shown = num_pieces.min(self.args.max_pieces);
+            let index_width = utils::digit_count(shown.saturating_sub(1));
+
+            for (piece, spans) in torrent.piece_file_map().take(shown).enumerate() {
+                for (span_number, span) in spans.iter().enumerate() {
+                    let index = if span_number == 0 {
+                        piece.to_string()
+                    } else {
+                        String::new()
+                    };
+                    let path = paths.get(span.file_index).map_or("?", String::as_str);
+                    println!(
+                        "{INDENT}{index:>index_width$}{INDENT}{path} {}",
+                        format!("bytes {}-{}", span.range.start, span.range.end.saturating_sub(1)).dimmed()
+                    );
+                }
+            }
+            if shown < num_pieces {
+                println!(
+                    "{INDENT}{}",
+                    format!("... {} more pieces, use --max-pieces to show more", num_pieces - shown).dimmed()
                 );
-                println!("{:>10}   {name}", size.cyan());
+            }
+        }
+    }
+
+    /// Print the current and recommended piece length for each torrent
+    fn print_piece_length_recommendations(&self) {
+        let num_files = self.files.len();
+        let digits = utils::digit_count(num_files);
+
+        for (number, file) in self.files.iter().enumerate() {
+            let torrent = utils::read_torrent(file, self.args.strict);
+            self.print_file_header(number + 1, num_files, file, digits, torrent.as_ref().ok());
+            match torrent {
+                Ok(torrent) => {
+                    let total_size = torrent.total_size_bytes();
+                    let current = torrent.piece_length_bytes();
+                    let recommended = u64::from(torrentinfo::recommend_piece_length(total_size));
+                    let describe = |length: u64| {
+                        format!(
+                            "{} ({} pieces)",
+                            utils::format_piece_length(length),
+                            total_size.div_ceil(length.max(1))
+                        )
+                    };
+                    Self::print_line("piece length", &describe(current));
+                    if recommended == current {
+                        Self::print_line("recommended", &describe(recommended).green());
+                    } else {
+                        Self::print_line("recommended", &describe(recommended).yellow());
+                    }
+                }
+                Err(e) => log::error!("{e:#}"),
+            }
+        }
+    }
+
+    /// Print the full announce request URL for the primary HTTP tracker of each torrent
+    fn print_announce_urls(&self) {
+        let num_files = self.files.len();
+        let digits = utils::digit_count(num_files);
+        let peer_id = utils::generate_peer_id();
+
+        for (number, file) in self.files.iter().enumerate() {
+            let torrent = utils::read_torrent(file, self.args.strict);
+            self.print_file_header(number + 1, num_files, file, digits, torrent.as_ref().ok());
+            let result = torrent.and_then(|torrent| {
+                let tracker = torrent
+                    .trackers()
+                    .into_iter()
+                    .find(|url| url.starts_with("http://") || url.starts_with("https://"));
+                match tracker {
+                    Some(tracker) => {
+                        let url = torrent.announce_request_url(tracker, &peer_id, DEFAULT_PORT)?;
+                        Self::print_line("announce request", &url);
+                        if let Some(scrape) = utils::scrape_url(tracker) {
+                            Self::print_line("scrape url", &scrape);
+                        }
+                    }
+                    None => Self::print_line("announce request", &"no HTTP tracker".yellow()),
+                }
+                Ok(())
             });
+            if let Err(e) = result {
+                log::error!("{e:#}");
+            }
+        }
+    }
 
-        let total_str = utils::format_file_size(total_size as f64);
-        println!("\n{}: {}", "Total size:".bold(), total_str.cyan());
+    /// Print a magnet link for each torrent, or write it to a `.magnet` file next to the torrent.
+    ///
+    /// Links are prefixed with the torrent filename in recursive mode.
+    fn print_magnet_links(&self) {
+        for file in &self.files {
+            let filename = self.display_path(file);
+            let result = utils::read_torrent(file, self.args.strict).and_then(|torrent| {
+                let magnet = torrent.magnet_link()?;
+                if self.args.magnet_file {
+                    if utils::is_url(file) {
+                        anyhow::bail!("Can not write a magnet file for a URL");
+                    }
+                    let magnet_path = file.with_extension(utils::MAGNET_EXTENSION);
+                    std::fs::write(&magnet_path, format!("{magnet}\n"))
+                        .with_context(|| format!("Failed to write {}", magnet_path.display()))?;
+                    let magnet_name = self.display_path(&magnet_path);
+                    println!("{filename} -> {}", magnet_name.green());
+                } else if self.args.recursive {
+                    println!("{}: {magnet}", filename.bold());
+                } else {
+                    println!("{magnet}");
+                }
+                Ok(())
+            });
+            if let Err(e) = result {
+                log::error!("{filename}: {e:#}");
+            }
+        }
+    }
+
+    /// Print the bencoded info dictionary of each torrent as a hexdump
+    fn print_info_hexdumps(&self) {
+        let num_files = self.files.len();
+        let digits = utils::digit_count(num_files);
+
+        for (number, file) in self.files.iter().enumerate() {
+            let torrent = utils::read_torrent(file, self.args.strict);
+            self.print_file_header(number + 1, num_files, file, digits, torrent.as_ref().ok());
+            match torrent.and_then(|torrent| Ok(torrent.info_bytes()?)) {
+                Ok(info) => println!("{}", utils::hexdump(&info)),
+                Err(e) => log::error!("{e:#}"),
+            }
+        }
+    }
 
+    /// Write the piece hashes of a single torrent to the output file or stdout.
+    ///
+    /// Raw mode writes the concatenated hashes verbatim,
+    /// hex mode writes one 40-character hash per line.
+    fn dump_pieces(&self, output: &Path, hex: bool) -> anyhow::Result<()> {
+        let [file] = self.files.as_slice() else {
+            anyhow::bail!(
+                "Dumping pieces requires a single torrent file, found {}",
+                self.files.len()
+            );
+        };
+        let torrent = utils::read_torrent(file, self.args.strict)?;
+        let pieces = torrent.info.pieces();
+        let data = if hex {
+            let mut lines = String::with_capacity(pieces.len() * 2 + torrent.num_pieces());
+            for hash in pieces.chunks(torrentinfo::PIECE_HASH_LENGTH) {
+                lines.push_str(&torrentinfo::to_hex(hash));
+                lines.push('\n');
+            }
+            lines.into_bytes()
+        } else {
+            pieces.to_vec()
+        };
+
+        if output == Path::new("-") {
+            std::io::stdout()
+                .write_all(&data)
+                .context("Failed to write pieces to stdout")?;
+        } else {
+            std::fs::write(output, &data).with_context(|| format!("Failed to write {}", output.display()))?;
+        }
         Ok(())
     }
 
-    /// Print information for a single torrent file
-    fn print_single_torrent(&self, filepath: &Path) -> anyhow::Result<()> {
-        if self.args.everything {
-            Self::print_raw_data(filepath, INDENT)
+    /// Load all torrent files, failing on the first file that can not be parsed
+    fn load_torrents(&self) -> anyhow::Result<Vec<(&PathBuf, Torrent)>> {
+        self.files
+            .iter()
+            .map(|file| utils::read_torrent(file, self.args.strict).map(|torrent| (file, torrent)))
+            .collect()
+    }
+
+    /// Get the displayed path of a torrent file relative to the display root.
+    ///
+    /// Paths use `/` as the separator unless `--native-separators` is set.
+    fn display_path(&self, path: &Path) -> String {
+        let path = utils::get_relative_path_or_filename(path, &self.root, self.args.raw_names);
+        if self.args.native_separators {
+            path
         } else {
-            self.print_torrent_info(filepath)
+            utils::forward_slashes(path)
         }
     }
 
-    /// Print information for a single torrent file
-    fn print_torrent_info(&self, filepath: &Path) -> anyhow::Result<()> {
-        let torrent = Torrent::from_file(filepath)?;
+    /// Check if the selected mode prints each torrent on its own, so `--no-sort-paths` can print torrents as they are found.
+    ///
+    /// Reports over all torrents, aligned tables and single-torrent modes need the whole list first.
+    const fn prints_per_torrent(&self) -> bool {
+        !(self.args.dedupe_files
+            || self.args.find_dupes.is_some()
+            || self.args.toml
+            || self.args.html.is_some()
+            || self.args.columns.is_some()
+            || self.args.hash_only.is_some()
+            || self.args.summary_line
+            || self.args.stats
+            || self.args.tracker_report
+            || self.args.creator_report
+            || self.args.checksum_self
+            || self.args.pick
+            || self.args.dump_pieces.is_some()
+            || self.args.dump_pieces_hex.is_some())
+    }
 
-        Self::print_info(&torrent);
-        if self.args.details {
-            Self::print_extra_info(&torrent);
+    /// Selected sort key, `--sort` alone sorts by size
+    fn sort_key(&self) -> Option<SortKey> {
+        self.args.sort_by.or_else(|| self.args.sort.then_some(SortKey::Size))
+    }
+
+    /// Sort loaded torrents by the selected key, or keep the input order when not sorting
+    fn sort_torrents<T: SortFields>(&self, torrents: &mut [(&PathBuf, T)]) {
+        let Some(key) = self.sort_key() else {
+            return;
+        };
+        torrents
+            .sort_by(|(file_a, torrent_a), (file_b, torrent_b)| key.compare((file_a, torrent_a), (file_b, torrent_b)));
+        if self.args.reverse {
+            torrents.reverse();
         }
-        if self.args.files {
-            Self::print_files(&torrent);
+    }
+
+    /// Print the file header and information for a single torrent file.
+    ///
+    /// The torrent is only parsed for the raw `--everything` output when the header shows badges.
+    fn print_single_torrent(&self, current: usize, total: usize, filepath: &Path, width: usize) -> anyhow::Result<()> {
+        if self.args.everything {
+            let torrent = self
+                .args
+                .badges
+                .then(|| utils::read_torrent(filepath, self.args.strict).ok())
+                .flatten();
+            self.print_file_header(current, total, filepath, width, torrent.as_ref());
+            return self.print_raw_data(filepath, INDENT);
         }
+        let torrent = utils::read_torrent(filepath, self.args.strict);
+        self.print_file_header(current, total, filepath, width, torrent.as_ref().ok());
+        self.print_torrent_sections(&torrent?);
+        Ok(())
+    }
 
+    /// Decode a base64-encoded torrent, or read it from stdin for "-", and print its information
+    pub fn print_base64_torrent(&self, input: &str) -> anyhow::Result<()> {
+        let torrent = if input == "-" {
+            let mut buffer = String::new();
+            std::io::stdin()
+                .read_to_string(&mut buffer)
+                .context("Failed to read base64 input from stdin")?;
+            utils::decode_base64_torrent(&buffer, self.args.strict)?
+        } else {
+            utils::decode_base64_torrent(input, self.args.strict)?
+        };
+        self.print_torrent_sections(&torrent);
         Ok(())
     }
 
+    /// Print the basic information and the sections selected on the command line
+    fn print_torrent_sections(&self, torrent: &Torrent) {
+        self.print_info(torrent);
+        if self.args.verbose {
+            Self::print_piece_health(torrent);
+            Self::print_tracker_redundancy(torrent);
+            Self::print_privacy_warnings(torrent);
+        }
+        if self.args.details {
+            Self::print_extra_info(torrent);
+            if self.args.pieces_bar {
+                self.print_pieces_bar(torrent);
+            }
+        }
+        if self.args.files {
+            self.print_files(torrent);
+        }
+    }
+
     /// Print basic torrent information
-    fn print_info(torrent: &Torrent) {
+    fn print_info(&self, torrent: &Torrent) {
         if let Some(name) = torrent.name() {
             Self::print_line("name", &name);
         }
         if let Some(comment) = &torrent.comment() {
             Self::print_line("comment", comment);
         }
-        if let Some(announce_url) = &torrent.announce() {
-            Self::print_line("announce url", announce_url);
+        if let Some(announce_url) = torrent.primary_tracker() {
+            Self::print_line("announce url", &announce_url);
         }
         if let Some(created_by) = &torrent.created_by() {
             Self::print_line("created by", created_by);
@@ -160,14 +1473,52 @@ impl TorrentInfo {
         let files = torrent.num_files();
         Self::print_line("num files", &files);
 
-        let size_str = utils::format_file_size(torrent.total_size() as f64);
-        Self::print_line("total size", &size_str.cyan());
+        if !self.args.no_size {
+            let size_str = utils::format_file_size(torrent.total_size_bytes());
+            Self::print_line("total size", &size_str.cyan());
+        }
 
-        let info_hash_str = match torrent.info_hash() {
-            Ok(info_hash) => torrentinfo::to_hex(&info_hash),
-            Err(e) => format!("Could not calculate info hash: {e}"),
+        // Calculating the info hash re-serializes the info dictionary
+        if !self.args.no_hash {
+            let info_hash_str = match torrent.info_hash() {
+                Ok(info_hash) => torrentinfo::to_hex(&info_hash),
+                Err(e) => format!("Could not calculate info hash: {:#}", anyhow::Error::from(e)),
+            };
+            Self::print_line("info hash", &info_hash_str);
+        }
+    }
+
+    /// Print an advisory if the piece count is outside the recommended range
+    fn print_piece_health(torrent: &Torrent) {
+        let advisory = match torrent.piece_count_health() {
+            PieceHealth::Ok => return,
+            PieceHealth::TooFew => format!(
+                "only {} pieces, a smaller piece length would be more efficient",
+                torrent.num_pieces()
+            ),
+            PieceHealth::TooMany => format!(
+                "{} pieces, a larger piece length would make the torrent file smaller",
+                torrent.num_pieces()
+            ),
         };
-        Self::print_line("info hash", &info_hash_str);
+        Self::print_line("piece advisory", &advisory.yellow());
+    }
+
+    /// Print an advisory when the torrent depends on a single tracker without DHT nodes to fall back on
+    fn print_tracker_redundancy(torrent: &Torrent) {
+        if torrent.tracker_count() == 1 && torrent.num_nodes() == 0 {
+            Self::print_line(
+                "tracker advisory",
+                &"single tracker and no DHT nodes, peers can not be found if the tracker is down".yellow(),
+            );
+        }
+    }
+
+    /// Print advisories for trackers that conflict with the private flag
+    fn print_privacy_warnings(torrent: &Torrent) {
+        for warning in torrent.tracker_privacy_warnings() {
+            Self::print_line("privacy advisory", &warning.yellow());
+        }
     }
 
     /// Print detailed torrent information
@@ -175,6 +1526,17 @@ impl TorrentInfo {
         let piece_length_str = format!("[{} Bytes]", torrent.info.pieces().len()).cyan().bold();
         Self::print_line("piece length", &piece_length_str);
 
+        let num_pieces_str = format!(
+            "{} (last piece {} bytes)",
+            torrent.num_pieces(),
+            torrent.last_piece_size()
+        );
+        Self::print_line("num pieces", &num_pieces_str);
+        Self::print_line(
+            "piece hash overhead",
+            &utils::format_file_size(torrent.pieces_overhead_bytes() as u64).cyan(),
+        );
+
         if let Some(path) = &torrent.info.path {
             Self::print_line("path", &format!("{path:#?}").cyan());
         }
@@ -182,48 +1544,193 @@ impl TorrentInfo {
         if let Some(private) = torrent.info.private() {
             Self::print_line("private", &utils::colorize_bool(private > &0));
         }
+
+        Self::print_line("dht allowed", &utils::colorize_bool(torrent.can_use_dht()));
+        Self::print_line(
+            "trackerless capable",
+            &utils::colorize_bool(torrent.is_trackerless_capable()),
+        );
+
+        if let Some(meta_version) = torrent.info.meta_version() {
+            Self::print_line("meta version", meta_version);
+        }
+
+        Self::print_line("content fingerprint", &torrent.content_fingerprint());
+
+        Self::print_line("category", &torrent.guess_category());
+
+        let schemes = torrent.tracker_schemes();
+        if !schemes.is_empty() {
+            Self::print_line("tracker schemes", &format_scheme_counts(&schemes));
+        }
+
+        if torrent.files().is_some()
+            && let Some(largest) = torrent.largest_file()
+        {
+            let size = utils::format_file_size(u64::try_from(largest.length()).unwrap_or(0));
+            Self::print_line(
+                "largest file",
+                &format!("{} ({})", largest.path().join("/"), size.cyan()),
+            );
+        }
     }
 
-    /// Print a list of all the files in the torrent.
-    fn print_files(torrent: &Torrent) {
-        let mut files_list: Vec<torrentinfo::File> = Vec::new();
-        let files = torrent.files().as_ref().unwrap_or_else(|| {
-            let name = torrent.name().to_owned().unwrap_or_default();
-            let f = torrentinfo::File::new(torrent.total_size(), vec![name]);
-            files_list = vec![f];
-            &files_list
+    /// Print the piece count as a bar scaled to the configured or terminal width
+    fn print_pieces_bar(&self, torrent: &Torrent) {
+        let reserved_width = INDENT.len() + COLUMN_WIDTH + 1 + BAR_LABEL_WIDTH;
+        let width = self.args.bar_width.unwrap_or_else(|| {
+            utils::terminal_width().map_or(utils::DEFAULT_BAR_WIDTH, |width| width.saturating_sub(reserved_width))
         });
+        Self::print_line("pieces", &utils::pieces_bar(torrent.num_pieces(), width).cyan());
+    }
+
+    /// Print a list of all the files in the torrent.
+    ///
+    /// With details, BEP 47 attribute flags and symlink targets are shown after the path.
+    /// Files are listed in torrent order unless natural sorting is enabled.
+    fn print_files(&self, torrent: &Torrent) {
+        let mut file_tree_entries = torrent.info.file_tree_entries();
+        let prefix = self.path_prefix(torrent, &file_tree_entries);
+        if !file_tree_entries.is_empty() {
+            if self.args.natural_sort {
+                file_tree_entries
+                    .sort_by(|left, right| utils::natural_cmp(&left.path().join("/"), &right.path().join("/")));
+            }
+            self.print_file_tree(&file_tree_entries, &prefix);
+            return;
+        }
+
+        let mut files = torrent.files_normalized();
+        if self.args.natural_sort {
+            files.sort_by(|left, right| utils::natural_cmp(&left.path().join("/"), &right.path().join("/")));
+        }
 
         if files.len() == 1 {
-            Self::print_line("files", &files[0].path().join("/"));
+            let path = format!("{prefix}{}", files[0].path().join("/"));
+            let path_width = self.path_width(INDENT.len() + COLUMN_WIDTH + 1);
+            Self::print_line("files", &utils::truncate_middle(&path, path_width));
         } else {
             println!("{INDENT}{}", "files".bold());
 
             let digits = utils::digit_count(files.len());
+            let path_width = self.path_width(self.file_row_prefix_width(digits));
+            let mut running_total = 0;
 
             for (index, file) in files.iter().enumerate() {
-                let size = match NumberPrefix::decimal(file.length() as f64) {
-                    NumberPrefix::Standalone(bytes) => format!("{bytes} bytes"),
-                    NumberPrefix::Prefixed(prefix, n) => format!("{n:.2} {prefix}B"),
+                let length = file.length().max(0) as u64;
+                let size = utils::colorize_size(length);
+                let cumulative = self.cumulative_column(&mut running_total, length);
+                let metadata = if self.args.details {
+                    Self::file_metadata(file)
+                } else {
+                    String::new()
                 };
                 println!(
-                    "{}{:>0width$}{INDENT}{:>9}{INDENT}{}",
+                    "{}{:>0width$}{INDENT}{:>SIZE_COLUMN_WIDTH$}{cumulative}{INDENT}{}{metadata}",
                     INDENT.repeat(2),
                     (index + 1).to_string().bold(),
-                    size.cyan(),
-                    file.path().join("/"),
+                    size,
+                    utils::truncate_middle(&format!("{prefix}{}", file.path().join("/")), path_width),
                     width = digits
                 );
             }
         }
     }
 
+    /// Get the torrent name directory prefix for file paths with `--full-paths`, or an empty string.
+    ///
+    /// File paths of multi-file torrents are relative to the name directory a client creates on disk.
+    /// Single-file torrents only have the file itself, which is already shown by its name.
+    fn path_prefix(&self, torrent: &Torrent, file_tree_entries: &[torrentinfo::FileEntry]) -> String {
+        let name = torrent.content_name();
+        let single_file = match file_tree_entries {
+            [entry] => torrent.files().is_none() && entry.path() == [name.as_ref()],
+            [] => torrent.files().is_none(),
+            _ => false,
+        };
+        if self.args.full_paths && !single_file {
+            format!("{name}/")
+        } else {
+            String::new()
+        }
+    }
+
+    /// Format the attribute flags and symlink target of a file, prefixed with a space when not empty
+    fn file_metadata(file: &torrentinfo::File) -> String {
+        let attributes = file.attributes();
+        let flags = if attributes.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", format!("[{attributes}]").yellow())
+        };
+        let target = file
+            .symlink_path()
+            .map_or_else(String::new, |target| format!(" -> {}", target.join("/")));
+        format!("{flags}{target}")
+    }
+
+    /// Get the number of columns left for a file path after a prefix of the given width.
+    ///
+    /// Paths are not truncated with `--no-truncate`.
+    fn path_width(&self, prefix_width: usize) -> usize {
+        if self.args.no_truncate {
+            return usize::MAX;
+        }
+        let width = utils::terminal_width().unwrap_or(utils::DEFAULT_TERMINAL_WIDTH);
+        width.saturating_sub(prefix_width).max(MIN_PATH_WIDTH)
+    }
+
+    /// Width of the index and size columns before the path in a file listing row
+    const fn file_row_prefix_width(&self, digits: usize) -> usize {
+        let cumulative_width = if self.args.cumulative {
+            INDENT.len() + SIZE_COLUMN_WIDTH
+        } else {
+            0
+        };
+        INDENT.len() * 2 + digits + INDENT.len() + SIZE_COLUMN_WIDTH + cumulative_width + INDENT.len()
+    }
+
+    /// Add the file length to the running total and format it as a column when `--cumulative` is set
+    fn cumulative_column(&self, running_total: &mut u64, length: u64) -> String {
+        if !self.args.cumulative {
+            return String::new();
+        }
+        *running_total += length;
+        format!(
+            "{INDENT}{:>SIZE_COLUMN_WIDTH$}",
+            utils::format_file_size(*running_total).dimmed()
+        )
+    }
+
+    /// Print the files of a v2 torrent together with their pieces root hashes
+    fn print_file_tree(&self, entries: &[torrentinfo::FileEntry], prefix: &str) {
+        println!("{INDENT}{}", "files".bold());
+        let digits = utils::digit_count(entries.len());
+        let path_width = self.path_width(self.file_row_prefix_width(digits));
+        let mut running_total = 0;
+        for (index, entry) in entries.iter().enumerate() {
+            let length = entry.length().max(0) as u64;
+            let size = utils::colorize_size(length);
+            let cumulative = self.cumulative_column(&mut running_total, length);
+            let pieces_root = entry.pieces_root().map_or_else(|| "-".to_string(), torrentinfo::to_hex);
+            println!(
+                "{}{:>0width$}{INDENT}{:>SIZE_COLUMN_WIDTH$}{cumulative}{INDENT}{}{INDENT}{}",
+                INDENT.repeat(2),
+                (index + 1).to_string().bold(),
+                size,
+                utils::truncate_middle(&format!("{prefix}{}", entry.path().join("/")), path_width),
+                pieces_root.dimmed(),
+                width = digits
+            );
+        }
+    }
+
     /// Print all data in the torrent file without trying to parse it into a `Torrent`
-    fn print_raw_data(filepath: &Path, indent: &str) -> anyhow::Result<()> {
-        let bytes = Torrent::read_bytes(filepath)?;
-        let bencoded = serde_bencode::from_bytes(&bytes).context("could not decode .torrent file")?;
+    fn print_raw_data(&self, filepath: &Path, indent: &str) -> anyhow::Result<()> {
+        let bytes = utils::read_torrent_bytes(filepath)?;
+        let bencoded = Torrent::raw_value_from_buf(&bytes).context("could not decode .torrent file")?;
         if let Value::Dict(root) = bencoded {
-            Self::print_dict(&root, indent, 1);
+            self.print_dict(&root, indent, 1);
         } else {
             println!("torrent file is not a dict");
         }
@@ -231,24 +1738,27 @@ impl TorrentInfo {
     }
 
     /// Print a formatted line of data with indentation
-    fn print_line<T: std::fmt::Display>(name: &str, value: &T) {
+    pub fn print_line<T: std::fmt::Display>(name: &str, value: &T) {
         let num_whitespace = COLUMN_WIDTH.saturating_sub(name.len());
         println!("{INDENT}{} {}{value}", name.bold(), " ".repeat(num_whitespace));
     }
 
     /// Print a single bencode value
-    fn print_value(value: &Value, indent: &str, depth: usize) {
+    fn print_value(&self, value: &Value, indent: &str, depth: usize) {
         match value {
-            Value::Dict(d) => Self::print_dict(d, indent, depth),
-            Value::List(l) => Self::print_list(l, indent, depth),
-            Value::Bytes(b) => Self::print_bytes(b, indent, depth),
+            Value::Dict(d) => self.print_dict(d, indent, depth),
+            Value::List(l) => self.print_list(l, indent, depth),
+            Value::Bytes(b) => self.print_bytes(b, indent, depth),
             Value::Int(i) => println!("{}{}", indent.repeat(depth), i.to_string().cyan()),
         }
     }
 
-    /// Print dictionary values recursively
-    fn print_dict(dict: &Dict, indent: &str, depth: usize) {
-        for (key, value) in dict {
+    /// Print dictionary values recursively in sorted key order, matching the canonical bencode order
+    fn print_dict(&self, dict: &Dict, indent: &str, depth: usize) {
+        for (key, value) in dict
+            .iter()
+            .sorted_by(|(left_key, _), (right_key, _)| left_key.cmp(right_key))
+        {
             let key = String::from_utf8_lossy(key);
             println!(
                 "{}{}",
@@ -259,12 +1769,12 @@ impl TorrentInfo {
                     key.bold()
                 }
             );
-            Self::print_value(value, indent, depth + 1);
+            self.print_value(value, indent, depth + 1);
         }
     }
 
     /// Print list values recursively
-    fn print_list(list: &[Value], indent: &str, depth: usize) {
+    fn print_list(&self, list: &[Value], indent: &str, depth: usize) {
         for (key, value) in list.iter().enumerate() {
             println!(
                 "{}{}",
@@ -275,21 +1785,29 @@ impl TorrentInfo {
                     key.to_string().bold()
                 }
             );
-            Self::print_value(value, indent, depth + 1);
+            self.print_value(value, indent, depth + 1);
         }
     }
 
-    /// Print byte values with appropriate formatting
-    fn print_bytes(bytes: &[u8], indent: &str, depth: usize) {
-        if bytes.len() > BYTE_THRESHOLD {
-            println!(
-                "{}{}",
-                indent.repeat(depth),
-                format!("[{} Bytes]", bytes.len()).cyan().bold()
-            );
+    /// Print byte values as text, as a hex preview for short binary values,
+    /// or as a byte count when longer than the `--inline-bytes` threshold
+    fn print_bytes(&self, bytes: &[u8], indent: &str, depth: usize) {
+        let content = if bytes.len() > self.args.inline_bytes {
+            format!("[{} Bytes]", bytes.len()).cyan().bold()
+        } else if let Some(text) = utils::printable_text(bytes) {
+            text.normal()
         } else {
-            let content = std::str::from_utf8(bytes).unwrap_or("[invalid utf-8]");
-            println!("{}{content}", indent.repeat(depth));
-        }
+            utils::hex_preview(bytes).cyan()
+        };
+        println!("{}{content}", indent.repeat(depth));
     }
 }
+
+/// Format tracker scheme counts sorted by scheme, for example `https: 1, udp: 9`
+fn format_scheme_counts(schemes: &HashMap<String, usize>) -> String {
+    schemes
+        .iter()
+        .sorted()
+        .map(|(scheme, count)| format!("{scheme}: {count}"))
+        .join(", ")
+}
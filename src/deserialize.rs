@@ -0,0 +1,228 @@
+/*
+ * torrentinfo, A torrent file parser
+ * Copyright (C) 2025  Akseli Lukkarila
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+//! Tolerant deserializers for fields that non-compliant torrent creators encode inconsistently.
+
+use std::fmt;
+
+use serde::de::{self, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+
+/// Deserialize the `private` flag from either an integer or a single-digit byte string.
+pub fn private_flag<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct PrivateFlagVisitor;
+
+    impl Visitor<'_> for PrivateFlagVisitor {
+        type Value = Option<u8>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an integer or a single digit string")
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+            u8::try_from(value)
+                .map(Some)
+                .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            u8::try_from(value)
+                .map(Some)
+                .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+        }
+
+        fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+            match value {
+                [digit] if digit.is_ascii_digit() => Ok(Some(digit - b'0')),
+                _ => Err(E::invalid_value(de::Unexpected::Bytes(value), &self)),
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            self.visit_bytes(value.as_bytes())
+        }
+    }
+
+    deserializer.deserialize_any(PrivateFlagVisitor)
+}
+
+/// Deserialize the `creation date` from an integer or an ASCII number string.
+///
+/// Fractional seconds in a string are truncated.
+/// Any other value results in `None` instead of failing the whole parse.
+pub fn creation_date<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct CreationDateVisitor;
+
+    impl<'de> Visitor<'de> for CreationDateVisitor {
+        type Value = Option<i64>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an integer timestamp or a numeric string")
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+            Ok(Some(value))
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            Ok(i64::try_from(value).ok())
+        }
+
+        fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+            Ok(std::str::from_utf8(value).ok().and_then(parse_timestamp))
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            Ok(parse_timestamp(value))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            while seq.next_element::<IgnoredAny>()?.is_some() {}
+            Ok(None)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+            Ok(None)
+        }
+    }
+
+    deserializer.deserialize_any(CreationDateVisitor)
+}
+
+/// Deserialize a list of URLs that may also be given as a single string, which is returned as a one-element list
+pub fn string_or_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct StringOrListVisitor;
+
+    impl<'de> Visitor<'de> for StringOrListVisitor {
+        type Value = Option<Vec<String>>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a string or a list of strings")
+        }
+
+        fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+            std::str::from_utf8(value)
+                .map(|value| Some(vec![value.to_string()]))
+                .map_err(|_| E::invalid_value(de::Unexpected::Bytes(value), &self))
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            Ok(Some(vec![value.to_string()]))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(value) = seq.next_element::<String>()? {
+                values.push(value);
+            }
+            Ok(Some(values))
+        }
+    }
+
+    deserializer.deserialize_any(StringOrListVisitor)
+}
+
+/// Deserialize a file `length` from either an integer or an ASCII number string.
+///
+/// Strings that are not a number fail the parse like any other invalid value.
+pub fn length<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(LengthVisitor)
+}
+
+/// Deserialize an optional `length`, see [`length`]
+pub fn optional_length<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    length(deserializer).map(Some)
+}
+
+struct LengthVisitor;
+
+impl Visitor<'_> for LengthVisitor {
+    type Value = i64;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an integer length or a numeric string")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(value)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        i64::try_from(value).map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        std::str::from_utf8(value)
+            .ok()
+            .and_then(parse_length)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Bytes(value), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        self.visit_bytes(value.as_bytes())
+    }
+}
+
+/// DHT node port, deserialized from either an integer or an ASCII number string
+#[derive(Debug, Clone, Copy)]
+pub struct Port(pub i64);
+
+impl<'de> Deserialize<'de> for Port {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(LengthVisitor).map(Self)
+    }
+}
+
+/// Parse a length string of ASCII digits
+fn parse_length(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit()) {
+        value.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Parse a timestamp string of ASCII digits with optional fractional seconds
+fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (seconds, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let valid = !seconds.is_empty()
+        && seconds.bytes().all(|byte| byte.is_ascii_digit())
+        && fraction.bytes().all(|byte| byte.is_ascii_digit());
+    if valid { seconds.parse().ok() } else { None }
+}
@@ -0,0 +1,150 @@
+use std::path::Path;
+
+use anyhow::Context;
+use colored::Colorize;
+
+use torrentinfo::Torrent;
+
+use crate::EditArgs;
+use crate::cli::TorrentInfo;
+
+/// Apply the requested edits to each input torrent file and write the results.
+///
+/// A failure is logged and the remaining files are still edited.
+pub fn run(args: &EditArgs) -> anyhow::Result<()> {
+    if args.output.is_some() && args.inputs.len() > 1 {
+        anyhow::bail!("--output can only be used with a single input torrent");
+    }
+
+    let mut failed = 0;
+    for (index, input) in args.inputs.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        if let Err(e) = edit_torrent(args, input) {
+            log::error!("{e:#}");
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!("Failed to edit {failed} of {} torrents", args.inputs.len());
+    }
+    Ok(())
+}
+
+/// Parse a `--replace-tracker` value of the form `OLD=NEW`.
+///
+/// Tracker URLs often contain `=` in a passkey query string,
+/// so the value is split at the first `=` followed by a `scheme://` URL if there is one,
+/// and at the first `=` otherwise.
+pub fn parse_replacement(value: &str) -> Result<(String, String), String> {
+    let split = value
+        .match_indices('=')
+        .map(|(index, _)| index)
+        .find(|&index| starts_with_url_scheme(&value[index + 1..]))
+        .or_else(|| value.find('='));
+    match split.map(|index| (&value[..index], &value[index + 1..])) {
+        Some((from, to)) if !from.is_empty() && !to.is_empty() => Ok((from.to_string(), to.to_string())),
+        _ => Err(format!("expected OLD=NEW, got `{value}`")),
+    }
+}
+
+/// Apply the requested edits to a torrent file and write the result.
+///
+/// The changed fields and the old and new info hash are printed before writing.
+/// Only the edited keys are replaced in the original data, so keys that `Torrent` does not model are kept.
+/// Nothing is written in dry run mode or when an unchanged torrent would overwrite its input.
+fn edit_torrent(args: &EditArgs, input: &Path) -> anyhow::Result<()> {
+    let buf = Torrent::read_bytes(input).with_context(|| format!("Failed to read torrent: {}", input.display()))?;
+    let mut torrent =
+        Torrent::from_buf(&buf).with_context(|| format!("Failed to read torrent: {}", input.display()))?;
+    let old_hash = torrentinfo::to_hex(&Torrent::info_hash_from_bytes(&buf)?);
+    let old_fields = diff_fields(&torrent);
+
+    if let Some(name) = &args.name {
+        torrent.set_name(name);
+    }
+    if let Some(private) = args.set_private {
+        torrent.set_private(private);
+    }
+    if args.clear_trackers {
+        torrent.clear_trackers();
+    }
+    for (from, to) in &args.replace_tracker {
+        if args.substring {
+            torrent.replace_tracker_substring(from, to);
+        } else {
+            torrent.replace_tracker(from, to);
+        }
+    }
+
+    let bytes = torrent.patch_bytes(&buf)?;
+    let new_hash = torrentinfo::to_hex(&Torrent::info_hash_from_bytes(&bytes)?);
+    let output = args.output.as_deref().unwrap_or(input);
+
+    println!("{}", output.display().to_string().bold());
+    let changed = print_diff(&old_fields, &diff_fields(&torrent));
+    TorrentInfo::print_line("old info hash", &old_hash);
+    if new_hash == old_hash {
+        TorrentInfo::print_line("new info hash", &new_hash);
+    } else {
+        TorrentInfo::print_line("new info hash", &new_hash.yellow());
+    }
+
+    if !changed && output == input {
+        println!("{}", "No changes, nothing written".yellow());
+    } else if args.dry_run {
+        println!("{}", "Dry run, no changes written".yellow());
+    } else {
+        std::fs::write(output, bytes).with_context(|| format!("Failed to write torrent: {}", output.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Get the displayed values of the fields that can be edited
+fn diff_fields(torrent: &Torrent) -> [(&'static str, String); 3] {
+    let trackers = torrent.trackers();
+    [
+        ("name", torrent.name().clone().unwrap_or_default()),
+        (
+            "private",
+            torrent.info().private().is_some_and(|private| private > 0).to_string(),
+        ),
+        (
+            "trackers",
+            if trackers.is_empty() {
+                "none".to_string()
+            } else {
+                trackers.join(", ")
+            },
+        ),
+    ]
+}
+
+/// Print the old value in red and the new value in green for each field that changed.
+///
+/// Returns whether any field changed.
+fn print_diff(old_fields: &[(&str, String)], new_fields: &[(&str, String)]) -> bool {
+    let mut changed = false;
+    for ((field, old), (_, new)) in old_fields.iter().zip(new_fields) {
+        if old != new {
+            TorrentInfo::print_line(field, &format!("{} -> {}", old.red(), new.green()));
+            changed = true;
+        }
+    }
+    if !changed {
+        TorrentInfo::print_line("changes", &"none".dimmed());
+    }
+    changed
+}
+
+/// Check if the text starts with a URL scheme followed by `://`
+fn starts_with_url_scheme(text: &str) -> bool {
+    text.split_once("://").is_some_and(|(scheme, _)| {
+        scheme.starts_with(|character: char| character.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|character| character.is_ascii_alphanumeric() || matches!(character, '+' | '-' | '.'))
+    })
+}
@@ -19,13 +19,48 @@
 
 use thiserror::Error;
 
+/// Errors returned by the torrentinfo library.
+///
+/// Variants wrapping another error return it from [`std::error::Error::source`] instead of repeating it
+/// in the message, so error reports such as `anyhow`'s `{:#}` and `{:?}` show each cause once.
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("Failed to decode bencode: {0}")]
+    #[error("Failed to decode bencode")]
     Decode(#[from] serde_bencode::Error),
 
-    #[error("IO error: {0}")]
+    #[error("IO error")]
     Io(#[from] std::io::Error),
+
+    #[cfg(feature = "network")]
+    #[error("HTTP request failed")]
+    Http(#[from] reqwest::Error),
+
+    #[cfg(feature = "network")]
+    #[error("Unexpected content type: {0}")]
+    UnexpectedContentType(String),
+
+    #[cfg(feature = "toml")]
+    #[error("Failed to serialize TOML")]
+    TomlSerialize(#[from] toml::ser::Error),
+
+    #[cfg(feature = "toml")]
+    #[error("Failed to parse TOML")]
+    TomlDeserialize(#[from] toml::de::Error),
+
+    #[error("Missing required field: {0}")]
+    MissingField(&'static str),
+
+    #[error("Invalid piece length: {0}")]
+    InvalidPieceLength(i64),
+
+    #[error("Invalid pieces length: {0} bytes is not a multiple of the 20 byte SHA-1 hash length")]
+    InvalidPieces(usize),
+
+    #[error("Invalid file information: torrent has {0}")]
+    InvalidFiles(&'static str),
+
+    #[error("Negative integer for key: {0}")]
+    NegativeInteger(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
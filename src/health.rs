@@ -0,0 +1,140 @@
+/*
+ * torrentinfo, A torrent file parser
+ * Copyright (C) 2025  Akseli Lukkarila
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+//! Triage of damaged `.torrent` files.
+//!
+//! [`check`] tells a corrupt file, such as a truncated download, apart from a file that decodes fine
+//! but does not follow the specification, which clients usually still accept.
+
+use std::error::Error as _;
+use std::fmt;
+
+use crate::errors::Error;
+use crate::{PIECE_HASH_LENGTH, Torrent, Value, trim_torrent_data};
+
+/// Overall health of a `.torrent` file, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Grade {
+    /// Decodes and follows the specification
+    Ok,
+    /// Decodes, but breaks the specification or has inconsistent piece information
+    Suspicious,
+    /// Is not bencode, lacks the info dictionary, or has damaged or missing piece hashes
+    Corrupt,
+}
+
+/// Result of [`check`] with the grade and a description of each problem found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthReport {
+    pub grade: Grade,
+    pub problems: Vec<String>,
+}
+
+impl HealthReport {
+    fn corrupt(problem: String) -> Self {
+        Self {
+            grade: Grade::Corrupt,
+            problems: vec![problem],
+        }
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Ok => "OK",
+            Self::Suspicious => "suspicious",
+            Self::Corrupt => "corrupt",
+        };
+        f.pad(label)
+    }
+}
+
+/// Check whether torrent data is bencode with a usable info dictionary and consistent piece hashes.
+///
+/// The raw `Value` tree is inspected first, so files that do not parse as a torrent at all still get a reason.
+/// v1 file information without any piece hashes is corrupt, since the content can not be verified.
+/// Data that parses is then checked with [`Torrent::from_buf_strict`],
+/// for a piece hash count that does not match the content size,
+/// and for a byte order mark or whitespace that [`Torrent::from_buf`] only tolerates.
+#[must_use]
+pub fn check(buf: &[u8]) -> HealthReport {
+    let data = trim_torrent_data(buf);
+    let root = match serde_bencode::from_bytes(data) {
+        Ok(Value::Dict(root)) => root,
+        Ok(_) => return HealthReport::corrupt("top level value is not a dictionary".to_string()),
+        Err(error) => return HealthReport::corrupt(format!("not valid bencode: {error}")),
+    };
+    let info = match root.get(b"info".as_slice()) {
+        Some(Value::Dict(info)) => info,
+        Some(_) => return HealthReport::corrupt("info is not a dictionary".to_string()),
+        None => return HealthReport::corrupt("missing info dictionary".to_string()),
+    };
+    match info.get(b"pieces".as_slice()) {
+        Some(Value::Bytes(pieces)) if !pieces.len().is_multiple_of(PIECE_HASH_LENGTH) => {
+            return HealthReport::corrupt(format!(
+                "pieces length of {} bytes is not a multiple of {PIECE_HASH_LENGTH}",
+                pieces.len()
+            ));
+        }
+        Some(Value::Bytes(_)) | None => {}
+        Some(_) => return HealthReport::corrupt("pieces is not a byte string".to_string()),
+    }
+    let torrent = match Torrent::from_buf(data) {
+        Ok(torrent) => torrent,
+        Err(error) => return HealthReport::corrupt(describe(&error)),
+    };
+
+    if torrent.info().is_v1() && torrent.num_pieces() == 0 && torrent.total_size_bytes() > 0 {
+        return HealthReport::corrupt(format!(
+            "no piece hashes for {} bytes of content",
+            torrent.total_size_bytes()
+        ));
+    }
+
+    let mut problems = Vec::new();
+    if data.len() != buf.len() {
+        problems.push("byte order mark or whitespace around the bencode data".to_string());
+    }
+    if let Err(error) = Torrent::from_buf_strict(data) {
+        problems.push(describe(&error));
+    }
+    let piece_length = torrent.piece_length_bytes();
+    if torrent.info().is_v1() && piece_length > 0 {
+        let expected = torrent.total_size_bytes().div_ceil(piece_length);
+        let actual = torrent.num_pieces() as u64;
+        if actual != expected {
+            problems.push(format!("{actual} piece hashes but the content needs {expected}"));
+        }
+    }
+    HealthReport {
+        grade: if problems.is_empty() {
+            Grade::Ok
+        } else {
+            Grade::Suspicious
+        },
+        problems,
+    }
+}
+
+/// Describe an error followed by its source, if any
+fn describe(error: &Error) -> String {
+    error
+        .source()
+        .map_or_else(|| error.to_string(), |source| format!("{error}: {source}"))
+}
@@ -0,0 +1,161 @@
+/*
+ * torrentinfo, A torrent file parser
+ * Copyright (C) 2025  Akseli Lukkarila
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+//! Standalone HTML reports for `--html`.
+//!
+//! The page is built from [`TorrentReport`] with inline styles and a small inline script
+//! for sorting the file tables, so it can be shared as a single file without external assets.
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use torrentinfo::report::TorrentReport;
+
+use crate::utils;
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2em auto; max-width: 60em; padding: 0 1em; color: #222; }
+h1 { font-size: 1.6em; }
+h2 { font-size: 1.3em; margin-top: 2em; word-break: break-all; }
+table { border-collapse: collapse; width: 100%; margin: 1em 0; }
+th, td { text-align: left; padding: 0.3em 0.6em; border-bottom: 1px solid #ddd; vertical-align: top; }
+table.metadata th { width: 10em; white-space: nowrap; }
+table.files th { cursor: pointer; background: #f4f4f4; user-select: none; }
+td.size { text-align: right; white-space: nowrap; }
+code { font-family: ui-monospace, monospace; word-break: break-all; }
+";
+
+const SCRIPT: &str = r#"
+document.querySelectorAll("table.files th").forEach((header, column) => {
+  header.addEventListener("click", () => {
+    const body = header.closest("table").tBodies[0];
+    const ascending = header.dataset.order !== "asc";
+    header.dataset.order = ascending ? "asc" : "desc";
+    const key = (row) => row.cells[column].dataset.sort ?? row.cells[column].textContent;
+    const rows = Array.from(body.rows).sort((a, b) => {
+      const [x, y] = [key(a), key(b)];
+      const order = isNaN(x) || isNaN(y) ? x.localeCompare(y, undefined, { numeric: true }) : x - y;
+      return ascending ? order : -order;
+    });
+    body.append(...rows);
+  });
+});
+"#;
+
+/// Render the reports as one self-contained HTML page with a section per torrent
+pub fn render(reports: &[TorrentReport]) -> String {
+    let title = match reports {
+        [report] => report.name.as_deref().unwrap_or(&report.info_hash),
+        _ => "Torrent report",
+    };
+
+    let mut page = String::new();
+    page.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(page, "<title>{}</title>", escape(title));
+    let _ = writeln!(page, "<style>\n{STYLE}</style>\n</head>\n<body>");
+    let _ = writeln!(page, "<h1>{}</h1>", escape(title));
+    for report in reports {
+        render_torrent(&mut page, report, reports.len() > 1);
+    }
+    let _ = writeln!(page, "<script>{SCRIPT}</script>\n</body>\n</html>");
+    page
+}
+
+/// Append the metadata and file tables of a single torrent
+fn render_torrent(page: &mut String, report: &TorrentReport, heading: bool) {
+    page.push_str("<section>\n");
+    if heading {
+        let name = report.name.as_deref().unwrap_or(&report.info_hash);
+        let _ = writeln!(page, "<h2>{}</h2>", escape(name));
+    }
+
+    page.push_str("<table class=\"metadata\">\n");
+    let mut row = |label: &str, value: &str| {
+        let _ = writeln!(page, "<tr><th>{label}</th><td>{value}</td></tr>");
+    };
+    if let Some(name) = &report.name {
+        row("Name", &escape(name));
+    }
+    row("Info hash", &format!("<code>{}</code>", escape(&report.info_hash)));
+    if let Some(comment) = &report.comment {
+        row("Comment", &escape(comment));
+    }
+    if let Some(created_by) = &report.created_by {
+        row("Created by", &escape(created_by));
+    }
+    if let Some(date) = report.creation_date {
+        row("Created", &escape(&utils::format_creation_date(date)));
+    }
+    if let Some(encoding) = &report.encoding {
+        row("Encoding", &escape(encoding));
+    }
+    row("Total size", &size_text(report.total_size));
+    row("Files", &report.num_files.to_string());
+    row(
+        "Piece length",
+        &utils::format_piece_length(u64::try_from(report.piece_length).unwrap_or(0)),
+    );
+    row("Pieces", &report.num_pieces.to_string());
+    row("Private", if report.private { "yes" } else { "no" });
+    if !report.trackers.is_empty() {
+        let trackers = report
+            .trackers
+            .iter()
+            .map(|tracker| format!("<code>{}</code>", escape(tracker)))
+            .collect::<Vec<_>>()
+            .join("<br>");
+        row("Trackers", &trackers);
+    }
+    page.push_str("</table>\n");
+
+    page.push_str("<table class=\"files\">\n<thead><tr><th>Path</th><th>Size</th></tr></thead>\n<tbody>\n");
+    for file in &report.files {
+        let _ = writeln!(
+            page,
+            "<tr><td>{}</td><td class=\"size\" data-sort=\"{}\">{}</td></tr>",
+            escape(&file.path),
+            file.length,
+            size_text(file.length)
+        );
+    }
+    page.push_str("</tbody>\n</table>\n</section>\n");
+}
+
+/// Format a byte count from a report for display
+fn size_text(size: i64) -> String {
+    utils::format_file_size(u64::try_from(size).unwrap_or(0))
+}
+
+/// Escape text for use in HTML element content and quoted attribute values
+fn escape(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 16);
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+    Cow::Owned(escaped)
+}
@@ -0,0 +1,126 @@
+use std::path::Path;
+
+use anyhow::Context;
+use colored::Colorize;
+
+use torrentinfo::{FileMatch, Torrent};
+
+use crate::VerifyLayoutArgs;
+use crate::utils;
+
+/// Result of checking a single file against the directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LayoutStatus {
+    Ok,
+    Missing,
+    WrongSize,
+}
+
+impl LayoutStatus {
+    const fn from_match(file_match: &FileMatch) -> Self {
+        match file_match.size_on_disk {
+            None => Self::Missing,
+            Some(size) if size == file_match.length => Self::Ok,
+            Some(_) => Self::WrongSize,
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Missing => "missing",
+            Self::WrongSize => "wrong size",
+        }
+    }
+}
+
+/// Check that each file of the torrent exists in the directory with the expected length.
+///
+/// Files are expected at `dir/<name>` for single-file torrents and at `dir/<name>/<path>` for multi-file torrents.
+/// Only names and sizes are compared, so this is instant but can not detect corrupt data.
+/// Prints a table of the files and fails if any of them is missing or has the wrong size.
+pub fn run(args: &VerifyLayoutArgs) -> anyhow::Result<()> {
+    let torrent = Torrent::from_file(&args.torrent)
+        .with_context(|| format!("Failed to read torrent: {}", args.torrent.display()))?;
+    if !args.directory.is_dir() {
+        anyhow::bail!("Directory does not exist: {}", args.directory.display());
+    }
+
+    let matches = torrent.match_existing(&args.directory);
+    let rows: Vec<(LayoutStatus, &FileMatch, String)> = matches
+        .iter()
+        .map(|file_match| {
+            (
+                LayoutStatus::from_match(file_match),
+                file_match,
+                display_path(&file_match.path, &args.directory),
+            )
+        })
+        .collect();
+    print_table(&rows);
+
+    let failed = rows.iter().filter(|(status, ..)| *status != LayoutStatus::Ok).count();
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} files do not match the torrent", rows.len());
+    }
+    println!("\n{}", "All files match the torrent".green());
+    Ok(())
+}
+
+/// Print the status, expected length, size on disk and path of each file as aligned columns
+fn print_table(rows: &[(LayoutStatus, &FileMatch, String)]) {
+    let status_width = rows
+        .iter()
+        .map(|(status, ..)| status.label().len())
+        .chain(std::iter::once("status".len()))
+        .max()
+        .unwrap_or(0);
+    let expected: Vec<String> = rows
+        .iter()
+        .map(|(_, file_match, _)| file_match.length.to_string())
+        .collect();
+    let on_disk: Vec<String> = rows
+        .iter()
+        .map(|(_, file_match, _)| {
+            file_match
+                .size_on_disk
+                .map_or_else(|| "-".to_string(), |size| size.to_string())
+        })
+        .collect();
+    let expected_width = column_width(&expected, "expected");
+    let on_disk_width = column_width(&on_disk, "on disk");
+
+    println!(
+        "{}",
+        format!(
+            "{:<status_width$}  {:>expected_width$}  {:>on_disk_width$}  path",
+            "status", "expected", "on disk"
+        )
+        .bold()
+    );
+    for (((status, _, path), expected), on_disk) in rows.iter().zip(&expected).zip(&on_disk) {
+        let label = format!("{:<status_width$}", status.label());
+        let label = match status {
+            LayoutStatus::Ok => label.green(),
+            LayoutStatus::Missing => label.red(),
+            LayoutStatus::WrongSize => label.yellow(),
+        };
+        println!("{label}  {expected:>expected_width$}  {on_disk:>on_disk_width$}  {path}");
+    }
+}
+
+/// Get the width of a column from its values and header
+fn column_width(values: &[String], header: &str) -> usize {
+    values
+        .iter()
+        .map(String::len)
+        .chain(std::iter::once(header.len()))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Get the path of a file relative to the checked directory, with forward slashes
+fn display_path(path: &Path, directory: &Path) -> String {
+    let relative = path.strip_prefix(directory).unwrap_or(path);
+    utils::forward_slashes(utils::path_to_string(relative))
+}
@@ -17,24 +17,80 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>
  */
 
+pub mod category;
 pub mod errors;
+pub mod health;
+pub mod report;
+pub mod suspicious;
 
+mod deserialize;
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
 use std::fs::File as StdFile;
 use std::io::Read;
-use std::path::Path;
+use std::io::{Seek, SeekFrom};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
+use serde::de::DeserializeOwned;
 use serde_bencode::ser;
-use serde_bencode::value::Value;
+pub use serde_bencode::value::Value;
 use serde_bytes::ByteBuf;
 use serde_derive::{Deserialize, Serialize};
-use sha1::{Digest, Sha1};
+pub use sha1::Digest;
+use sha1::Sha1;
+use sha2::Sha256;
+use walkdir::WalkDir;
+
+use crate::category::Category;
+use crate::errors::{Error, Result};
+use crate::suspicious::SuspiciousTrait;
 
-use crate::errors::Result;
+/// File extension for torrent files
+pub const TORRENT_EXTENSION: &str = "torrent";
+
+/// Maximum directory depth for recursive torrent file discovery
+pub const MAX_WALK_DEPTH: usize = 999;
 
 const HEX_CHARS: &[u8] = b"0123456789abcdef";
+const HEX_CHARS_UPPER: &[u8] = b"0123456789ABCDEF";
+/// UTF-8 byte order mark that text editors and text-mode transfers may prepend
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+/// Placeholder name for torrents without a name or files
+const UNKNOWN_NAME: &str = "unknown";
+/// Length of a single SHA-1 piece hash in bytes
+pub const PIECE_HASH_LENGTH: usize = 20;
+/// Piece counts below this are considered too coarse for efficient transfers
+const MIN_RECOMMENDED_PIECES: usize = 8;
+/// Piece counts above this produce unnecessarily large torrent files
+const MAX_RECOMMENDED_PIECES: usize = 50_000;
 const DEFAULT_BUFFER_SIZE: usize = 1024 * 1024; // 1MB
-
-#[derive(Debug, Default, Deserialize, Serialize)]
+/// Amount of piece data read per batch in parallel verification
+#[cfg(feature = "parallel")]
+const PARALLEL_BATCH_BYTES: usize = 64 * 1024 * 1024;
+/// Smallest recommended piece length, 16 KiB
+const MIN_PIECE_LENGTH: u64 = 16 * 1024;
+/// Largest recommended piece length, 16 MiB
+const MAX_PIECE_LENGTH: u64 = 16 * 1024 * 1024;
+/// Recommended piece lengths keep the piece count at or below this, and above half of it
+const TARGET_MAX_PIECES: u64 = 2000;
+/// Hosts of well-known open trackers, also matching their subdomains
+const PUBLIC_TRACKER_HOSTS: &[&str] = &[
+    "coppersurfer.tk",
+    "demonii.com",
+    "exodus.desync.com",
+    "explodie.org",
+    "leechers-paradise.org",
+    "open.stealth.si",
+    "openbittorrent.com",
+    "opentrackr.org",
+    "publicbt.com",
+    "torrent.eu.org",
+];
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct Torrent {
     #[serde(default)]
     pub announce: Option<String>,
@@ -46,51 +102,244 @@ pub struct Torrent {
     #[serde(default)]
     #[serde(rename = "created by")]
     pub created_by: Option<String>,
-    #[serde(default)]
+    /// Creation time as a Unix timestamp, also accepted as a numeric string written by some non-compliant creators.
+    /// Invalid values are ignored and the date is always serialized back as an integer.
+    #[serde(default, deserialize_with = "deserialize::creation_date")]
     #[serde(rename = "creation date")]
     pub creation_date: Option<i64>,
     #[serde(default)]
     pub encoding: Option<String>,
+    /// Info dictionary, change it through [`Torrent::info_mut`] so the info hash is recalculated
     pub info: Info,
     #[serde(default)]
     nodes: Option<Vec<Node>>,
+    /// DHT bootstrap nodes stored under a non-standard key by some clients, see [`Torrent::nodes`]
+    #[serde(default)]
+    #[serde(rename = "x-nodes")]
+    x_nodes: Option<Vec<Node>>,
+    /// DHT bootstrap nodes stored under a non-standard key by some clients, see [`Torrent::nodes`]
     #[serde(default)]
+    #[serde(rename = "dht-nodes")]
+    dht_nodes: Option<Vec<Node>>,
+    /// BEP 17 HTTP seed URLs, also accepted as a single string
+    #[serde(default, deserialize_with = "deserialize::string_or_list")]
     pub httpseeds: Option<Vec<String>>,
+    /// BEP 19 web seed URLs, also accepted as a single string
+    #[serde(default, deserialize_with = "deserialize::string_or_list")]
+    #[serde(rename = "url-list")]
+    pub url_list: Option<Vec<String>>,
+    #[serde(default)]
+    #[serde(rename = "piece layers")]
+    pub piece_layers: Option<Value>,
+    /// Info dictionary bytes exactly as stored in the parsed data, see [`Torrent::info_bytes`]
+    #[serde(skip)]
+    raw_info: Option<Vec<u8>>,
 }
 
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Info {
     #[serde(default)]
     pub files: Option<Vec<File>>,
-    #[serde(default)]
+    /// Length of a single-file torrent, also accepted as a numeric string written by some non-compliant creators
+    #[serde(default, deserialize_with = "deserialize::optional_length")]
     pub length: Option<i64>,
     #[serde(default)]
     pub md5sum: Option<String>,
     pub name: Option<String>,
     #[serde(default)]
+    #[serde(rename = "file tree")]
+    pub file_tree: Option<Value>,
+    #[serde(default)]
+    #[serde(rename = "meta version")]
+    pub meta_version: Option<i64>,
+    #[serde(default)]
     pub path: Option<Vec<String>>,
     #[serde(rename = "piece length")]
     pub piece_length: i64,
-    #[serde(default)]
+    /// Concatenated SHA-1 piece hashes. Omitted when empty, as in v2-only torrents.
+    #[serde(default, skip_serializing_if = "is_empty_bytes")]
     pub pieces: ByteBuf,
-    #[serde(default)]
+    /// Private flag, also accepted as a single digit string written by some non-compliant creators.
+    /// The flag is always serialized back as an integer.
+    #[serde(default, deserialize_with = "deserialize::private_flag")]
     pub private: Option<u8>,
     #[serde(default)]
     #[serde(rename = "root hash")]
     pub root_hash: Option<String>,
 }
 
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct File {
+    /// File length, also accepted as a numeric string written by some non-compliant creators
+    #[serde(deserialize_with = "deserialize::length")]
     pub length: i64,
     pub path: Vec<String>,
+    /// UTF-8 path components written alongside `path` by some creators, preferred by [`File::path`]
+    #[serde(default)]
+    #[serde(rename = "path.utf-8")]
+    pub path_utf8: Option<Vec<String>>,
     #[serde(default)]
     pub md5sum: Option<String>,
+    /// BEP 47 file attributes, for example `p` for padding files
+    #[serde(default)]
+    pub attr: Option<String>,
+    /// BEP 47 symlink target path components, relative to the torrent root
+    #[serde(default)]
+    #[serde(rename = "symlink path")]
+    pub symlink_path: Option<Vec<String>>,
+}
+
+/// Decoded BEP 47 file attribute flags
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct FileAttributes {
+    /// `x`: the file is executable
+    pub executable: bool,
+    /// `l`: the file is a symlink, see `symlink path`
+    pub symlink: bool,
+    /// `p`: the file is padding for piece alignment
+    pub padding: bool,
+    /// `h`: the file is hidden
+    pub hidden: bool,
+}
+
+/// A file from a BEP 52 (v2) file tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    pub length: i64,
+    pub path: Vec<String>,
+    pub pieces_root: Option<Vec<u8>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// DHT bootstrap node as a host and port pair.
+///
+/// Deserialized through a plain tuple since `serde_bencode` does not consume
+/// the end of a list for tuple structs, which breaks torrents with more than one node.
+/// The port is also accepted as a numeric string written by some clients.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(from = "(String, deserialize::Port)")]
 struct Node(String, i64);
 
+/// Result of verifying data against the piece hashes of a torrent
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Number of pieces described by the torrent
+    pub total_pieces: usize,
+    /// Indices of pieces whose data did not match the expected hash
+    pub failed_pieces: Vec<usize>,
+    /// Indices of pieces for which no data was available
+    pub missing_pieces: Vec<usize>,
+    /// Number of bytes read from the data source
+    pub bytes_read: u64,
+    /// Non-empty files that were not found on disk
+    pub missing_files: Vec<PathBuf>,
+}
+
+/// Lightweight torrent metadata for sorting and listing large directories.
+///
+/// The torrent is decoded like [`Torrent::from_buf_metadata_only`],
+/// so the piece hashes and v2 piece layers are never copied out of the torrent data.
+/// [`Torrent::peek`] also calculates the info hash, which is skipped when only sorting.
+#[derive(Debug, Default)]
+pub struct TorrentSummary {
+    /// Torrent without the piece data
+    torrent: Torrent,
+    /// SHA-1 info hash, only calculated by [`Torrent::peek`]
+    info_hash: Option<Vec<u8>>,
+}
+
+/// Top-level fields decoded for [`Torrent::from_buf_metadata_only`] and [`TorrentSummary`], without the v2 piece layers
+#[derive(Deserialize)]
+struct MetadataTorrent {
+    #[serde(default)]
+    announce: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "announce-list")]
+    announce_list: Option<Vec<Vec<String>>>,
+    #[serde(default)]
+    comment: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "created by")]
+    created_by: Option<String>,
+    #[serde(default, deserialize_with = "deserialize::creation_date")]
+    #[serde(rename = "creation date")]
+    creation_date: Option<i64>,
+    #[serde(default)]
+    encoding: Option<String>,
+    info: MetadataInfo,
+    #[serde(default)]
+    nodes: Option<Vec<Node>>,
+    #[serde(default)]
+    #[serde(rename = "x-nodes")]
+    x_nodes: Option<Vec<Node>>,
+    #[serde(default)]
+    #[serde(rename = "dht-nodes")]
+    dht_nodes: Option<Vec<Node>>,
+    #[serde(default, deserialize_with = "deserialize::string_or_list")]
+    httpseeds: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize::string_or_list")]
+    #[serde(rename = "url-list")]
+    url_list: Option<Vec<String>>,
+}
+
+/// Info dictionary fields decoded for [`Torrent::from_buf_metadata_only`] and [`TorrentSummary`], without the piece hashes
+#[derive(Deserialize)]
+struct MetadataInfo {
+    #[serde(default)]
+    files: Option<Vec<File>>,
+    #[serde(default, deserialize_with = "deserialize::optional_length")]
+    length: Option<i64>,
+    #[serde(default)]
+    md5sum: Option<String>,
+    name: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "file tree")]
+    file_tree: Option<Value>,
+    #[serde(default)]
+    #[serde(rename = "meta version")]
+    meta_version: Option<i64>,
+    #[serde(default)]
+    path: Option<Vec<String>>,
+    #[serde(rename = "piece length")]
+    piece_length: i64,
+    #[serde(default, deserialize_with = "deserialize::private_flag")]
+    private: Option<u8>,
+    #[serde(default)]
+    #[serde(rename = "root hash")]
+    root_hash: Option<String>,
+}
+
+/// A file of a torrent matched against its expected location on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMatch {
+    /// Expected location of the file
+    pub path: PathBuf,
+    /// File length in the torrent
+    pub length: u64,
+    /// Size of the file on disk, `None` if it does not exist
+    pub size_on_disk: Option<u64>,
+}
+
+/// Part of a file covered by a piece, see [`Torrent::piece_file_spans`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceSpan {
+    /// Index of the file in the torrent, counting padding files
+    pub file_index: usize,
+    /// Byte range within the file
+    pub range: Range<u64>,
+}
+
+/// Heuristic rating of the piece count relative to the torrent size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PieceHealth {
+    /// Piece length is very large relative to the total size
+    TooFew,
+    /// Piece count is within the recommended range
+    Ok,
+    /// Piece length is very small relative to the total size
+    TooMany,
+}
+
 impl Torrent {
     /// Create `Torrent` from a file path
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
@@ -98,15 +347,186 @@ impl Torrent {
         Self::from_buf(&buf)
     }
 
-    /// Create `Torrent` from bytes
+    /// Create `Torrent` from a file path without loading the piece hashes, see [`Torrent::from_buf_metadata_only`]
+    pub fn from_file_metadata_only<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let buf = Self::read_bytes(path.as_ref())?;
+        Self::from_buf_metadata_only(&buf)
+    }
+
+    /// Create `Torrent` from bytes, skipping the `pieces` and `piece layers` data.
+    ///
+    /// Useful for pathological torrents with huge piece blobs when only the metadata is needed.
+    /// The piece hashes and piece layers are left empty, so piece-dependent methods are unavailable:
+    /// the piece count is zero, verification does not work,
+    /// and the info hashes do not match the original torrent.
+    pub fn from_buf_metadata_only(buf: &[u8]) -> Result<Self> {
+        let (torrent, _): (MetadataTorrent, _) = decode_lenient(buf).map_err(|error| Self::decode_error(buf, error))?;
+        Ok(torrent.into())
+    }
+
+    /// Copy the torrent without the `pieces` and `piece layers` data.
+    ///
+    /// Cheaper than [`Clone::clone`] for large torrents when only the metadata is needed,
+    /// with the same limitations as [`Torrent::from_buf_metadata_only`].
+    #[must_use]
+    pub fn clone_metadata(&self) -> Self {
+        let info = &self.info;
+        Self {
+            announce: self.announce.clone(),
+            announce_list: self.announce_list.clone(),
+            comment: self.comment.clone(),
+            created_by: self.created_by.clone(),
+            creation_date: self.creation_date,
+            encoding: self.encoding.clone(),
+            info: Info {
+                files: info.files.clone(),
+                length: info.length,
+                md5sum: info.md5sum.clone(),
+                name: info.name.clone(),
+                file_tree: info.file_tree.clone(),
+                meta_version: info.meta_version,
+                path: info.path.clone(),
+                piece_length: info.piece_length,
+                pieces: ByteBuf::new(),
+                private: info.private,
+                root_hash: info.root_hash.clone(),
+            },
+            nodes: self.nodes.clone(),
+            x_nodes: self.x_nodes.clone(),
+            dht_nodes: self.dht_nodes.clone(),
+            httpseeds: self.httpseeds.clone(),
+            url_list: self.url_list.clone(),
+            piece_layers: None,
+            raw_info: None,
+        }
+    }
+
+    /// Find all torrent files in a directory, sorted case-insensitively by path.
+    ///
+    /// Only the top level is searched unless `recursive` is set.
+    /// Hidden files and directories starting with a dot are skipped unless `include_hidden` is set.
+    #[must_use]
+    pub fn find_in_dir(root: &Path, recursive: bool, include_hidden: bool) -> Vec<PathBuf> {
+        let max_depth = if recursive { MAX_WALK_DEPTH } else { 1 };
+        Self::find_in_dir_to_depth(root, max_depth, include_hidden)
+    }
+
+    /// Find all torrent files at most `max_depth` directory levels deep, see [`Torrent::find_in_dir`].
+    ///
+    /// A depth of 1 only searches the directory itself and 2 also searches its direct subdirectories.
+    #[must_use]
+    pub fn find_in_dir_to_depth(root: &Path, max_depth: usize, include_hidden: bool) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = Self::iter_in_dir(root, max_depth, include_hidden).collect();
+        files.sort_by_cached_key(|path| path.to_string_lossy().to_lowercase());
+        files
+    }
+
+    /// Iterate over the torrent files at most `max_depth` directory levels deep in the order the directory walk finds them.
+    ///
+    /// Unlike [`Torrent::find_in_dir_to_depth`] the paths are not collected and sorted first,
+    /// so the first path is available immediately and memory use does not grow with the number of files.
+    pub fn iter_in_dir(root: &Path, max_depth: usize, include_hidden: bool) -> impl Iterator<Item = PathBuf> + use<> {
+        let extension = OsStr::new(TORRENT_EXTENSION);
+        WalkDir::new(root)
+            .max_depth(max_depth)
+            .into_iter()
+            .filter_entry(move |entry| include_hidden || !is_hidden(entry))
+            .filter_map(std::result::Result::ok)
+            .map(|entry| entry.path().to_owned())
+            .filter(move |path| path.is_file() && path.extension() == Some(extension))
+    }
+
+    /// Create `Torrent` from bytes.
+    ///
+    /// Torrents with a `pieces` length that is not a multiple of 20 are accepted with a warning,
+    /// use [`Torrent::from_buf_strict`] to reject them.
+    /// Data that fails to decode is retried without a leading UTF-8 byte order mark and surrounding whitespace,
+    /// which text-mode transfers sometimes add.
     pub fn from_buf(buf: &[u8]) -> Result<Self> {
-        serde_bencode::from_bytes(buf).map_err(|e| {
-            if let Ok(Value::Dict(dict)) = serde_bencode::from_bytes::<Value>(buf) {
-                eprintln!("Bencode decode error. Torrent structure:");
-                Self::debug_torrent_structure(&dict);
+        let (mut torrent, data): (Self, _) = decode_lenient(buf).map_err(|error| Self::decode_error(buf, error))?;
+        torrent.raw_info = raw_info_range(data).map(|range| data[range].to_vec());
+        if !torrent.pieces_are_valid_length() {
+            log::warn!(
+                "Pieces length {} is not a multiple of {PIECE_HASH_LENGTH}, piece hashes are misaligned",
+                torrent.info.pieces.len()
+            );
+        }
+        Ok(torrent)
+    }
+
+    /// Create `Torrent` from bytes, rejecting torrents that do not follow the specification.
+    ///
+    /// [`Torrent::from_buf`] accepts all of these, but strict parsing requires that:
+    /// - no integer anywhere in the torrent is negative
+    /// - the info dictionary has a `name`, and `pieces` unless the torrent only has v2 metadata
+    /// - the piece length is a power of two
+    /// - the `pieces` length is a multiple of 20
+    /// - exactly one of `length` and `files` is present, or neither for v2-only torrents
+    pub fn from_buf_strict(buf: &[u8]) -> Result<Self> {
+        let mut torrent: Self = serde_bencode::from_bytes(buf).map_err(|error| Self::decode_error(buf, error))?;
+        torrent.raw_info = raw_info_range(buf).map(|range| buf[range].to_vec());
+        let Value::Dict(root) = Self::raw_value_from_buf(buf)? else {
+            return Err(Error::MissingField("info"));
+        };
+        let mut keys: Vec<&Vec<u8>> = root.keys().collect();
+        keys.sort_unstable();
+        if let Some(key) = keys
+            .into_iter()
+            .find_map(|key| first_negative_integer(&root[key], &String::from_utf8_lossy(key)))
+        {
+            return Err(Error::NegativeInteger(key));
+        }
+        if let Some(Value::Dict(info)) = root.get(b"info".as_slice()) {
+            if !info.contains_key(b"name".as_slice()) {
+                return Err(Error::MissingField("name"));
             }
-            e.into()
-        })
+            let v2_only = torrent.info.is_v2() && torrent.info.files.is_none() && torrent.info.length.is_none();
+            if !v2_only && !info.contains_key(b"pieces".as_slice()) {
+                return Err(Error::MissingField("pieces"));
+            }
+        }
+        torrent.check_strict()?;
+        Ok(torrent)
+    }
+
+    /// Read a torrent file as a raw bencode `Value` tree.
+    ///
+    /// Useful for inspecting keys that are not modeled by `Torrent`.
+    pub fn raw_value<P: AsRef<Path>>(path: P) -> Result<Value> {
+        let buf = Self::read_bytes(path.as_ref())?;
+        Self::raw_value_from_buf(&buf)
+    }
+
+    /// Decode bencoded torrent data as a raw bencode `Value` tree.
+    ///
+    /// Mangled data is retried like in [`Torrent::from_buf`].
+    pub fn raw_value_from_buf(buf: &[u8]) -> Result<Value> {
+        Ok(decode_lenient(buf)?.0)
+    }
+
+    /// Download and parse a torrent from an HTTP(S) URL
+    #[cfg(feature = "network")]
+    pub fn from_url(url: &str) -> Result<Self> {
+        let buf = Self::download_bytes(url)?;
+        Self::from_buf(&buf)
+    }
+
+    /// Download torrent file bytes from an HTTP(S) URL without converting to a `Torrent`.
+    ///
+    /// Redirects are followed. Error statuses and HTML responses are rejected.
+    #[cfg(feature = "network")]
+    pub fn download_bytes(url: &str) -> Result<Vec<u8>> {
+        let response = reqwest::blocking::get(url)?.error_for_status()?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok());
+        if let Some(content_type) = content_type
+            && content_type.starts_with("text/html")
+        {
+            return Err(Error::UnexpectedContentType(content_type.to_string()));
+        }
+        Ok(response.bytes()?.to_vec())
     }
 
     /// Read torrent file bytes without converting to a `Torrent`
@@ -125,309 +545,2857 @@ impl Torrent {
         &self.info.files
     }
 
+    /// Get the number of files in the torrent, see [`Info::file_count`]
     #[must_use]
     pub fn num_files(&self) -> usize {
-        self.info
-            .files
-            .as_ref()
-            .map_or_else(|| usize::from(self.info.length.is_some()), Vec::len)
+        self.info.file_count()
     }
 
-    /// Get total size of all files in the torrent
+    /// Get total size of all files in the torrent.
+    ///
+    /// Falls back to the v2 file tree when the torrent has no v1 file information.
     #[must_use]
     pub fn total_size(&self) -> i64 {
-        self.info.files.as_ref().map_or_else(
-            || self.info.length.unwrap_or(0),
-            |files| files.iter().map(|file| file.length).sum(),
-        )
+        self.normalized_files().iter().map(|file| file.length).sum()
     }
 
-    /// Calculate SHA-1 info hash
-    pub fn info_hash(&self) -> Result<Vec<u8>> {
-        let info = ser::to_bytes(&self.info)?;
-        let info_hash: Vec<u8> = Sha1::digest(&info).to_vec();
-        Ok(info_hash)
+    /// Get total size of all files in the torrent in bytes.
+    ///
+    /// Negative sizes from malformed torrents are clamped to zero.
+    #[must_use]
+    pub fn total_size_bytes(&self) -> u64 {
+        u64::try_from(self.total_size()).unwrap_or(0)
+    }
+
+    /// Serialize the torrent back to bencoded bytes.
+    ///
+    /// Keys that are not modeled by `Torrent` are not included in the output.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(ser::to_bytes(self)?)
+    }
+
+    /// Serialize the editable fields of the torrent over the bencoded data it was parsed from.
+    ///
+    /// Only the announce URLs, name and private flag that differ from the original data are replaced
+    /// in the raw `Value` tree, so keys that are not modeled by `Torrent` are preserved.
+    /// A changed name also replaces an existing `name.utf-8`, which clients prefer over `name`.
+    /// Unless the name or private flag changed, the info dictionary bytes are kept exactly as stored,
+    /// so the info hash does not change.
+    pub fn patch_bytes(&self, buf: &[u8]) -> Result<Vec<u8>> {
+        let original = Self::from_buf(buf)?;
+        let Value::Dict(mut root) = Self::raw_value_from_buf(buf)? else {
+            return Err(Error::MissingField("info"));
+        };
+
+        if self.announce != original.announce {
+            match &self.announce {
+                Some(url) => root.insert(b"announce".to_vec(), Value::Bytes(url.clone().into_bytes())),
+                None => root.remove(b"announce".as_slice()),
+            };
+        }
+        if self.announce_list != original.announce_list {
+            match &self.announce_list {
+                Some(tiers) => {
+                    let tiers = tiers
+                        .iter()
+                        .map(|tier| {
+                            Value::List(tier.iter().map(|url| Value::Bytes(url.clone().into_bytes())).collect())
+                        })
+                        .collect();
+                    root.insert(b"announce-list".to_vec(), Value::List(tiers))
+                }
+                None => root.remove(b"announce-list".as_slice()),
+            };
+        }
+
+        let Some(Value::Dict(mut info)) = root.remove(b"info".as_slice()) else {
+            return Err(Error::MissingField("info"));
+        };
+        let info_bytes = if self.info.name == original.info.name && self.info.private == original.info.private {
+            original.info_bytes()?
+        } else {
+            if self.info.name != original.info.name {
+                // Clients prefer `name.utf-8` when present, so it must not keep the old name
+                if let Some(name) = &self.info.name {
+                    if info.contains_key(b"name.utf-8".as_slice()) {
+                        info.insert(b"name.utf-8".to_vec(), Value::Bytes(name.clone().into_bytes()));
+                    }
+                    info.insert(b"name".to_vec(), Value::Bytes(name.clone().into_bytes()));
+                } else {
+                    info.remove(b"name.utf-8".as_slice());
+                    info.remove(b"name".as_slice());
+                }
+            }
+            if self.info.private != original.info.private {
+                match self.info.private {
+                    Some(private) => info.insert(b"private".to_vec(), Value::Int(private.into())),
+                    None => info.remove(b"private".as_slice()),
+                };
+            }
+            ser::to_bytes(&Value::Dict(info))?
+        };
+
+        let mut entries: Vec<(Vec<u8>, Value)> = root.into_iter().collect();
+        entries.push((b"info".to_vec(), Value::Bytes(Vec::new())));
+        entries.sort_unstable_by(|(left_key, _), (right_key, _)| left_key.cmp(right_key));
+        let mut bytes = vec![b'd'];
+        for (key, value) in entries {
+            bytes.extend(ser::to_bytes(&Value::Bytes(key.clone()))?);
+            if key == b"info" {
+                bytes.extend(&info_bytes);
+            } else {
+                bytes.extend(ser::to_bytes(&value)?);
+            }
+        }
+        bytes.push(b'e');
+        Ok(bytes)
+    }
+
+    /// Set or remove the private flag.
+    ///
+    /// Public torrents omit the flag entirely. Changing the flag changes the info hash.
+    pub fn set_private(&mut self, private: bool) {
+        self.raw_info = None;
+        self.info.private = if private { Some(1) } else { None };
+    }
+
+    /// Set the torrent name.
+    ///
+    /// For multi-file torrents this renames the content root directory, not the files.
+    /// Changing the name changes the info hash.
+    pub fn set_name(&mut self, name: &str) {
+        self.raw_info = None;
+        self.info.name = Some(name.to_string());
     }
 
+    /// Remove the primary announce URL and the announce-list
+    pub fn clear_trackers(&mut self) {
+        self.announce = None;
+        self.announce_list = None;
+    }
+
+    /// Get the number of pieces in the torrent
     #[must_use]
-    pub const fn info(&self) -> &Info {
-        &self.info
+    pub fn num_pieces(&self) -> usize {
+        self.info.num_pieces()
     }
 
+    /// Get the number of bytes the v1 piece hashes add to the `.torrent` file.
+    ///
+    /// Each piece costs one 20-byte SHA-1 hash, so doubling the piece length roughly halves the overhead.
     #[must_use]
-    pub const fn name(&self) -> &Option<String> {
-        &self.info.name
+    pub fn pieces_overhead_bytes(&self) -> usize {
+        self.num_pieces() * PIECE_HASH_LENGTH
     }
 
+    /// Check that the `pieces` length is a whole number of SHA-1 hashes
     #[must_use]
-    pub const fn comment(&self) -> &Option<String> {
-        &self.comment
+    pub fn pieces_are_valid_length(&self) -> bool {
+        self.info.pieces.len().is_multiple_of(PIECE_HASH_LENGTH)
     }
 
+    /// Get the size of the last piece in bytes.
+    ///
+    /// The last piece is usually shorter than the piece length,
+    /// unless the total size is an exact multiple of it.
     #[must_use]
-    pub const fn announce(&self) -> &Option<String> {
-        &self.announce
+    pub fn last_piece_size(&self) -> i64 {
+        let num_pieces = i64::try_from(self.num_pieces()).unwrap_or(i64::MAX);
+        if num_pieces == 0 {
+            return 0;
+        }
+        self.total_size() - (num_pieces - 1) * self.info.piece_length
     }
 
+    /// Get the piece length in bytes, invalid negative values are clamped to zero
     #[must_use]
-    pub const fn announce_list(&self) -> &Option<Vec<Vec<String>>> {
-        &self.announce_list
+    pub fn piece_length_bytes(&self) -> u64 {
+        u64::try_from(self.info.piece_length).unwrap_or(0)
     }
 
+    /// Get the average file size in bytes, ignoring padding files.
+    ///
+    /// Returns zero if the torrent has no files.
     #[must_use]
-    pub const fn created_by(&self) -> &Option<String> {
-        &self.created_by
+    pub fn average_file_size(&self) -> u64 {
+        let sizes = self.file_sizes();
+        if sizes.is_empty() {
+            return 0;
+        }
+        sizes.iter().sum::<u64>() / sizes.len() as u64
     }
 
+    /// Get the median file size in bytes, ignoring padding files.
+    ///
+    /// For an even number of files this is the average of the two middle sizes.
+    /// Returns zero if the torrent has no files.
     #[must_use]
-    pub const fn creation_date(&self) -> &Option<i64> {
-        &self.creation_date
+    pub fn median_file_size(&self) -> u64 {
+        let mut sizes = self.file_sizes();
+        sizes.sort_unstable();
+        let middle = sizes.len() / 2;
+        match sizes.len() {
+            0 => 0,
+            count if count % 2 == 0 => sizes[middle - 1].midpoint(sizes[middle]),
+            _ => sizes[middle],
+        }
     }
 
+    /// Get the largest file in the torrent.
+    ///
+    /// Zero-length and padding files are ignored.
+    /// Single-file torrents return a file entry named after the torrent.
     #[must_use]
-    pub const fn encoding(&self) -> &Option<String> {
-        &self.encoding
+    pub fn largest_file(&self) -> Option<Cow<'_, File>> {
+        self.content_files().max_by_key(|file| file.length)
     }
 
-    /// Debug helper to print torrent structure
-    fn debug_torrent_structure(dict: &std::collections::HashMap<Vec<u8>, Value>) {
-        for (key, value) in dict {
-            let key_str = String::from_utf8_lossy(key);
-            match value {
-                Value::List(list) => {
-                    eprintln!("  {}: List with {} elements", key_str, list.len());
-                    if key_str == "announce-list" {
-                        eprintln!("    announce-list structure issue detected");
-                    }
-                }
-                Value::Bytes(bytes) => {
-                    eprintln!("  {key_str}: Bytes ({} bytes)", bytes.len());
+    /// Get the smallest file in the torrent.
+    ///
+    /// Zero-length and padding files are ignored.
+    /// Single-file torrents return a file entry named after the torrent.
+    #[must_use]
+    pub fn smallest_file(&self) -> Option<Cow<'_, File>> {
+        self.content_files().min_by_key(|file| file.length)
+    }
+
+    /// Check if both torrents describe the same content.
+    ///
+    /// Compares the torrent name and the paths and lengths of all files,
+    /// ignoring file order, padding files, piece layout and trackers.
+    /// This finds re-pieced copies of a torrent that have a different info hash.
+    #[must_use]
+    pub fn same_content(&self, other: &Self) -> bool {
+        self.info.name == other.info.name && self.content_signature() == other.content_signature()
+    }
+
+    /// Get the byte offset of the file at the given index within the concatenated torrent data.
+    ///
+    /// This is the sum of all preceding file lengths, including padding files
+    /// since they occupy space in the pieces even though they are not real content.
+    /// Returns `None` if the index is out of bounds.
+    #[must_use]
+    pub fn file_offset(&self, index: usize) -> Option<u64> {
+        let layout = self.file_layout();
+        let preceding = layout.get(..=index)?.split_last()?.1;
+        Some(preceding.iter().map(|(_, length)| length).sum())
+    }
+
+    /// Get the range of piece indices that contain data of the file at the given index.
+    ///
+    /// Zero-length files occupy no pieces and return an empty range.
+    /// Returns `None` if the index is out of bounds or the piece length is invalid.
+    #[must_use]
+    pub fn file_piece_range(&self, index: usize) -> Option<Range<usize>> {
+        let piece_length = u64::try_from(self.info.piece_length)
+            .ok()
+            .filter(|length| *length > 0)?;
+        let (_, length) = self.file_layout().into_iter().nth(index)?;
+        let offset = self.file_offset(index)?;
+        let start = usize::try_from(offset / piece_length).ok()?;
+        if length == 0 {
+            return Some(start..start);
+        }
+        let end = usize::try_from((offset + length - 1) / piece_length + 1).ok()?;
+        Some(start..end)
+    }
+
+    /// Get the parts of files covered by the piece at the given index, in data order.
+    ///
+    /// This is the inverse of [`Torrent::file_piece_range`]: a piece that straddles a file boundary
+    /// returns a span for each file it covers. Zero-length files are never covered by a piece.
+    /// Use [`Torrent::piece_file_map`] to get the spans of all pieces.
+    /// Returns `None` if the index is out of bounds or the piece length is invalid.
+    #[must_use]
+    pub fn piece_file_spans(&self, piece: usize) -> Option<Vec<PieceSpan>> {
+        let piece_length = u64::try_from(self.info.piece_length)
+            .ok()
+            .filter(|length| *length > 0)?;
+        if piece >= self.num_pieces() {
+            return None;
+        }
+        let piece_start = piece as u64 * piece_length;
+        let piece_end = (piece_start + piece_length).min(self.total_size_bytes());
+
+        let mut spans = Vec::new();
+        let mut file_start = 0;
+        for (file_index, (_, length)) in self.file_layout().into_iter().enumerate() {
+            let file_end = file_start + length;
+            if file_start >= piece_end {
+                break;
+            }
+            if length > 0 && file_end > piece_start {
+                spans.push(PieceSpan {
+                    file_index,
+                    range: piece_start.max(file_start) - file_start..piece_end.min(file_end) - file_start,
+                });
+            }
+            file_start = file_end;
+        }
+        Some(spans)
+    }
+
+    /// Get the parts of files covered by each piece, in piece order.
+    ///
+    /// Yields the same spans as [`Torrent::piece_file_spans`] for every piece,
+    /// but builds the file layout once and walks it with a cursor instead of scanning all files per piece.
+    /// Yields nothing if the piece length is invalid.
+    pub fn piece_file_map(&self) -> impl Iterator<Item = Vec<PieceSpan>> + use<> {
+        let piece_length = u64::try_from(self.info.piece_length)
+            .ok()
+            .filter(|length| *length > 0)
+            .unwrap_or(0);
+        let num_pieces = if piece_length == 0 { 0 } else { self.num_pieces() };
+        let total_size = self.total_size_bytes();
+        let lengths: Vec<u64> = self.file_layout().into_iter().map(|(_, length)| length).collect();
+        let mut cursor = 0;
+        let mut cursor_start = 0;
+        (0..num_pieces).map(move |piece| {
+            let piece_start = piece as u64 * piece_length;
+            let piece_end = (piece_start + piece_length).min(total_size);
+            // Skip the files that end before this piece, pieces never move backwards
+            while let Some(length) = lengths
+                .get(cursor)
+                .copied()
+                .filter(|length| cursor_start + length <= piece_start)
+            {
+                cursor_start += length;
+                cursor += 1;
+            }
+
+            let mut spans = Vec::new();
+            let mut file_start = cursor_start;
+            for (file_index, &length) in lengths.iter().enumerate().skip(cursor) {
+                if file_start >= piece_end {
+                    break;
                 }
-                Value::Int(i) => {
-                    eprintln!("  {key_str}: Integer ({i})");
+                let file_end = file_start + length;
+                if length > 0 {
+                    spans.push(PieceSpan {
+                        file_index,
+                        range: piece_start.max(file_start) - file_start..piece_end.min(file_end) - file_start,
+                    });
                 }
-                Value::Dict(_) => {
-                    eprintln!("  {key_str}: Dictionary");
+                file_start = file_end;
+            }
+            spans
+        })
+    }
+
+    /// Match the files of the torrent against the downloaded files under the given directory.
+    ///
+    /// Uses the same locations as [`Torrent::verify_files`] and only checks file sizes,
+    /// so it is much faster than verification but can not detect corrupt data.
+    /// Padding files are skipped since clients usually do not write them to disk.
+    #[must_use]
+    pub fn match_existing(&self, root: &Path) -> Vec<FileMatch> {
+        let padding: Vec<bool> = self
+            .info
+            .files
+            .as_ref()
+            .map_or_else(|| vec![false], |files| files.iter().map(File::is_padding).collect());
+        self.disk_layout(root)
+            .into_iter()
+            .zip(padding)
+            .filter(|(_, is_padding)| !is_padding)
+            .map(|((path, length), _)| {
+                let size_on_disk = std::fs::metadata(&path)
+                    .ok()
+                    .filter(std::fs::Metadata::is_file)
+                    .map(|metadata| metadata.len());
+                FileMatch {
+                    path,
+                    length,
+                    size_on_disk,
                 }
+            })
+            .collect()
+    }
+
+    /// Verify the downloaded files under the given directory against the piece hashes.
+    ///
+    /// Files are expected at `root/<name>` for single-file torrents
+    /// and at `root/<name>/<path>` for multi-file torrents.
+    /// Pieces overlapping a missing or truncated file are reported as missing.
+    /// Zero-length files are never read, so they are not reported even if absent.
+    pub fn verify_files(&self, root: &Path) -> Result<VerifyReport> {
+        let piece_length = self.info.piece_length_bytes()?;
+        let mut report = VerifyReport {
+            total_pieces: self.num_pieces(),
+            ..VerifyReport::default()
+        };
+        let mut files = self.open_files(root, &mut report)?;
+
+        let total_size = self.total_size_bytes();
+        let mut buffer = vec![0; piece_length];
+        for index in 0..report.total_pieces {
+            let Some(filled) = read_piece(&mut files, index, piece_length, total_size, &mut buffer)? else {
+                report.missing_pieces.push(index);
+                continue;
+            };
+            report.bytes_read += filled as u64;
+            let digest = Sha1::digest(&buffer[..filled]);
+            if self.info.piece_hash(index) != Some(digest.as_slice()) {
+                report.failed_pieces.push(index);
             }
         }
+        Ok(report)
     }
-}
 
-impl Info {
+    /// Verify the downloaded files like [`Torrent::verify_files`], hashing pieces on multiple threads.
+    ///
+    /// Pieces are read sequentially in batches of about 64 MiB, and each batch is hashed in parallel,
+    /// so memory use stays bounded regardless of the torrent size.
+    /// The result is identical to the serial version.
+    /// This only pays off with multiple cores and storage that reads faster than a single core hashes,
+    /// such as a fast SSD or data already in the page cache.
+    #[cfg(feature = "parallel")]
+    pub fn verify_files_parallel(&self, root: &Path) -> Result<VerifyReport> {
+        self.verify_files_in_batches(root, PARALLEL_BATCH_BYTES)
+    }
+
+    /// Get all tracker URLs without duplicates.
+    ///
+    /// The primary announce URL comes first, followed by the announce-list tiers in order.
     #[must_use]
-    pub const fn name(&self) -> &Option<String> {
-        &self.name
+    pub fn trackers(&self) -> Vec<&str> {
+        let announce_list = self.announce_list.iter().flatten().flatten();
+        let mut seen = HashSet::new();
+        let mut trackers: Vec<&str> = Vec::new();
+        for url in self.announce.iter().chain(announce_list) {
+            if seen.insert(normalize_tracker_url(url)) {
+                trackers.push(url);
+            }
+        }
+        trackers
     }
 
+    /// Get the tracker that clients announce to first.
+    ///
+    /// This is the `announce` URL if present, otherwise the first URL of the first non-empty announce-list tier,
+    /// since some creators only write the announce-list.
     #[must_use]
-    pub const fn piece_length(&self) -> &i64 {
-        &self.piece_length
+    pub fn primary_tracker(&self) -> Option<&str> {
+        self.announce.as_deref().or_else(|| {
+            self.announce_list
+                .iter()
+                .flatten()
+                .find_map(|tier| tier.first())
+                .map(String::as_str)
+        })
     }
 
+    /// Get the number of distinct trackers across the primary announce URL and all announce-list tiers
     #[must_use]
-    pub const fn pieces(&self) -> &ByteBuf {
-        &self.pieces
+    pub fn tracker_count(&self) -> usize {
+        self.trackers().len()
     }
 
+    /// Check if there is more than one distinct tracker, so the torrent does not depend on a single tracker
     #[must_use]
-    pub const fn private(&self) -> &Option<u8> {
-        &self.private
+    pub fn has_backup_trackers(&self) -> bool {
+        self.tracker_count() > 1
     }
-}
 
-impl File {
+    /// Get the DHT bootstrap nodes as host and port pairs.
+    ///
+    /// Nodes from the non-standard `x-nodes` and `dht-nodes` keys follow the standard `nodes`,
+    /// with duplicates removed.
     #[must_use]
-    pub const fn new(length: i64, path: Vec<String>) -> Self {
-        Self {
-            length,
-            path,
-            md5sum: None,
+    pub fn nodes(&self) -> Vec<(&str, i64)> {
+        let mut seen = HashSet::new();
+        [&self.nodes, &self.x_nodes, &self.dht_nodes]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|node| (node.0.as_str(), node.1))
+            .filter(|node| seen.insert(*node))
+            .collect()
+    }
+
+    /// Get the number of DHT bootstrap nodes
+    #[must_use]
+    pub fn num_nodes(&self) -> usize {
+        self.nodes().len()
+    }
+
+    /// Check if clients may use DHT for this torrent.
+    ///
+    /// Private torrents must only get peers from their trackers, so DHT and peer exchange are disabled.
+    #[must_use]
+    pub fn can_use_dht(&self) -> bool {
+        self.info.private.is_none_or(|private| private == 0)
+    }
+
+    /// Get the web seed URLs from the BEP 19 `url-list` followed by the BEP 17 `httpseeds`, without duplicates
+    #[must_use]
+    pub fn web_seeds(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        [&self.url_list, &self.httpseeds]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(String::as_str)
+            .filter(|url| seen.insert(*url))
+            .collect()
+    }
+
+    /// Get advisories for tracker setups that conflict with the `private` flag.
+    ///
+    /// Private torrents should only announce to their private tracker, so listing public trackers leaks peers
+    /// and a private torrent without trackers can not find any peers at all.
+    /// Public torrents without trackers or DHT nodes rely on clients that already know other DHT nodes.
+    /// Trackers count as public if they use UDP, which private trackers rarely support,
+    /// or are one of a few well-known open trackers.
+    #[must_use]
+    pub fn tracker_privacy_warnings(&self) -> Vec<String> {
+        let trackers = self.trackers();
+        let mut warnings = Vec::new();
+        if self.can_use_dht() {
+            if trackers.is_empty() && self.num_nodes() == 0 {
+                warnings.push(
+                    "public torrent without trackers or DHT nodes, peers can only be found by clients already on DHT"
+                        .to_string(),
+                );
+            }
+            return warnings;
+        }
+
+        if trackers.is_empty() {
+            warnings.push("private torrent without trackers, peers can not be found".to_string());
+        }
+        let public_trackers: Vec<&str> = trackers.into_iter().filter(|url| is_public_tracker(url)).collect();
+        if !public_trackers.is_empty() {
+            warnings.push(format!(
+                "private torrent lists {} public tracker{}: {}",
+                public_trackers.len(),
+                if public_trackers.len() == 1 { "" } else { "s" },
+                public_trackers.join(", ")
+            ));
         }
+        warnings
     }
 
+    /// Check if peers can be found without any tracker, using DHT and the bootstrap nodes in the torrent
     #[must_use]
-    pub const fn length(&self) -> i64 {
-        self.length
+    pub fn is_trackerless_capable(&self) -> bool {
+        self.can_use_dht() && self.num_nodes() > 0
     }
 
+    /// Count the trackers by URL scheme, for example `https`, `udp` or `wss` for trackers used by browser clients.
+    ///
+    /// Schemes are lowercase, and trackers without a scheme are counted as `unknown`.
     #[must_use]
-    pub fn path(&self) -> &[String] {
-        &self.path
+    pub fn tracker_schemes(&self) -> HashMap<String, usize> {
+        let mut schemes = HashMap::new();
+        for url in self.trackers() {
+            let scheme = url
+                .split_once("://")
+                .map_or_else(|| UNKNOWN_NAME.to_string(), |(scheme, _)| scheme.to_ascii_lowercase());
+            *schemes.entry(scheme).or_insert(0) += 1;
+        }
+        schemes
     }
-}
 
-/// Convert bytes to hexadecimal string representation
-#[must_use]
-pub fn to_hex(bytes: &[u8]) -> String {
-    let mut result = String::with_capacity(bytes.len() * 2);
-    for &byte in bytes {
-        result.push(HEX_CHARS[(byte >> 4) as usize] as char);
-        result.push(HEX_CHARS[(byte & 0xf) as usize] as char);
+    /// Get tracker URLs grouped by announce-list tier.
+    ///
+    /// The primary announce URL is inserted as its own first tier
+    /// if it does not appear in any tier, so clients can fail over in the intended order.
+    /// Empty tiers are skipped.
+    #[must_use]
+    pub fn tracker_tiers(&self) -> Vec<Vec<&str>> {
+        let mut tiers: Vec<Vec<&str>> = self
+            .announce_list
+            .iter()
+            .flatten()
+            .map(|tier| tier.iter().map(String::as_str).collect::<Vec<&str>>())
+            .filter(|tier| !tier.is_empty())
+            .collect();
+        if let Some(announce) = self.announce.as_deref()
+            && !tiers.iter().flatten().any(|url| *url == announce)
+        {
+            tiers.insert(0, vec![announce]);
+        }
+        tiers
+    }
+
+    /// Add a tracker URL to the given announce-list tier.
+    ///
+    /// The announce-list is created from the primary announce URL if it does not exist yet,
+    /// and a new tier is appended if the given tier does not exist.
+    /// The primary announce URL is set if it is missing.
+    /// Returns `false` if the tracker is already present, compared with [`normalize_tracker_url`].
+    pub fn add_tracker(&mut self, url: &str, tier: usize) -> bool {
+        let normalized = normalize_tracker_url(url);
+        if self
+            .trackers()
+            .into_iter()
+            .any(|tracker| normalize_tracker_url(tracker) == normalized)
+        {
+            return false;
+        }
+
+        let announce_list = self
+            .announce_list
+            .get_or_insert_with(|| self.announce.iter().map(|announce| vec![announce.clone()]).collect());
+        match announce_list.get_mut(tier) {
+            Some(urls) => urls.push(url.to_string()),
+            None => announce_list.push(vec![url.to_string()]),
+        }
+
+        if self.announce.is_none() {
+            self.announce = Some(url.to_string());
+        }
+        true
+    }
+
+    /// Add all trackers from another torrent, keeping their tier placement.
+    ///
+    /// Returns the number of trackers that were added.
+    pub fn merge_trackers_from(&mut self, other: &Self) -> usize {
+        let other_tiers: Vec<Vec<String>> = other
+            .announce_list
+            .clone()
+            .unwrap_or_else(|| other.announce.iter().map(|announce| vec![announce.clone()]).collect());
+
+        let mut added = 0;
+        for (tier, urls) in other_tiers.iter().enumerate() {
+            for url in urls {
+                if self.add_tracker(url, tier) {
+                    added += 1;
+                }
+            }
+        }
+        added
     }
-    result
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Replace a tracker URL in the announce URL and the announce-list.
+    ///
+    /// Only URLs equal to `from` are replaced, see [`Torrent::replace_tracker_substring`] for partial matches.
+    /// Trackers are not part of the info dictionary, so the info hash does not change.
+    /// Returns the number of URLs that were replaced.
+    pub fn replace_tracker(&mut self, from: &str, to: &str) -> usize {
+        self.replace_tracker_urls(|url| (url == from).then(|| to.to_string()))
+    }
 
-    /// Path to the Ubuntu test torrent file
-    const UBUNTU_TORRENT: &str = "tests/ubuntu-24.04.3-desktop-amd64.iso.torrent";
-    /// Expected file size in bytes for the Ubuntu ISO
-    const UBUNTU_SIZE: i64 = 6_345_887_744;
-    /// Expected info hash for the Ubuntu torrent
-    const UBUNTU_INFO_HASH: &str = "d160b8d8ea35a5b4e52837468fc8f03d55cef1f7";
+    /// Replace every occurrence of `from` within the tracker URLs, for example to change only the host.
+    ///
+    /// Returns the number of URLs that were changed, which is zero for an empty `from`.
+    pub fn replace_tracker_substring(&mut self, from: &str, to: &str) -> usize {
+        if from.is_empty() {
+            return 0;
+        }
+        self.replace_tracker_urls(|url| url.contains(from).then(|| url.replace(from, to)))
+    }
+
+    /// Apply a replacement to each tracker URL and count the URLs that changed
+    fn replace_tracker_urls(&mut self, replacement: impl Fn(&str) -> Option<String>) -> usize {
+        let mut replaced = 0;
+        let urls = self
+            .announce
+            .iter_mut()
+            .chain(self.announce_list.iter_mut().flatten().flatten());
+        for url in urls {
+            if let Some(new_url) = replacement(url).filter(|new_url| new_url != url) {
+                *url = new_url;
+                replaced += 1;
+            }
+        }
+        replaced
+    }
+
+    /// Build a magnet URI with the info hash, display name and all trackers
+    pub fn magnet_link(&self) -> Result<String> {
+        let mut magnet = format!("magnet:?xt=urn:btih:{}", to_hex(&self.info_hash()?));
+        if let Some(name) = &self.info.name {
+            magnet.push_str("&dn=");
+            magnet.push_str(&percent_encode(name.as_bytes()));
+        }
+        for tracker in self.trackers() {
+            magnet.push_str("&tr=");
+            magnet.push_str(&percent_encode(tracker.as_bytes()));
+        }
+        Ok(magnet)
+    }
+
+    /// Build the HTTP announce request URL a client would send to the given tracker.
+    ///
+    /// The info hash and peer id are percent-encoded as raw bytes as required by the `BitTorrent` spec.
+    pub fn announce_request_url(&self, tracker: &str, peer_id: &[u8], port: u16) -> Result<String> {
+        let info_hash = self.info_hash()?;
+        let separator = if tracker.contains('?') { '&' } else { '?' };
+        Ok(format!(
+            "{tracker}{separator}info_hash={}&peer_id={}&port={port}&uploaded=0&downloaded=0&left={}&compact=1",
+            percent_encode(&info_hash),
+            percent_encode(peer_id),
+            self.total_size_bytes()
+        ))
+    }
+
+    /// Rate whether the piece count is reasonable for the torrent size
+    #[must_use]
+    pub fn piece_count_health(&self) -> PieceHealth {
+        match self.num_pieces() {
+            count if count < MIN_RECOMMENDED_PIECES => PieceHealth::TooFew,
+            count if count > MAX_RECOMMENDED_PIECES => PieceHealth::TooMany,
+            _ => PieceHealth::Ok,
+        }
+    }
+
+    /// Calculate SHA-1 info hash directly from bencoded torrent data.
+    ///
+    /// The `info` dictionary bytes are hashed exactly as stored, like [`Torrent::info_hash`] does for a parsed torrent,
+    /// without decoding the rest of the torrent into a `Torrent`.
+    pub fn info_hash_from_bytes(buf: &[u8]) -> Result<Vec<u8>> {
+        let (serde::de::IgnoredAny, data) = decode_lenient(buf)?;
+        let info_range = raw_info_range(data).ok_or(Error::MissingField("info"))?;
+        Ok(Sha1::digest(&data[info_range]).to_vec())
+    }
+
+    /// Read the name, size and info hash of a torrent file without a full parse.
+    ///
+    /// See [`Torrent::peek_buf`].
+    pub fn peek<P: AsRef<Path>>(path: P) -> Result<TorrentSummary> {
+        let buf = Self::read_bytes(path.as_ref())?;
+        Self::peek_buf(&buf)
+    }
+
+    /// Read the name, size and info hash from bencoded torrent data without a full parse.
+    ///
+    /// Decodes the same fields as [`TorrentSummary::from_buf`], and calculates the info hash
+    /// from the info dictionary bytes exactly as stored, which matches [`Torrent::info_hash`].
+    pub fn peek_buf(buf: &[u8]) -> Result<TorrentSummary> {
+        TorrentSummary::decode(buf, true)
+    }
+
+    /// Calculate SHA-1 info hash, see [`Torrent::info_bytes`]
+    pub fn info_hash(&self) -> Result<Vec<u8>> {
+        self.info_hash_with::<Sha1>()
+    }
+
+    /// Calculate the BEP 52 (v2) SHA-256 info hash
+    pub fn info_hash_v2(&self) -> Result<Vec<u8>> {
+        self.info_hash_with::<Sha256>()
+    }
+
+    /// Calculate the info hash with the given digest implementation.
+    ///
+    /// Allows plugging in alternative or hardware-accelerated hash backends
+    /// that implement the `Digest` trait.
+    pub fn info_hash_with<H: Digest>(&self) -> Result<Vec<u8>> {
+        let info = self.info_bytes()?;
+        Ok(H::digest(&info).to_vec())
+    }
+
+    /// Get the bencoded info dictionary that the info hash is calculated from.
+    ///
+    /// For parsed torrents these are the bytes exactly as stored, including keys that are not modeled by `Info`,
+    /// until `info` is changed through [`Torrent::info_mut`], [`Torrent::set_name`] or [`Torrent::set_private`].
+    /// Otherwise, and for torrents built in code or parsed with [`Torrent::from_buf_metadata_only`],
+    /// `info` is re-encoded canonically with sorted keys.
+    pub fn info_bytes(&self) -> Result<Vec<u8>> {
+        match &self.raw_info {
+            Some(raw_info) => Ok(raw_info.clone()),
+            None => Ok(ser::to_bytes(&self.info)?),
+        }
+    }
+
+    #[must_use]
+    pub const fn info(&self) -> &Info {
+        &self.info
+    }
+
+    /// Get the info dictionary for modification.
+    ///
+    /// Drops the info bytes stored when parsing, so the info hash is calculated from the changed `info`.
+    pub fn info_mut(&mut self) -> &mut Info {
+        self.raw_info = None;
+        &mut self.info
+    }
+
+    #[must_use]
+    pub const fn name(&self) -> &Option<String> {
+        &self.info.name
+    }
+
+    /// Get the name of the content, meaning the file name for single-file torrents
+    /// and the root directory for multi-file torrents.
+    ///
+    /// Falls back to the first component of the first file path if the name is missing,
+    /// and to `unknown` if there are no files either.
+    #[must_use]
+    pub fn content_name(&self) -> Cow<'_, str> {
+        self.derived_content_name().unwrap_or(Cow::Borrowed(UNKNOWN_NAME))
+    }
+
+    /// Get the name of the content, falling back to the source torrent filename.
+    ///
+    /// Same as [`Torrent::content_name`] but uses the file stem of `source`
+    /// before falling back to `unknown`.
+    #[must_use]
+    pub fn content_name_or_source<'a>(&'a self, source: &'a Path) -> Cow<'a, str> {
+        self.derived_content_name()
+            .or_else(|| source.file_stem().map(|stem| stem.to_string_lossy()))
+            .unwrap_or(Cow::Borrowed(UNKNOWN_NAME))
+    }
+
+    /// Get the torrent name sanitized for use as a filename.
+    ///
+    /// Returns `None` if the torrent does not have a name.
+    #[must_use]
+    pub fn safe_name(&self) -> Option<String> {
+        self.info.name.as_deref().map(sanitize_filename)
+    }
+
+    /// Guess the content category from the file extensions in the torrent.
+    ///
+    /// For single-file torrents the torrent name is used as the file name.
+    #[must_use]
+    pub fn guess_category(&self) -> Category {
+        if let Some(files) = &self.info.files {
+            return Category::from_file_names(files.iter().filter_map(|file| file.path().last()).map(String::as_str));
+        }
+        let file_tree_entries = self.info.file_tree_entries();
+        if file_tree_entries.is_empty() {
+            Category::from_file_names(self.info.name.as_deref())
+        } else {
+            Category::from_file_names(
+                file_tree_entries
+                    .iter()
+                    .filter_map(|entry| entry.path.last())
+                    .map(String::as_str),
+            )
+        }
+    }
+
+    /// Check the torrent for traits of fake or spam torrents, see [`SuspiciousTrait`]
+    #[must_use]
+    pub fn looks_suspicious(&self) -> Vec<SuspiciousTrait> {
+        SuspiciousTrait::detect(self)
+    }
+
+    #[must_use]
+    pub const fn comment(&self) -> &Option<String> {
+        &self.comment
+    }
+
+    #[must_use]
+    pub const fn announce(&self) -> &Option<String> {
+        &self.announce
+    }
+
+    #[must_use]
+    pub const fn announce_list(&self) -> &Option<Vec<Vec<String>>> {
+        &self.announce_list
+    }
+
+    #[must_use]
+    pub const fn created_by(&self) -> &Option<String> {
+        &self.created_by
+    }
+
+    #[must_use]
+    pub const fn creation_date(&self) -> &Option<i64> {
+        &self.creation_date
+    }
+
+    #[must_use]
+    pub const fn encoding(&self) -> &Option<String> {
+        &self.encoding
+    }
+
+    /// Get the torrent name or derive it from the first file path
+    fn derived_content_name(&self) -> Option<Cow<'_, str>> {
+        if let Some(name) = self.info.name.as_deref().filter(|name| !name.is_empty()) {
+            return Some(Cow::Borrowed(name));
+        }
+        if let Some(files) = &self.info.files {
+            return files
+                .iter()
+                .find_map(|file| file.path().first())
+                .map(|component| Cow::Borrowed(component.as_str()));
+        }
+        self.info
+            .file_tree_entries()
+            .into_iter()
+            .find_map(|entry| entry.path.into_iter().next())
+            .map(Cow::Owned)
+    }
+
+    /// Get the size of each file that is not padding in bytes.
+    ///
+    /// Falls back to the v2 file tree when the torrent has no v1 file information.
+    fn file_sizes(&self) -> Vec<u64> {
+        self.iter_files()
+            .map(|file| u64::try_from(file.length).unwrap_or(0))
+            .collect()
+    }
+
+    /// Get the sorted paths and lengths of all files that are not padding, see [`Torrent::iter_files`]
+    fn content_signature(&self) -> Vec<(Vec<String>, i64)> {
+        let mut signature: Vec<(Vec<String>, i64)> = self
+            .iter_files()
+            .map(|file| (file.path().to_vec(), file.length))
+            .collect();
+        signature.sort();
+        signature
+    }
+
+    /// Get all files in the torrent, including padding files, as a list for every torrent layout.
+    ///
+    /// Single-file torrents return one file with the content name as the path and the MD5 checksum of the torrent,
+    /// and torrents without v1 file information return the files of the v2 file tree.
+    /// Use [`Torrent::iter_files`] to skip padding files without copying the file list.
+    #[must_use]
+    pub fn files_normalized(&self) -> Vec<File> {
+        self.normalized_files().into_iter().map(Cow::into_owned).collect()
+    }
+
+    /// Iterate over all files that are not padding.
+    ///
+    /// Files are the same as in [`Torrent::files_normalized`], but only single-file and v2-only torrents are copied.
+    pub fn iter_files(&self) -> impl Iterator<Item = Cow<'_, File>> {
+        self.normalized_files().into_iter().filter(|file| !file.is_padding())
+    }
+
+    /// Get all files, borrowing the v1 file list when there is one
+    fn normalized_files(&self) -> Vec<Cow<'_, File>> {
+        match (&self.info.files, self.info.length) {
+            (Some(files), _) => files.iter().map(Cow::Borrowed).collect(),
+            (None, Some(length)) => {
+                let mut file = File::new(length, vec![self.content_name().into_owned()]);
+                file.md5sum.clone_from(&self.info.md5sum);
+                vec![Cow::Owned(file)]
+            }
+            (None, None) => self
+                .info
+                .file_tree_entries()
+                .into_iter()
+                .map(|entry| Cow::Owned(File::new(entry.length, entry.path)))
+                .collect(),
+        }
+    }
+
+    /// Calculate a SHA-256 fingerprint of the content as lowercase hex.
+    ///
+    /// Only the name and the sorted relative paths and lengths of the files are hashed,
+    /// so torrents with the same content but different piece lengths, trackers or padding files
+    /// share a fingerprint even though their info hashes differ.
+    /// Two torrents share a fingerprint exactly when [`Torrent::same_content`] is true.
+    #[must_use]
+    pub fn content_fingerprint(&self) -> String {
+        // Length prefixes keep the boundaries between the name and the path components unambiguous
+        let mut hasher = Sha256::new();
+        let name = self.info.name.as_deref().map(str::as_bytes);
+        hasher.update(name.map_or(u64::MAX, |name| name.len() as u64).to_be_bytes());
+        hasher.update(name.unwrap_or_default());
+        for (path, length) in self.content_signature() {
+            hasher.update((path.len() as u64).to_be_bytes());
+            for component in &path {
+                hasher.update((component.len() as u64).to_be_bytes());
+                hasher.update(component.as_bytes());
+            }
+            hasher.update(length.to_be_bytes());
+        }
+        to_hex(&hasher.finalize())
+    }
+
+    /// Check if any file has one of the given extensions.
+    ///
+    /// Extensions are compared case-insensitively and may be given with or without the leading dot.
+    #[must_use]
+    pub fn contains_extension<S: AsRef<str>>(&self, extensions: &[S]) -> bool {
+        let extensions: Vec<String> = extensions
+            .iter()
+            .map(|extension| extension.as_ref().trim_start_matches('.').to_lowercase())
+            .collect();
+        self.iter_files().any(|file| {
+            file.path
+                .last()
+                .and_then(|name| Path::new(name).extension())
+                .is_some_and(|extension| extensions.contains(&extension.to_string_lossy().to_lowercase()))
+        })
+    }
+
+    /// Iterate over non-empty files that are not padding.
+    ///
+    /// Single-file torrents yield a synthetic entry with the torrent name as the path.
+    fn content_files(&self) -> impl Iterator<Item = Cow<'_, File>> {
+        let files: Vec<Cow<'_, File>> = self.info.files.as_ref().map_or_else(
+            || {
+                self.info
+                    .length
+                    .map(|length| Cow::Owned(File::new(length, self.info.name.iter().cloned().collect())))
+                    .into_iter()
+                    .collect()
+            },
+            |files| files.iter().map(Cow::Borrowed).collect(),
+        );
+        files.into_iter().filter(|file| file.length > 0 && !file.is_padding())
+    }
+
+    /// Parallel verification reading about `batch_bytes` of piece data at a time
+    #[cfg(feature = "parallel")]
+    fn verify_files_in_batches(&self, root: &Path, batch_bytes: usize) -> Result<VerifyReport> {
+        use rayon::prelude::*;
+
+        let piece_length = self.info.piece_length_bytes()?;
+        let mut report = VerifyReport {
+            total_pieces: self.num_pieces(),
+            ..VerifyReport::default()
+        };
+        let mut files = self.open_files(root, &mut report)?;
+
+        let total_size = self.total_size_bytes();
+        let batch_size = (batch_bytes / piece_length).max(rayon::current_num_threads());
+        let mut buffers = vec![vec![0; piece_length]; batch_size.min(report.total_pieces)];
+        for batch_start in (0..report.total_pieces).step_by(batch_size) {
+            let batch_end = (batch_start + batch_size).min(report.total_pieces);
+            let mut pieces = Vec::with_capacity(batch_end - batch_start);
+            for (index, buffer) in (batch_start..batch_end).zip(buffers.iter_mut()) {
+                match read_piece(&mut files, index, piece_length, total_size, buffer)? {
+                    Some(filled) => {
+                        report.bytes_read += filled as u64;
+                        pieces.push((index, &buffer[..filled]));
+                    }
+                    None => report.missing_pieces.push(index),
+                }
+            }
+            let failed: Vec<usize> = pieces
+                .par_iter()
+                .filter(|(index, data)| self.info.piece_hash(*index) != Some(Sha1::digest(data).as_slice()))
+                .map(|(index, _)| *index)
+                .collect();
+            report.failed_pieces.extend(failed);
+        }
+        Ok(report)
+    }
+
+    /// Open the non-empty files of the torrent under the given directory in piece layout order.
+    ///
+    /// Files that do not exist are recorded as missing in the report and have no handle.
+    fn open_files(&self, root: &Path, report: &mut VerifyReport) -> Result<Vec<(Option<StdFile>, u64)>> {
+        let mut files = Vec::new();
+        for (filepath, length) in self.disk_layout(root) {
+            if length == 0 {
+                continue;
+            }
+            let handle = match StdFile::open(&filepath) {
+                Ok(handle) => Some(handle),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    report.missing_files.push(filepath);
+                    None
+                }
+                Err(e) => return Err(e.into()),
+            };
+            files.push((handle, length));
+        }
+        Ok(files)
+    }
+
+    /// Get the expected location on disk and length of each file, in piece layout order.
+    ///
+    /// Files are placed at `root/<name>` for single-file torrents
+    /// and at `root/<name>/<path>` for multi-file torrents.
+    fn disk_layout(&self, root: &Path) -> Vec<(PathBuf, u64)> {
+        let base = root.join(self.info.name.as_deref().unwrap_or_default());
+        let multi_file = self.info.files.is_some();
+        self.file_layout()
+            .into_iter()
+            .map(|(path, length)| {
+                let filepath = if multi_file {
+                    path.iter()
+                        .fold(base.clone(), |filepath, component| filepath.join(component))
+                } else {
+                    base.clone()
+                };
+                (filepath, length)
+            })
+            .collect()
+    }
+
+    /// Get the path and length of each file in the order the data is laid out in pieces.
+    ///
+    /// Single-file torrents use the torrent name as the path.
+    fn file_layout(&self) -> Vec<(Vec<String>, u64)> {
+        self.info.files.as_ref().map_or_else(
+            || vec![(self.info.name.iter().cloned().collect(), self.total_size_bytes())],
+            |files| {
+                files
+                    .iter()
+                    .map(|file| (file.path().to_vec(), u64::try_from(file.length).unwrap_or(0)))
+                    .collect()
+            },
+        )
+    }
+
+    /// Check the piece layout and file information rules of [`Torrent::from_buf_strict`]
+    fn check_strict(&self) -> Result<()> {
+        let piece_length = self.info.piece_length;
+        if u64::try_from(piece_length).map_or(true, |length| !length.is_power_of_two()) {
+            return Err(Error::InvalidPieceLength(piece_length));
+        }
+        if !self.pieces_are_valid_length() {
+            return Err(Error::InvalidPieces(self.info.pieces.len()));
+        }
+        match (&self.info.files, self.info.length) {
+            (Some(_), Some(_)) => Err(Error::InvalidFiles("both `length` and `files`")),
+            (None, None) if !self.info.is_v2() => Err(Error::InvalidFiles("neither `length` nor `files`")),
+            _ => Ok(()),
+        }
+    }
+
+    /// Convert a failed typed decode into an error, logging the torrent structure for debugging.
+    ///
+    /// Data that decodes as a dictionary but lacks a required field
+    /// is reported as [`Error::MissingField`] instead of a generic decode error.
+    fn decode_error(buf: &[u8], error: serde_bencode::Error) -> Error {
+        let Ok(Value::Dict(dict)) = serde_bencode::from_bytes(buf) else {
+            return error.into();
+        };
+        log::debug!("Bencode decode error. Torrent structure:");
+        Self::debug_torrent_structure(&dict);
+        Self::missing_required_field(&dict).map_or_else(|| error.into(), Error::MissingField)
+    }
+
+    /// Find the first required field that is missing from a torrent dictionary.
+    ///
+    /// A missing `name` is only logged, since torrents without a name fall back to their file names.
+    fn missing_required_field(dict: &HashMap<Vec<u8>, Value>) -> Option<&'static str> {
+        let info = match dict.get(b"info".as_slice()) {
+            None => return Some("info"),
+            Some(Value::Dict(info)) => info,
+            Some(_) => return None,
+        };
+        if !info.contains_key(b"name".as_slice()) {
+            log::debug!("  info has no name");
+        }
+        (!info.contains_key(b"piece length".as_slice())).then_some("piece length")
+    }
+
+    /// Debug helper to print torrent structure
+    fn debug_torrent_structure(dict: &HashMap<Vec<u8>, Value>) {
+        for (key, value) in dict {
+            let key_str = String::from_utf8_lossy(key);
+            match value {
+                Value::List(list) => {
+                    log::debug!("  {}: List with {} elements", key_str, list.len());
+                    if key_str == "announce-list" {
+                        log::debug!("    announce-list structure issue detected");
+                    }
+                }
+                Value::Bytes(bytes) => {
+                    log::debug!("  {key_str}: Bytes ({} bytes)", bytes.len());
+                }
+                Value::Int(i) => {
+                    log::debug!("  {key_str}: Integer ({i})");
+                }
+                Value::Dict(_) => {
+                    log::debug!("  {key_str}: Dictionary");
+                }
+            }
+        }
+    }
+}
+
+impl Info {
+    #[must_use]
+    pub const fn name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    #[must_use]
+    pub const fn piece_length(&self) -> &i64 {
+        &self.piece_length
+    }
+
+    #[must_use]
+    pub const fn pieces(&self) -> &ByteBuf {
+        &self.pieces
+    }
+
+    #[must_use]
+    pub const fn private(&self) -> &Option<u8> {
+        &self.private
+    }
+
+    #[must_use]
+    pub const fn meta_version(&self) -> &Option<i64> {
+        &self.meta_version
+    }
+
+    /// Get the number of files described by the info dictionary.
+    ///
+    /// Multi-file torrents count the entries in `files`, including padding and zero-length files.
+    /// Single-file torrents have a `length` instead of `files` and always count as one file.
+    /// Torrents with neither fall back to the number of files in the v2 file tree,
+    /// which is zero for malformed torrents without any file information.
+    #[must_use]
+    pub fn file_count(&self) -> usize {
+        match (&self.files, self.length) {
+            (Some(files), _) => files.len(),
+            (None, Some(_)) => 1,
+            (None, None) => self.file_tree_entries().len(),
+        }
+    }
+
+    /// Check if the torrent contains v1 file information, which hybrid torrents have alongside the v2 metadata.
+    ///
+    /// Torrents with a `length` or `files` key count as v1 even if the piece hashes are missing or empty.
+    #[must_use]
+    pub const fn is_v1(&self) -> bool {
+        self.length.is_some() || self.files.is_some()
+    }
+
+    /// Check if the torrent contains BEP 52 (v2) metadata
+    #[must_use]
+    pub fn is_v2(&self) -> bool {
+        self.meta_version.is_some_and(|version| version >= 2) || self.file_tree.is_some()
+    }
+
+    /// Get all files from the v2 file tree in path order.
+    ///
+    /// Returns an empty list for v1-only torrents.
+    #[must_use]
+    pub fn file_tree_entries(&self) -> Vec<FileEntry> {
+        let mut entries = Vec::new();
+        if let Some(Value::Dict(tree)) = &self.file_tree {
+            collect_file_tree(tree, &mut Vec::new(), &mut entries);
+        }
+        entries
+    }
+
+    /// Get the number of 20-byte piece hashes
+    #[must_use]
+    pub fn num_pieces(&self) -> usize {
+        self.pieces.len() / PIECE_HASH_LENGTH
+    }
+
+    /// Get the range of piece indices covering `len` bytes starting at `offset` in the torrent data.
+    ///
+    /// Offsets are counted across all files in order, including padding files.
+    /// Ranges running past the last piece are clamped to the pieces that exist,
+    /// and an empty range or an invalid piece length returns an empty range.
+    #[must_use]
+    pub fn pieces_for_range(&self, offset: u64, len: u64) -> Range<usize> {
+        let num_pieces = self.num_pieces();
+        let Ok(piece_length) = self.piece_length_bytes() else {
+            return 0..0;
+        };
+        let piece_length = piece_length as u64;
+        let to_index = |piece: u64| usize::try_from(piece).map_or(num_pieces, |piece| piece.min(num_pieces));
+
+        let start = to_index(offset / piece_length);
+        if len == 0 {
+            return start..start;
+        }
+        let end = to_index(offset.saturating_add(len - 1) / piece_length + 1);
+        start..end
+    }
+
+    /// Get the expected SHA-1 hash for the piece at the given index
+    #[must_use]
+    pub fn piece_hash(&self, index: usize) -> Option<&[u8]> {
+        let start = index.checked_mul(PIECE_HASH_LENGTH)?;
+        self.pieces.get(start..start + PIECE_HASH_LENGTH)
+    }
+
+    /// Verify a single contiguous byte stream against the piece hashes.
+    ///
+    /// The stream is hashed incrementally one piece at a time,
+    /// so memory use is bounded by the piece length.
+    /// Pieces that the stream ends before are reported as missing.
+    pub fn verify_stream<R: Read>(&self, mut reader: R) -> Result<VerifyReport> {
+        let piece_length = self.piece_length_bytes()?;
+
+        let mut report = VerifyReport {
+            total_pieces: self.num_pieces(),
+            ..VerifyReport::default()
+        };
+        let mut buffer = vec![0; piece_length];
+        for index in 0..report.total_pieces {
+            let bytes_read = read_full(&mut reader, &mut buffer)?;
+            report.bytes_read += bytes_read as u64;
+            if bytes_read == 0 {
+                report.missing_pieces.push(index);
+                continue;
+            }
+            let digest = Sha1::digest(&buffer[..bytes_read]);
+            if self.piece_hash(index) != Some(digest.as_slice()) {
+                report.failed_pieces.push(index);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Get the piece length as a positive byte count
+    fn piece_length_bytes(&self) -> Result<usize> {
+        usize::try_from(self.piece_length)
+            .ok()
+            .filter(|length| *length > 0)
+            .ok_or(Error::InvalidPieceLength(self.piece_length))
+    }
+}
+
+impl From<(String, deserialize::Port)> for Node {
+    fn from((host, port): (String, deserialize::Port)) -> Self {
+        Self(host, port.0)
+    }
+}
+
+impl FileAttributes {
+    /// Decode the flags from a BEP 47 `attr` string, unknown characters are ignored
+    #[must_use]
+    pub fn from_attr(attr: &str) -> Self {
+        Self {
+            executable: attr.contains('x'),
+            symlink: attr.contains('l'),
+            padding: attr.contains('p'),
+            hidden: attr.contains('h'),
+        }
+    }
+
+    /// Check if no flags are set
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        !(self.executable || self.symlink || self.padding || self.hidden)
+    }
+}
+
+impl std::fmt::Display for FileAttributes {
+    /// Format the set flags in BEP 47 notation, for example `xh`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (enabled, flag) in [
+            (self.executable, 'x'),
+            (self.symlink, 'l'),
+            (self.padding, 'p'),
+            (self.hidden, 'h'),
+        ] {
+            if enabled {
+                write!(f, "{flag}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FileEntry {
+    #[must_use]
+    pub const fn length(&self) -> i64 {
+        self.length
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Get the 32-byte merkle root of the file's piece hashes.
+    ///
+    /// Empty files do not have a pieces root.
+    #[must_use]
+    pub fn pieces_root(&self) -> Option<&[u8]> {
+        self.pieces_root.as_deref()
+    }
+}
+
+impl TorrentSummary {
+    /// Create `TorrentSummary` from a file path
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let buf = Torrent::read_bytes(path.as_ref())?;
+        Self::from_buf(&buf)
+    }
+
+    /// Create `TorrentSummary` from bytes, skipping the fields not needed for listing
+    pub fn from_buf(buf: &[u8]) -> Result<Self> {
+        Self::decode(buf, false)
+    }
+
+    /// Create `TorrentSummary` from an already parsed torrent, with the info hash if requested.
+    ///
+    /// The piece data is dropped after hashing, so the summary holds the same fields as a decoded one.
+    pub fn from_torrent(mut torrent: Torrent, with_info_hash: bool) -> Result<Self> {
+        let info_hash = if with_info_hash {
+            Some(torrent.info_hash()?)
+        } else {
+            None
+        };
+        torrent.info_mut().pieces = ByteBuf::new();
+        torrent.piece_layers = None;
+        Ok(Self { torrent, info_hash })
+    }
+
+    #[must_use]
+    pub const fn name(&self) -> &Option<String> {
+        self.torrent.name()
+    }
+
+    /// Get the name of the content, see [`Torrent::content_name_or_source`]
+    #[must_use]
+    pub fn content_name_or_source<'a>(&'a self, source: &'a Path) -> Cow<'a, str> {
+        self.torrent.content_name_or_source(source)
+    }
+
+    #[must_use]
+    pub const fn creation_date(&self) -> &Option<i64> {
+        self.torrent.creation_date()
+    }
+
+    /// Get the number of files, see [`Torrent::num_files`]
+    #[must_use]
+    pub fn num_files(&self) -> usize {
+        self.torrent.num_files()
+    }
+
+    /// Get total size of all files in bytes, see [`Torrent::total_size_bytes`]
+    #[must_use]
+    pub fn total_size_bytes(&self) -> u64 {
+        self.torrent.total_size_bytes()
+    }
+
+    /// Get the SHA-1 info hash if the summary was read with [`Torrent::peek`], see [`Torrent::info_hash`]
+    #[must_use]
+    pub fn info_hash(&self) -> Option<&[u8]> {
+        self.info_hash.as_deref()
+    }
+
+    /// Decode the summary fields, and calculate the info hash if requested
+    fn decode(buf: &[u8], with_info_hash: bool) -> Result<Self> {
+        let (torrent, data): (MetadataTorrent, _) =
+            decode_lenient(buf).map_err(|error| Torrent::decode_error(buf, error))?;
+        let info_hash = if with_info_hash {
+            let info_range = raw_info_range(data).ok_or(Error::MissingField("info"))?;
+            Some(Sha1::digest(&data[info_range]).to_vec())
+        } else {
+            None
+        };
+        Ok(Self {
+            torrent: torrent.into(),
+            info_hash,
+        })
+    }
+}
+
+impl From<MetadataTorrent> for Torrent {
+    fn from(torrent: MetadataTorrent) -> Self {
+        let info = Info {
+            files: torrent.info.files,
+            length: torrent.info.length,
+            md5sum: torrent.info.md5sum,
+            name: torrent.info.name,
+            file_tree: torrent.info.file_tree,
+            meta_version: torrent.info.meta_version,
+            path: torrent.info.path,
+            piece_length: torrent.info.piece_length,
+            pieces: ByteBuf::new(),
+            private: torrent.info.private,
+            root_hash: torrent.info.root_hash,
+        };
+        Self {
+            announce: torrent.announce,
+            announce_list: torrent.announce_list,
+            comment: torrent.comment,
+            created_by: torrent.created_by,
+            creation_date: torrent.creation_date,
+            encoding: torrent.encoding,
+            info,
+            nodes: torrent.nodes,
+            x_nodes: torrent.x_nodes,
+            dht_nodes: torrent.dht_nodes,
+            httpseeds: torrent.httpseeds,
+            url_list: torrent.url_list,
+            piece_layers: None,
+            raw_info: None,
+        }
+    }
+}
+
+impl FileMatch {
+    /// Check if the file exists with the expected length.
+    ///
+    /// Zero-length files are always complete, like in [`Torrent::verify_files`].
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.length == 0 || self.size_on_disk == Some(self.length)
+    }
+
+    /// Get the number of bytes present on disk, capped at the file length
+    #[must_use]
+    pub fn present_bytes(&self) -> u64 {
+        self.size_on_disk.unwrap_or(0).min(self.length)
+    }
+}
+
+impl VerifyReport {
+    /// Check if all pieces were present and matched their hashes
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.failed_pieces.is_empty() && self.missing_pieces.is_empty()
+    }
+
+    /// Get the number of pieces that matched their hashes
+    #[must_use]
+    pub const fn passed_pieces(&self) -> usize {
+        self.total_pieces
+            .saturating_sub(self.failed_pieces.len())
+            .saturating_sub(self.missing_pieces.len())
+    }
+}
+
+impl File {
+    #[must_use]
+    pub const fn new(length: i64, path: Vec<String>) -> Self {
+        Self {
+            length,
+            path,
+            path_utf8: None,
+            md5sum: None,
+            attr: None,
+            symlink_path: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn length(&self) -> i64 {
+        self.length
+    }
+
+    /// Get the path components, preferring `path.utf-8` over the legacy `path` when present
+    #[must_use]
+    pub fn path(&self) -> &[String] {
+        self.path_utf8.as_deref().unwrap_or(&self.path)
+    }
+
+    /// Get the decoded BEP 47 attribute flags
+    #[must_use]
+    pub fn attributes(&self) -> FileAttributes {
+        self.attr.as_deref().map(FileAttributes::from_attr).unwrap_or_default()
+    }
+
+    /// Get the symlink target path components if the file is a symlink
+    #[must_use]
+    pub fn symlink_path(&self) -> Option<&[String]> {
+        self.symlink_path.as_deref()
+    }
+
+    /// Check if this is a BEP 47 padding file.
+    ///
+    /// Padding files are marked with the `p` attribute, older clients put them in a `.pad` directory.
+    #[must_use]
+    pub fn is_padding(&self) -> bool {
+        self.attributes().padding || self.path().first().is_some_and(|component| component == ".pad")
+    }
+}
+
+/// Recommend a piece length in bytes for content of the given total size.
+///
+/// Picks the smallest power of two that keeps the piece count at most 2000,
+/// which puts it between 1000 and 2000 pieces unless clamped to the 16 KiB to 16 MiB range.
+#[must_use]
+pub fn recommend_piece_length(total_size: u64) -> u32 {
+    let piece_length = total_size
+        .div_ceil(TARGET_MAX_PIECES)
+        .next_power_of_two()
+        .clamp(MIN_PIECE_LENGTH, MAX_PIECE_LENGTH);
+    u32::try_from(piece_length).unwrap_or(u32::MAX)
+}
+
+/// Convert bytes to hexadecimal string representation
+#[must_use]
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        result.push(HEX_CHARS[(byte >> 4) as usize] as char);
+        result.push(HEX_CHARS[(byte & 0xf) as usize] as char);
+    }
+    result
+}
+
+/// Recursively collect files from a v2 file tree dictionary.
+///
+/// File nodes are marked with an empty key that holds the file length and pieces root.
+fn collect_file_tree(tree: &HashMap<Vec<u8>, Value>, path: &mut Vec<String>, entries: &mut Vec<FileEntry>) {
+    let mut keys: Vec<&Vec<u8>> = tree.keys().collect();
+    keys.sort_unstable();
+    for key in keys {
+        let Some(Value::Dict(node)) = tree.get(key) else {
+            continue;
+        };
+        if key.is_empty() {
+            let length = match node.get(b"length".as_slice()) {
+                Some(Value::Int(length)) => *length,
+                _ => 0,
+            };
+            let pieces_root = match node.get(b"pieces root".as_slice()) {
+                Some(Value::Bytes(root)) => Some(root.clone()),
+                _ => None,
+            };
+            entries.push(FileEntry {
+                length,
+                path: path.clone(),
+                pieces_root,
+            });
+        } else {
+            path.push(String::from_utf8_lossy(key).into_owned());
+            collect_file_tree(node, path, entries);
+            path.pop();
+        }
+    }
+}
+
+/// Find the key of the first negative integer in a bencode value, searching dictionaries in key order.
+///
+/// Integers in lists are reported with the key of the list.
+fn first_negative_integer(value: &Value, key: &str) -> Option<String> {
+    match value {
+        Value::Int(number) if *number < 0 => Some(key.to_string()),
+        Value::List(list) => list.iter().find_map(|item| first_negative_integer(item, key)),
+        Value::Dict(dict) => {
+            let mut keys: Vec<&Vec<u8>> = dict.keys().collect();
+            keys.sort_unstable();
+            keys.into_iter()
+                .find_map(|key| first_negative_integer(&dict[key], &String::from_utf8_lossy(key)))
+        }
+        _ => None,
+    }
+}
+
+/// Decode bencoded data, retrying without a leading UTF-8 byte order mark and surrounding whitespace on failure.
+///
+/// Well-formed data is decoded in a single attempt. Returns the decoded value and the bytes it was decoded from,
+/// and the original error if the retry fails too.
+fn decode_lenient<T: DeserializeOwned>(buf: &[u8]) -> std::result::Result<(T, &[u8]), serde_bencode::Error> {
+    let error = match serde_bencode::from_bytes(buf) {
+        Ok(value) => return Ok((value, buf)),
+        Err(error) => error,
+    };
+    let trimmed = trim_torrent_data(buf);
+    if trimmed.len() == buf.len() {
+        return Err(error);
+    }
+    let value = serde_bencode::from_bytes(trimmed).map_err(|_| error)?;
+    log::warn!("Ignored a byte order mark or whitespace around the torrent data");
+    Ok((value, trimmed))
+}
+
+/// Strip a leading UTF-8 byte order mark and surrounding whitespace, see [`decode_lenient`]
+pub(crate) fn trim_torrent_data(buf: &[u8]) -> &[u8] {
+    buf.strip_prefix(UTF8_BOM).unwrap_or(buf).trim_ascii()
+}
+
+/// Find the byte range of the top-level `info` value in bencoded torrent data
+fn raw_info_range(buf: &[u8]) -> Option<std::ops::Range<usize>> {
+    if buf.first() != Some(&b'd') {
+        return None;
+    }
+    let mut position = 1;
+    while buf.get(position) != Some(&b'e') {
+        let key_start = position;
+        let value_start = bencode_value_end(buf, key_start)?;
+        let value_end = bencode_value_end(buf, value_start)?;
+        if &buf[key_start..value_start] == b"4:info" {
+            return Some(value_start..value_end);
+        }
+        position = value_end;
+    }
+    None
+}
+
+/// Get the position after the bencoded value starting at `start`
+fn bencode_value_end(buf: &[u8], start: usize) -> Option<usize> {
+    match *buf.get(start)? {
+        b'i' => Some(start + buf.get(start..)?.iter().position(|&byte| byte == b'e')? + 1),
+        b'l' | b'd' => {
+            let mut position = start + 1;
+            while *buf.get(position)? != b'e' {
+                position = bencode_value_end(buf, position)?;
+            }
+            Some(position + 1)
+        }
+        b'0'..=b'9' => {
+            let colon = start + buf.get(start..)?.iter().position(|&byte| byte == b':')?;
+            let length: usize = std::str::from_utf8(&buf[start..colon]).ok()?.parse().ok()?;
+            let end = colon + 1 + length;
+            (end <= buf.len()).then_some(end)
+        }
+        _ => None,
+    }
+}
+
+/// Check if a byte buffer is empty, used to skip serializing empty fields
+fn is_empty_bytes(bytes: &ByteBuf) -> bool {
+    bytes.is_empty()
+}
+
+/// Check if a directory entry is hidden, meaning its name starts with a dot.
+///
+/// The walk root itself is never considered hidden so relative paths like `.` work.
+fn is_hidden(entry: &walkdir::DirEntry) -> bool {
+    entry.depth() > 0 && entry.file_name().to_str().is_some_and(|name| name.starts_with('.'))
+}
+
+/// Read the data of a single piece from the opened files into the buffer.
+///
+/// Returns the number of bytes read, or `None` if any overlapping file is missing or truncated.
+fn read_piece(
+    files: &mut [(Option<StdFile>, u64)],
+    index: usize,
+    piece_length: usize,
+    total_size: u64,
+    buffer: &mut [u8],
+) -> Result<Option<usize>> {
+    let piece_start = index as u64 * piece_length as u64;
+    let piece_end = (piece_start + piece_length as u64).min(total_size);
+    if piece_start >= piece_end {
+        return Ok(None);
+    }
+    let mut filled = 0;
+    let mut file_start = 0;
+    for (handle, length) in files {
+        let file_end = file_start + *length;
+        if file_start >= piece_end {
+            break;
+        }
+        if file_end > piece_start {
+            let read_start = piece_start.max(file_start);
+            let read_length = (piece_end.min(file_end) - read_start) as usize;
+            let target = &mut buffer[filled..filled + read_length];
+            let Some(handle) = handle else {
+                return Ok(None);
+            };
+            handle.seek(SeekFrom::Start(read_start - file_start))?;
+            if read_full(handle, target)? < read_length {
+                return Ok(None);
+            }
+            filled += read_length;
+        }
+        file_start = file_end;
+    }
+    Ok(Some(filled))
+}
+
+/// Read from the reader until the buffer is full or the end of data is reached.
+///
+/// Returns the number of bytes read.
+fn read_full<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<usize> {
+    let mut total = 0;
+    while total < buffer.len() {
+        match reader.read(&mut buffer[total..]) {
+            Ok(0) => break,
+            Ok(count) => total += count,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(total)
+}
+
+/// Normalize a tracker URL for comparison by lowercasing the scheme and host.
+///
+/// The path and query are kept as is since they can be case-sensitive.
+#[must_use]
+pub fn normalize_tracker_url(url: &str) -> String {
+    let url = url.trim();
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return url.to_string();
+    };
+    let (host, path) = rest.split_at(rest.find(['/', '?', '#']).unwrap_or(rest.len()));
+    format!("{}://{}{path}", scheme.to_ascii_lowercase(), host.to_ascii_lowercase())
+}
+
+/// Check if a tracker URL looks like a public tracker, see [`Torrent::tracker_privacy_warnings`]
+fn is_public_tracker(url: &str) -> bool {
+    let normalized = normalize_tracker_url(url);
+    let Some((scheme, rest)) = normalized.split_once("://") else {
+        return false;
+    };
+    let authority = &rest[..rest.find(['/', '?', '#']).unwrap_or(rest.len())];
+    let host = authority.rsplit_once(':').map_or(authority, |(host, _port)| host);
+    scheme == "udp"
+        || PUBLIC_TRACKER_HOSTS
+            .iter()
+            .any(|public| host == *public || host.strip_suffix(public).is_some_and(|prefix| prefix.ends_with('.')))
+}
+
+/// Percent-encode bytes for use in a URL query string.
+///
+/// Unreserved characters (`A-Z`, `a-z`, `0-9`, `-`, `.`, `_`, `~`) are kept as is,
+/// all other bytes are encoded as `%XX`.
+#[must_use]
+pub fn percent_encode(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 3);
+    for &byte in bytes {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            result.push(byte as char);
+        } else {
+            result.push('%');
+            result.push(HEX_CHARS_UPPER[(byte >> 4) as usize] as char);
+            result.push(HEX_CHARS_UPPER[(byte & 0xf) as usize] as char);
+        }
+    }
+    result
+}
+
+/// Sanitize a name so it can be safely used as a filename on all common platforms.
+///
+/// Path separators, characters reserved on Windows, and control characters are replaced with `_`.
+/// Leading and trailing whitespace and trailing dots are removed.
+/// An empty result is replaced with `unnamed`.
+#[must_use]
+pub fn sanitize_filename(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|character| match character {
+            '/' | '\\' | '<' | '>' | ':' | '"' | '|' | '?' | '*' => '_',
+            character if character.is_control() => '_',
+            character => character,
+        })
+        .collect();
+
+    let sanitized = sanitized
+        .trim_start()
+        .trim_end_matches(|character: char| character == '.' || character.is_whitespace());
+    if sanitized.is_empty() {
+        "unnamed".to_string()
+    } else {
+        sanitized.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Path to the Ubuntu test torrent file
+    const UBUNTU_TORRENT: &str = "tests/ubuntu-24.04.3-desktop-amd64.iso.torrent";
+    /// Expected file size in bytes for the Ubuntu ISO
+    const UBUNTU_SIZE: i64 = 6_345_887_744;
+    /// Expected info hash for the Ubuntu torrent
+    const UBUNTU_INFO_HASH: &str = "d160b8d8ea35a5b4e52837468fc8f03d55cef1f7";
+
+    /// Path to the Pop!_OS test torrent file
+    const POPOS_TORRENT: &str = "tests/pop-os_24.04_amd64_nvidia_22.iso.torrent";
+    /// Expected file size in bytes for the Pop!_OS ISO
+    const POPOS_SIZE: i64 = 3_600_056_320;
+    /// Expected info hash for the Pop!_OS torrent
+    const POPOS_INFO_HASH: &str = "d4d16dbb800d9560f92b3821c84800f7047c186b";
+
+    #[test]
+    fn test_recommend_piece_length_minimum() {
+        assert_eq!(recommend_piece_length(0), 16 * 1024);
+        assert_eq!(recommend_piece_length(1), 16 * 1024);
+        assert_eq!(recommend_piece_length(10 * 1024 * 1024), 16 * 1024);
+    }
+
+    #[test]
+    fn test_recommend_piece_length_maximum() {
+        assert_eq!(recommend_piece_length(100 * 1024 * 1024 * 1024), 16 * 1024 * 1024);
+        assert_eq!(recommend_piece_length(u64::MAX), 16 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_recommend_piece_length_thresholds() {
+        // Exactly 2000 pieces stays at the smaller piece length
+        assert_eq!(recommend_piece_length(2000 * 64 * 1024), 64 * 1024);
+        assert_eq!(recommend_piece_length(2000 * 64 * 1024 + 1), 128 * 1024);
+        assert_eq!(recommend_piece_length(700 * 1024 * 1024), 512 * 1024);
+        assert_eq!(recommend_piece_length(UBUNTU_SIZE as u64), 4 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_recommend_piece_length_targets_piece_count() {
+        for total_size in [
+            50_000_000_u64,
+            123_456_789,
+            1_000_000_000,
+            4_700_000_000,
+            20_000_000_000,
+        ] {
+            let piece_length = u64::from(recommend_piece_length(total_size));
+            assert!(piece_length.is_power_of_two());
+            let num_pieces = total_size.div_ceil(piece_length);
+            assert!((1000..=2000).contains(&num_pieces), "{total_size}: {num_pieces} pieces");
+        }
+    }
+
+    #[test]
+    fn test_normalize_tracker_url() {
+        assert_eq!(
+            normalize_tracker_url("HTTPS://Tracker.Example.ORG/Announce?Key=AB"),
+            "https://tracker.example.org/Announce?Key=AB"
+        );
+        assert_eq!(
+            normalize_tracker_url(" udp://Tracker.Example.org:6969 "),
+            "udp://tracker.example.org:6969"
+        );
+        assert_eq!(normalize_tracker_url("not a url"), "not a url");
+    }
+
+    #[test]
+    fn test_trackers_deduplicates_normalized_urls() {
+        let torrent = Torrent {
+            announce: Some("https://tracker.example.org/announce".to_string()),
+            announce_list: Some(vec![
+                vec!["HTTPS://TRACKER.example.org/announce".to_string()],
+                vec!["https://tracker.example.org/ANNOUNCE".to_string()],
+                vec![
+                    "wss://tracker.example.org/socket".to_string(),
+                    "tracker.example.org".to_string(),
+                ],
+            ]),
+            ..Torrent::default()
+        };
+        assert_eq!(
+            torrent.trackers(),
+            vec![
+                "https://tracker.example.org/announce",
+                "https://tracker.example.org/ANNOUNCE",
+                "wss://tracker.example.org/socket",
+                "tracker.example.org",
+            ]
+        );
+        let schemes = torrent.tracker_schemes();
+        assert_eq!(schemes.get("https"), Some(&2));
+        assert_eq!(schemes.get("wss"), Some(&1));
+        assert_eq!(schemes.get("unknown"), Some(&1));
+    }
+
+    #[test]
+    fn test_to_hex_basic() {
+        assert_eq!(to_hex(b"foobar"), "666f6f626172");
+    }
+
+    #[test]
+    fn test_to_hex_binary() {
+        assert_eq!(to_hex(&[0xff, 0x00, 0xaa]), "ff00aa");
+    }
+
+    #[test]
+    fn test_to_hex_empty() {
+        assert_eq!(to_hex(&[]), "");
+    }
+
+    #[test]
+    fn test_to_hex_single_byte() {
+        assert_eq!(to_hex(&[0x0f]), "0f");
+        assert_eq!(to_hex(&[0xf0]), "f0");
+        assert_eq!(to_hex(&[0x00]), "00");
+        assert_eq!(to_hex(&[0xff]), "ff");
+    }
+
+    #[test]
+    fn test_percent_encode_unreserved() {
+        assert_eq!(percent_encode(b"AZaz09-._~"), "AZaz09-._~");
+    }
+
+    #[test]
+    fn test_percent_encode_reserved_and_binary() {
+        assert_eq!(percent_encode(b" /?&="), "%20%2F%3F%26%3D");
+        assert_eq!(percent_encode(&[0x00, 0xff, 0x0a]), "%00%FF%0A");
+    }
+
+    #[test]
+    fn test_percent_encode_info_hash() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        let info_hash = torrent.info_hash().expect("Failed to calculate info hash");
+        assert_eq!(
+            percent_encode(&info_hash),
+            "%D1%60%B8%D8%EA5%A5%B4%E5%287F%8F%C8%F0%3DU%CE%F1%F7"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_filename_keeps_valid_name() {
+        assert_eq!(
+            sanitize_filename("ubuntu-24.04.3-desktop-amd64.iso"),
+            "ubuntu-24.04.3-desktop-amd64.iso"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_filename_replaces_reserved_characters() {
+        assert_eq!(sanitize_filename("a/b\\c:d*e?f\"g<h>i|j"), "a_b_c_d_e_f_g_h_i_j");
+        assert_eq!(sanitize_filename("tab\tname"), "tab_name");
+    }
+
+    #[test]
+    fn test_sanitize_filename_trims_whitespace_and_dots() {
+        assert_eq!(sanitize_filename("  name. . "), "name");
+        assert_eq!(sanitize_filename(" ... "), "unnamed");
+        assert_eq!(sanitize_filename(""), "unnamed");
+    }
+
+    #[test]
+    fn test_torrent_safe_name() {
+        let mut torrent = Torrent::default();
+        assert!(torrent.safe_name().is_none());
+        torrent.info.name = Some("Some: Name?".to_string());
+        assert_eq!(torrent.safe_name().as_deref(), Some("Some_ Name_"));
+    }
+
+    /// Create an `Info` with piece hashes calculated from the given data
+    fn info_for_data(data: &[u8], piece_length: usize) -> Info {
+        let pieces: Vec<u8> = data
+            .chunks(piece_length)
+            .flat_map(|chunk| Sha1::digest(chunk).to_vec())
+            .collect();
+        Info {
+            length: Some(i64::try_from(data.len()).expect("Data length should fit in i64")),
+            piece_length: i64::try_from(piece_length).expect("Piece length should fit in i64"),
+            pieces: ByteBuf::from(pieces),
+            ..Info::default()
+        }
+    }
+
+    #[test]
+    fn test_verify_stream_valid_data() {
+        let data: Vec<u8> = (0..100u8).collect();
+        let info = info_for_data(&data, 16);
+        let report = info.verify_stream(data.as_slice()).expect("Failed to verify stream");
+        assert_eq!(report.total_pieces, 7);
+        assert_eq!(report.passed_pieces(), 7);
+        assert_eq!(report.bytes_read, 100);
+        assert!(report.is_complete());
+    }
+
+    #[test]
+    fn test_verify_stream_single_corrupt_byte() {
+        let data: Vec<u8> = (0..100u8).collect();
+        let info = info_for_data(&data, 16);
+        let mut corrupted = data;
+        corrupted[40] ^= 0xff;
+        let report = info
+            .verify_stream(corrupted.as_slice())
+            .expect("Failed to verify stream");
+        assert_eq!(report.failed_pieces, vec![2]);
+        assert!(report.missing_pieces.is_empty());
+        assert_eq!(report.passed_pieces(), 6);
+        assert!(!report.is_complete());
+    }
+
+    #[test]
+    fn test_verify_stream_truncated_data() {
+        let data: Vec<u8> = (0..100u8).collect();
+        let info = info_for_data(&data, 16);
+        let report = info.verify_stream(&data[..40]).expect("Failed to verify stream");
+        assert_eq!(report.failed_pieces, vec![2]);
+        assert_eq!(report.missing_pieces, vec![3, 4, 5, 6]);
+        assert_eq!(report.bytes_read, 40);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_verify_files_in_batches_matches_serial() {
+        let root = std::env::temp_dir().join("torrentinfo-verify-batches");
+        std::fs::create_dir_all(&root).expect("Failed to create test directory");
+        let data: Vec<u8> = (0..10_000u32).map(|index| (index * 31 % 251) as u8).collect();
+        let mut torrent = Torrent {
+            info: info_for_data(&data, 256),
+            ..Torrent::default()
+        };
+        torrent.info.name = Some("batched.bin".to_string());
+
+        // Corrupt one piece and truncate the last ones so every outcome is present in several batches
+        let mut on_disk = data[..9_000].to_vec();
+        on_disk[3_000] ^= 0xff;
+        std::fs::write(root.join("batched.bin"), &on_disk).expect("Failed to write test data");
+
+        let serial = torrent.verify_files(&root).expect("Failed to verify files");
+        assert_eq!(serial.failed_pieces, vec![11]);
+        assert_eq!(serial.missing_pieces, (35..40).collect::<Vec<_>>());
+        for batch_bytes in [0, 256, 1000, 4096, 1 << 20] {
+            let batched = torrent
+                .verify_files_in_batches(&root, batch_bytes)
+                .expect("Failed to verify files in batches");
+            assert_eq!(batched, serial, "batch size {batch_bytes}");
+        }
+    }
+
+    #[test]
+    fn test_verify_stream_invalid_piece_length() {
+        let info = Info::default();
+        let result = info.verify_stream(&[0u8; 4][..]);
+        assert!(matches!(result, Err(Error::InvalidPieceLength(0))));
+    }
+
+    #[test]
+    fn test_info_piece_hash() {
+        let data: Vec<u8> = (0..40u8).collect();
+        let info = info_for_data(&data, 16);
+        assert_eq!(info.piece_hash(0), Some(Sha1::digest(&data[..16]).as_slice()));
+        assert_eq!(info.piece_hash(2), Some(Sha1::digest(&data[32..]).as_slice()));
+        assert!(info.piece_hash(3).is_none());
+    }
+
+    #[test]
+    fn test_file_new() {
+        let file = File::new(1024, vec!["path".to_string(), "to".to_string(), "file.txt".to_string()]);
+        assert_eq!(file.length(), 1024);
+        assert_eq!(file.path(), &["path", "to", "file.txt"]);
+    }
+
+    #[test]
+    fn test_file_accessors() {
+        let file = File {
+            length: 2048,
+            path: vec!["test.txt".to_string()],
+            path_utf8: None,
+            md5sum: Some("abc123".to_string()),
+            attr: None,
+            symlink_path: None,
+        };
+        assert_eq!(file.length(), 2048);
+        assert_eq!(file.path(), &["test.txt"]);
+    }
+
+    #[test]
+    fn test_info_default() {
+        let info = Info::default();
+        assert!(info.name().is_none());
+        assert!(info.files.is_none());
+        assert!(info.length.is_none());
+        assert!(info.private().is_none());
+        assert_eq!(*info.piece_length(), 0);
+        assert!(info.pieces().is_empty());
+    }
+
+    #[test]
+    fn test_torrent_default() {
+        let torrent = Torrent::default();
+        assert!(torrent.name().is_none());
+        assert!(torrent.comment().is_none());
+        assert!(torrent.announce().is_none());
+        assert!(torrent.announce_list().is_none());
+        assert!(torrent.created_by().is_none());
+        assert!(torrent.creation_date().is_none());
+        assert!(torrent.encoding().is_none());
+        assert!(torrent.files().is_none());
+        assert_eq!(torrent.num_files(), 0);
+        assert_eq!(torrent.total_size(), 0);
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_from_file() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert!(torrent.name().is_some());
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_name() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert_eq!(torrent.name().as_deref(), Some("ubuntu-24.04.3-desktop-amd64.iso"));
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_comment() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert_eq!(torrent.comment().as_deref(), Some("Ubuntu CD releases.ubuntu.com"));
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_announce() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert_eq!(
+            torrent.announce().as_deref(),
+            Some("https://torrent.ubuntu.com/announce")
+        );
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_created_by() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert_eq!(torrent.created_by().as_deref(), Some("mktorrent 1.1"));
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_creation_date() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert!(torrent.creation_date().is_some());
+        let creation_date = torrent.creation_date().unwrap();
+        assert!(creation_date > 0);
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_num_files() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert_eq!(torrent.num_files(), 1);
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_total_size() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert_eq!(torrent.total_size(), UBUNTU_SIZE);
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_info_hash() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        let info_hash = torrent.info_hash().expect("Failed to calculate info hash");
+        assert_eq!(info_hash.len(), 20, "SHA-1 hash should be 20 bytes");
+
+        let hex_hash = to_hex(&info_hash);
+        assert_eq!(hex_hash.len(), 40, "Hex hash should be 40 characters");
+        assert_eq!(hex_hash, UBUNTU_INFO_HASH);
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_piece_length() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        let piece_length = *torrent.info().piece_length();
+        assert_eq!(piece_length, 262_144, "Ubuntu torrent piece length should be 256 KB");
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_pieces_not_empty() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert!(!torrent.info().pieces().is_empty(), "Pieces should not be empty");
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_num_pieces() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert_eq!(torrent.num_pieces(), 24_208);
+        assert_eq!(torrent.info().num_pieces(), 24_208);
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_last_piece_size() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        let expected = UBUNTU_SIZE - (24_208 - 1) * 262_144;
+        assert_eq!(torrent.last_piece_size(), expected);
+        assert_eq!(torrent.last_piece_size(), 167_936);
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_trackers() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert_eq!(
+            torrent.trackers(),
+            vec![
+                "https://torrent.ubuntu.com/announce",
+                "https://ipv6.torrent.ubuntu.com/announce"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_guess_category_multi_file() {
+        let torrent = Torrent {
+            info: Info {
+                files: Some(vec![
+                    File::new(700, vec!["Season 1".to_string(), "episode1.mkv".to_string()]),
+                    File::new(700, vec!["Season 1".to_string(), "episode2.MKV".to_string()]),
+                    File::new(10, vec!["release.nfo".to_string()]),
+                ]),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert_eq!(torrent.guess_category(), Category::Video);
+        assert_eq!(torrent.guess_category().to_string(), "video");
+    }
+
+    #[test]
+    fn test_guess_category_mixed_and_unknown() {
+        let torrent = Torrent {
+            info: Info {
+                files: Some(vec![
+                    File::new(100, vec!["album.flac".to_string()]),
+                    File::new(100, vec!["booklet.pdf".to_string()]),
+                ]),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert_eq!(torrent.guess_category(), Category::Mixed);
+        assert_eq!(Torrent::default().guess_category(), Category::Unknown);
+    }
+
+    #[test]
+    fn test_largest_and_smallest_file_skip_padding() {
+        let torrent = Torrent {
+            info: Info {
+                files: Some(vec![
+                    File::new(5000, vec!["video.mkv".to_string()]),
+                    File::new(0, vec!["marker".to_string()]),
+                    File {
+                        attr: Some("p".to_string()),
+                        ..File::new(10, vec![".pad".to_string(), "10".to_string()])
+                    },
+                    File::new(20, vec!["info.nfo".to_string()]),
+                    File::new(9000, vec!["extras".to_string(), "bonus.mkv".to_string()]),
+                ]),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        let largest = torrent.largest_file().expect("Largest file should exist");
+        assert_eq!(largest.path(), &["extras", "bonus.mkv"]);
+        let smallest = torrent.smallest_file().expect("Smallest file should exist");
+        assert_eq!(smallest.path(), &["info.nfo"]);
+    }
+
+    #[test]
+    fn test_largest_file_single_file_and_empty() {
+        let torrent = Torrent {
+            info: Info {
+                name: Some("image.iso".to_string()),
+                length: Some(4096),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        let largest = torrent.largest_file().expect("Single file should be returned");
+        assert_eq!(largest.path(), &["image.iso"]);
+        assert_eq!(largest.length(), 4096);
+        assert!(Torrent::default().largest_file().is_none());
+        assert!(Torrent::default().smallest_file().is_none());
+    }
+
+    #[test]
+    fn test_magnet_link_encodes_name_and_trackers() {
+        let torrent = Torrent {
+            announce: Some("udp://tracker.example.org:1337/announce".to_string()),
+            info: Info {
+                name: Some("My File [v2].iso".to_string()),
+                length: Some(1),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        let info_hash = to_hex(&torrent.info_hash().expect("Failed to calculate info hash"));
+        assert_eq!(
+            torrent.magnet_link().expect("Failed to build magnet link"),
+            format!(
+                "magnet:?xt=urn:btih:{info_hash}&dn=My%20File%20%5Bv2%5D.iso&tr=udp%3A%2F%2Ftracker.example.org%3A1337%2Fannounce"
+            )
+        );
+    }
+
+    #[test]
+    fn test_same_content_ignores_order_and_padding() {
+        let first = Torrent {
+            info: Info {
+                name: Some("album".to_string()),
+                files: Some(vec![
+                    File::new(100, vec!["01.flac".to_string()]),
+                    File::new(200, vec!["02.flac".to_string()]),
+                ]),
+                piece_length: 16384,
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        let second = Torrent {
+            announce: Some("https://tracker.example.org/announce".to_string()),
+            info: Info {
+                name: Some("album".to_string()),
+                files: Some(vec![
+                    File::new(200, vec!["02.flac".to_string()]),
+                    File {
+                        attr: Some("p".to_string()),
+                        ..File::new(16284, vec![".pad".to_string(), "16284".to_string()])
+                    },
+                    File::new(100, vec!["01.flac".to_string()]),
+                ]),
+                piece_length: 32768,
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert!(first.same_content(&second));
+        assert!(second.same_content(&first));
+
+        let renamed = Torrent {
+            info: Info {
+                name: Some("other album".to_string()),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert!(!first.same_content(&renamed));
+    }
+
+    #[test]
+    fn test_file_size_statistics() {
+        let torrent = Torrent {
+            info: Info {
+                files: Some(vec![
+                    File::new(100, vec!["a.bin".to_string()]),
+                    File::new(700, vec!["b.bin".to_string()]),
+                    File::new(300, vec!["c.bin".to_string()]),
+                    File {
+                        attr: Some("p".to_string()),
+                        ..File::new(99_999, vec![".pad".to_string(), "99999".to_string()])
+                    },
+                    File::new(1000, vec!["d.bin".to_string()]),
+                ]),
+                piece_length: 16384,
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert_eq!(torrent.piece_length_bytes(), 16384);
+        assert_eq!(torrent.average_file_size(), 525);
+        assert_eq!(torrent.median_file_size(), 500);
+    }
+
+    #[test]
+    fn test_file_size_statistics_odd_and_empty() {
+        let torrent = Torrent {
+            info: Info {
+                files: Some(vec![
+                    File::new(0, vec!["empty".to_string()]),
+                    File::new(10, vec!["small".to_string()]),
+                    File::new(5000, vec!["large".to_string()]),
+                ]),
+                piece_length: -1,
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert_eq!(torrent.piece_length_bytes(), 0);
+        assert_eq!(torrent.average_file_size(), 1670);
+        assert_eq!(torrent.median_file_size(), 10);
+
+        let empty = Torrent::default();
+        assert_eq!(empty.average_file_size(), 0);
+        assert_eq!(empty.median_file_size(), 0);
+    }
+
+    #[test]
+    fn test_creation_date_tolerant_parsing() {
+        let parse = |date: &[u8]| {
+            let mut buf = b"d13:creation date".to_vec();
+            buf.extend_from_slice(date);
+            buf.extend_from_slice(b"4:infod4:name1:a12:piece lengthi16384e6:pieces0:ee");
+            Torrent::from_buf(&buf)
+                .expect("Creation date should not fail the parse")
+                .creation_date
+        };
+        assert_eq!(parse(b"i1735689600e"), Some(1_735_689_600));
+        assert_eq!(parse(b"10:1735689600"), Some(1_735_689_600));
+        assert_eq!(parse(b"13:1735689600.75"), Some(1_735_689_600));
+        assert_eq!(parse(b"9:yesterday"), None);
+        assert_eq!(parse(b"li1ei2ee"), None);
+        assert_eq!(parse(b"d1:ai1ee"), None);
+    }
+
+    #[test]
+    fn test_content_name() {
+        let torrent = Torrent {
+            info: Info {
+                name: Some("release".to_string()),
+                files: Some(vec![File::new(1, vec!["disc1".to_string(), "track.flac".to_string()])]),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert_eq!(torrent.content_name(), "release");
+        assert_eq!(torrent.content_name_or_source(Path::new("source.torrent")), "release");
+    }
+
+    #[test]
+    fn test_content_name_missing_name() {
+        let multi_file = Torrent {
+            info: Info {
+                files: Some(vec![
+                    File::new(1, vec!["disc1".to_string(), "track.flac".to_string()]),
+                    File::new(1, vec!["disc2".to_string(), "track.flac".to_string()]),
+                ]),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert_eq!(multi_file.content_name(), "disc1");
+
+        let empty_name = Torrent {
+            info: Info {
+                name: Some(String::new()),
+                length: Some(1),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert_eq!(empty_name.content_name(), "unknown");
+        assert_eq!(
+            empty_name.content_name_or_source(Path::new("/downloads/backup.torrent")),
+            "backup"
+        );
+        assert_eq!(Torrent::default().content_name(), "unknown");
+    }
 
-    /// Path to the Pop!_OS test torrent file
-    const POPOS_TORRENT: &str = "tests/pop-os_24.04_amd64_nvidia_22.iso.torrent";
-    /// Expected file size in bytes for the Pop!_OS ISO
-    const POPOS_SIZE: i64 = 3_600_056_320;
-    /// Expected info hash for the Pop!_OS torrent
-    const POPOS_INFO_HASH: &str = "d4d16dbb800d9560f92b3821c84800f7047c186b";
+    #[test]
+    fn test_file_attributes() {
+        let attributes = FileAttributes::from_attr("xh?");
+        assert!(attributes.executable);
+        assert!(attributes.hidden);
+        assert!(!attributes.symlink);
+        assert!(!attributes.padding);
+        assert_eq!(attributes.to_string(), "xh");
+        assert!(FileAttributes::from_attr("").is_empty());
+        assert!(File::new(1, vec!["a".to_string()]).attributes().is_empty());
+    }
 
     #[test]
-    fn test_to_hex_basic() {
-        assert_eq!(to_hex(b"foobar"), "666f6f626172");
+    fn test_file_offset_includes_padding() {
+        let torrent = Torrent {
+            info: Info {
+                files: Some(vec![
+                    File::new(1000, vec!["first.bin".to_string()]),
+                    File {
+                        attr: Some("p".to_string()),
+                        ..File::new(15384, vec![".pad".to_string(), "15384".to_string()])
+                    },
+                    File::new(0, vec!["empty".to_string()]),
+                    File::new(500, vec!["second.bin".to_string()]),
+                ]),
+                piece_length: 16384,
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert_eq!(torrent.file_offset(0), Some(0));
+        assert_eq!(torrent.file_offset(1), Some(1000));
+        assert_eq!(torrent.file_offset(2), Some(16384));
+        assert_eq!(torrent.file_offset(3), Some(16384));
+        assert_eq!(torrent.file_offset(4), None);
+        assert_eq!(torrent.file_piece_range(3), Some(1..2));
     }
 
     #[test]
-    fn test_to_hex_binary() {
-        assert_eq!(to_hex(&[0xff, 0x00, 0xaa]), "ff00aa");
+    fn test_file_offset_single_file() {
+        let torrent = Torrent {
+            info: Info {
+                name: Some("single.bin".to_string()),
+                length: Some(1234),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert_eq!(torrent.file_offset(0), Some(0));
+        assert_eq!(torrent.file_offset(1), None);
     }
 
     #[test]
-    fn test_to_hex_empty() {
-        assert_eq!(to_hex(&[]), "");
+    fn test_info_hash_with_digest_types() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        let sha1_hash = torrent
+            .info_hash_with::<Sha1>()
+            .expect("Failed to calculate SHA-1 hash");
+        assert_eq!(sha1_hash, torrent.info_hash().expect("Failed to calculate info hash"));
+        assert_eq!(to_hex(&sha1_hash), "d160b8d8ea35a5b4e52837468fc8f03d55cef1f7");
+
+        let sha256_hash = torrent
+            .info_hash_with::<Sha256>()
+            .expect("Failed to calculate SHA-256 hash");
+        assert_eq!(sha256_hash.len(), 32);
+        assert_eq!(
+            sha256_hash,
+            torrent.info_hash_v2().expect("Failed to calculate v2 info hash")
+        );
+        let info = ser::to_bytes(torrent.info()).expect("Failed to encode info");
+        assert_eq!(sha256_hash, Sha256::digest(&info).to_vec());
     }
 
     #[test]
-    fn test_to_hex_single_byte() {
-        assert_eq!(to_hex(&[0x0f]), "0f");
-        assert_eq!(to_hex(&[0xf0]), "f0");
-        assert_eq!(to_hex(&[0x00]), "00");
-        assert_eq!(to_hex(&[0xff]), "ff");
+    fn test_single_tracker_with_nodes() {
+        let buf = b"d8:announce32:https://tracker.example/announce4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:e5:nodesll9:127.0.0.1i6881eeee";
+        let torrent = Torrent::from_buf(buf).expect("Failed to parse torrent with nodes");
+        assert_eq!(torrent.tracker_count(), 1);
+        assert!(!torrent.has_backup_trackers());
+        assert_eq!(torrent.num_nodes(), 1);
     }
 
     #[test]
-    fn test_file_new() {
-        let file = File::new(1024, vec!["path".to_string(), "to".to_string(), "file.txt".to_string()]);
-        assert_eq!(file.length(), 1024);
-        assert_eq!(file.path(), &["path", "to", "file.txt"]);
+    fn test_tracker_privacy_warnings_private_with_public_trackers() {
+        let buf = b"d8:announce36:https://tracker.example/abc/announce13:announce-listll36:https://tracker.example/abc/announceel37:udp://tracker.opentrackr.org:1337/annel37:http://tracker.openbittorrent.com/annee4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:7:privatei1eee";
+        let torrent = Torrent::from_buf(buf).expect("Failed to parse private torrent");
+        assert_eq!(
+            torrent.tracker_privacy_warnings(),
+            vec![
+                "private torrent lists 2 public trackers: udp://tracker.opentrackr.org:1337/ann, http://tracker.openbittorrent.com/ann"
+                    .to_string()
+            ]
+        );
+
+        let buf = b"d8:announce36:https://tracker.example/abc/announce4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:7:privatei1eee";
+        let torrent = Torrent::from_buf(buf).expect("Failed to parse private torrent");
+        assert!(torrent.tracker_privacy_warnings().is_empty());
     }
 
     #[test]
-    fn test_file_accessors() {
-        let file = File {
-            length: 2048,
-            path: vec!["test.txt".to_string()],
-            md5sum: Some("abc123".to_string()),
-        };
-        assert_eq!(file.length(), 2048);
-        assert_eq!(file.path(), &["test.txt"]);
+    fn test_tracker_privacy_warnings_public_without_peer_sources() {
+        let buf = b"d4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:ee";
+        let torrent = Torrent::from_buf(buf).expect("Failed to parse trackerless torrent");
+        let warnings = torrent.tracker_privacy_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("public torrent without trackers or DHT nodes"));
+
+        let buf = b"d4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:e5:nodesll9:127.0.0.1i6881eeee";
+        let torrent = Torrent::from_buf(buf).expect("Failed to parse torrent with nodes");
+        assert!(torrent.tracker_privacy_warnings().is_empty());
+
+        let buf = b"d4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:7:privatei1eee";
+        let torrent = Torrent::from_buf(buf).expect("Failed to parse private torrent");
+        assert_eq!(
+            torrent.tracker_privacy_warnings(),
+            vec!["private torrent without trackers, peers can not be found".to_string()]
+        );
     }
 
     #[test]
-    fn test_info_default() {
-        let info = Info::default();
-        assert!(info.name().is_none());
-        assert!(info.files.is_none());
-        assert!(info.length.is_none());
-        assert!(info.private().is_none());
-        assert_eq!(*info.piece_length(), 0);
-        assert!(info.pieces().is_empty());
+    fn test_parse_multiple_nodes() {
+        let buf = b"d4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:e5:nodesll9:127.0.0.1i6881eel9:127.0.0.2i6882eeee";
+        let torrent = Torrent::from_buf(buf).expect("Failed to parse torrent with nodes");
+        let nodes = torrent.nodes.as_ref().expect("Nodes should be present");
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1].0, "127.0.0.2");
+        assert_eq!(nodes[1].1, 6882);
     }
 
     #[test]
-    fn test_torrent_default() {
-        let torrent = Torrent::default();
-        assert!(torrent.name().is_none());
-        assert!(torrent.comment().is_none());
-        assert!(torrent.announce().is_none());
-        assert!(torrent.announce_list().is_none());
-        assert!(torrent.created_by().is_none());
-        assert!(torrent.creation_date().is_none());
-        assert!(torrent.encoding().is_none());
-        assert!(torrent.files().is_none());
-        assert_eq!(torrent.num_files(), 0);
-        assert_eq!(torrent.total_size(), 0);
+    fn test_tracker_tiers_inserts_missing_announce() {
+        let torrent = Torrent {
+            announce: Some("https://primary.example.org/announce".to_string()),
+            announce_list: Some(vec![
+                vec!["https://backup.example.org/announce".to_string()],
+                Vec::new(),
+                vec![
+                    "udp://first.example.org:1337/announce".to_string(),
+                    "udp://second.example.org:1337/announce".to_string(),
+                ],
+            ]),
+            ..Torrent::default()
+        };
+        assert_eq!(
+            torrent.tracker_tiers(),
+            vec![
+                vec!["https://primary.example.org/announce"],
+                vec!["https://backup.example.org/announce"],
+                vec![
+                    "udp://first.example.org:1337/announce",
+                    "udp://second.example.org:1337/announce"
+                ],
+            ]
+        );
     }
 
     #[test]
-    fn test_ubuntu_torrent_from_file() {
-        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
-        assert!(torrent.name().is_some());
+    fn test_tracker_tiers_without_trackers() {
+        assert!(Torrent::default().tracker_tiers().is_empty());
+
+        let torrent = Torrent {
+            announce: Some("https://primary.example.org/announce".to_string()),
+            ..Torrent::default()
+        };
+        assert_eq!(
+            torrent.tracker_tiers(),
+            vec![vec!["https://primary.example.org/announce"]]
+        );
     }
 
     #[test]
-    fn test_ubuntu_torrent_name() {
-        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
-        assert_eq!(torrent.name().as_deref(), Some("ubuntu-24.04.3-desktop-amd64.iso"));
+    fn test_add_tracker_to_empty_torrent() {
+        let mut torrent = Torrent::default();
+        assert!(torrent.add_tracker("udp://tracker.example.org:1337/announce", 0));
+        assert_eq!(
+            torrent.announce().as_deref(),
+            Some("udp://tracker.example.org:1337/announce")
+        );
+        assert_eq!(
+            torrent.announce_list(),
+            &Some(vec![vec!["udp://tracker.example.org:1337/announce".to_string()]])
+        );
     }
 
     #[test]
-    fn test_ubuntu_torrent_comment() {
-        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
-        assert_eq!(torrent.comment().as_deref(), Some("Ubuntu CD releases.ubuntu.com"));
+    fn test_primary_tracker_skips_empty_tiers() {
+        let torrent = Torrent {
+            announce_list: Some(vec![
+                Vec::new(),
+                vec!["https://second.example.org/announce".to_string()],
+            ]),
+            ..Torrent::default()
+        };
+        assert_eq!(torrent.primary_tracker(), Some("https://second.example.org/announce"));
+        assert!(Torrent::default().primary_tracker().is_none());
     }
 
     #[test]
-    fn test_ubuntu_torrent_announce() {
-        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+    fn test_add_tracker_keeps_primary_announce() {
+        let mut torrent = Torrent {
+            announce: Some("https://primary.example.org/announce".to_string()),
+            ..Torrent::default()
+        };
+        assert!(torrent.add_tracker("https://backup.example.org/announce", 1));
         assert_eq!(
             torrent.announce().as_deref(),
-            Some("https://torrent.ubuntu.com/announce")
+            Some("https://primary.example.org/announce")
+        );
+        assert_eq!(
+            torrent.announce_list(),
+            &Some(vec![
+                vec!["https://primary.example.org/announce".to_string()],
+                vec!["https://backup.example.org/announce".to_string()],
+            ])
         );
     }
 
     #[test]
-    fn test_ubuntu_torrent_created_by() {
-        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
-        assert_eq!(torrent.created_by().as_deref(), Some("mktorrent 1.1"));
+    fn test_add_tracker_existing_tier_and_duplicate() {
+        let mut torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert!(!torrent.add_tracker("https://ipv6.torrent.ubuntu.com/announce", 0));
+        assert!(!torrent.add_tracker("HTTPS://Torrent.Ubuntu.com/announce", 0));
+        assert!(torrent.add_tracker("https://mirror.example.org/announce", 0));
+        assert!(torrent.add_tracker("https://far.example.org/announce", 10));
+
+        let announce_list = torrent.announce_list().as_ref().expect("Announce list should exist");
+        assert_eq!(torrent.trackers().len(), 4);
+        assert_eq!(announce_list.len(), 3);
+        assert_eq!(
+            announce_list[0],
+            vec![
+                "https://torrent.ubuntu.com/announce".to_string(),
+                "https://mirror.example.org/announce".to_string()
+            ]
+        );
+        assert_eq!(announce_list[2], vec!["https://far.example.org/announce".to_string()]);
     }
 
     #[test]
-    fn test_ubuntu_torrent_creation_date() {
+    fn test_to_bytes_roundtrip() {
         let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
-        assert!(torrent.creation_date().is_some());
-        let creation_date = torrent.creation_date().unwrap();
-        assert!(creation_date > 0);
+        let bytes = torrent.to_bytes().expect("Failed to serialize torrent");
+        let parsed = Torrent::from_buf(&bytes).expect("Failed to parse serialized torrent");
+        assert_eq!(parsed.name(), torrent.name());
+        assert_eq!(parsed.trackers(), torrent.trackers());
+        assert_eq!(
+            to_hex(&parsed.info_hash().expect("Failed to calculate info hash")),
+            UBUNTU_INFO_HASH
+        );
     }
 
     #[test]
-    fn test_ubuntu_torrent_num_files() {
-        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
-        assert_eq!(torrent.num_files(), 1);
+    fn test_set_private_changes_info_hash() {
+        let mut torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        assert!(torrent.info().private().is_none());
+
+        torrent.set_private(true);
+        assert_eq!(*torrent.info().private(), Some(1));
+        let private_hash = to_hex(&torrent.info_hash().expect("Failed to calculate info hash"));
+        assert_ne!(private_hash, UBUNTU_INFO_HASH);
+
+        torrent.set_private(false);
+        assert!(torrent.info().private().is_none());
+        let public_hash = to_hex(&torrent.info_hash().expect("Failed to calculate info hash"));
+        assert_eq!(public_hash, UBUNTU_INFO_HASH);
     }
 
     #[test]
-    fn test_ubuntu_torrent_total_size() {
-        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+    fn test_set_name_changes_info_hash() {
+        let mut torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        torrent.set_name("renamed.iso");
+        assert_eq!(torrent.name().as_deref(), Some("renamed.iso"));
         assert_eq!(torrent.total_size(), UBUNTU_SIZE);
+        let renamed_hash = to_hex(&torrent.info_hash().expect("Failed to calculate info hash"));
+        assert_ne!(renamed_hash, UBUNTU_INFO_HASH);
+
+        torrent.set_name("ubuntu-24.04.3-desktop-amd64.iso");
+        let original_hash = to_hex(&torrent.info_hash().expect("Failed to calculate info hash"));
+        assert_eq!(original_hash, UBUNTU_INFO_HASH);
     }
 
     #[test]
-    fn test_ubuntu_torrent_info_hash() {
-        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
-        let info_hash = torrent.info_hash().expect("Failed to calculate info hash");
-        assert_eq!(info_hash.len(), 20, "SHA-1 hash should be 20 bytes");
+    fn test_clear_trackers() {
+        let mut torrent = Torrent::from_file(POPOS_TORRENT).expect("Failed to load Pop!_OS torrent");
+        torrent.clear_trackers();
+        assert!(torrent.announce().is_none());
+        assert!(torrent.announce_list().is_none());
+        assert!(torrent.trackers().is_empty());
+        assert_eq!(
+            to_hex(&torrent.info_hash().expect("Failed to calculate info hash")),
+            POPOS_INFO_HASH
+        );
+    }
 
-        let hex_hash = to_hex(&info_hash);
-        assert_eq!(hex_hash.len(), 40, "Hex hash should be 40 characters");
-        assert_eq!(hex_hash, UBUNTU_INFO_HASH);
+    #[test]
+    fn test_replace_tracker() {
+        let mut torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        let replaced = torrent.replace_tracker(
+            "https://torrent.ubuntu.com/announce",
+            "https://tracker.example.org/announce",
+        );
+        assert_eq!(replaced, 2, "Announce URL and announce-list entry should be replaced");
+        assert_eq!(
+            torrent.announce().as_deref(),
+            Some("https://tracker.example.org/announce")
+        );
+        assert!(!torrent.trackers().contains(&"https://torrent.ubuntu.com/announce"));
+        assert_eq!(
+            torrent.replace_tracker("https://torrent.ubuntu.com", "https://example.org"),
+            0
+        );
+        assert_eq!(
+            to_hex(&torrent.info_hash().expect("Failed to calculate info hash")),
+            UBUNTU_INFO_HASH
+        );
     }
 
     #[test]
-    fn test_ubuntu_torrent_piece_length() {
-        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
-        let piece_length = *torrent.info().piece_length();
-        assert_eq!(piece_length, 262_144, "Ubuntu torrent piece length should be 256 KB");
+    fn test_replace_tracker_substring() {
+        let mut torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        let tracker_count = torrent.tracker_count();
+        assert_eq!(torrent.replace_tracker_substring("", "ignored"), 0);
+        assert!(torrent.replace_tracker_substring("ubuntu.com", "example.org") > 0);
+        assert!(torrent.trackers().iter().all(|tracker| !tracker.contains("ubuntu.com")));
+        assert_eq!(torrent.tracker_count(), tracker_count);
+        assert_eq!(
+            to_hex(&torrent.info_hash().expect("Failed to calculate info hash")),
+            UBUNTU_INFO_HASH
+        );
     }
 
     #[test]
-    fn test_ubuntu_torrent_pieces_not_empty() {
+    fn test_merge_trackers_from() {
+        let mut ubuntu = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        let popos = Torrent::from_file(POPOS_TORRENT).expect("Failed to load Pop!_OS torrent");
+        let info_hash = ubuntu.info_hash().expect("Failed to calculate info hash");
+
+        assert_eq!(ubuntu.merge_trackers_from(&popos), 10);
+        assert_eq!(ubuntu.trackers().len(), 12);
+        assert_eq!(
+            ubuntu.merge_trackers_from(&popos),
+            0,
+            "Merging again should not add duplicates"
+        );
+
+        let announce_list = ubuntu.announce_list().as_ref().expect("Announce list should exist");
+        assert_eq!(announce_list[0].len(), 3);
+        assert_eq!(announce_list[0][2], "http://fosstorrents.com:6969/announce");
+        assert_eq!(ubuntu.info_hash().expect("Failed to calculate info hash"), info_hash);
+    }
+
+    #[test]
+    fn test_ubuntu_torrent_announce_request_url() {
         let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
-        assert!(!torrent.info().pieces().is_empty(), "Pieces should not be empty");
+        let url = torrent
+            .announce_request_url("https://torrent.ubuntu.com/announce", b"-TI0400-abcdefghijkl", 6881)
+            .expect("Failed to build announce URL");
+        assert_eq!(
+            url,
+            "https://torrent.ubuntu.com/announce?info_hash=%D1%60%B8%D8%EA5%A5%B4%E5%287F%8F%C8%F0%3DU%CE%F1%F7\
+             &peer_id=-TI0400-abcdefghijkl&port=6881&uploaded=0&downloaded=0&left=6345887744&compact=1"
+        );
+    }
+
+    #[test]
+    fn test_announce_request_url_with_existing_query() {
+        let torrent = Torrent::default();
+        let url = torrent
+            .announce_request_url("http://tracker.example.org/announce?passkey=abc", b"peer", 1234)
+            .expect("Failed to build announce URL");
+        assert!(url.starts_with("http://tracker.example.org/announce?passkey=abc&info_hash="));
     }
 
     #[test]
@@ -530,6 +3498,67 @@ mod tests {
         assert!(!torrent.info().pieces().is_empty(), "Pieces should not be empty");
     }
 
+    #[test]
+    fn test_popos_torrent_num_pieces() {
+        let torrent = Torrent::from_file(POPOS_TORRENT).expect("Failed to load Pop!_OS torrent");
+        assert_eq!(torrent.num_pieces(), 3434);
+    }
+
+    #[test]
+    fn test_popos_torrent_last_piece_size() {
+        let torrent = Torrent::from_file(POPOS_TORRENT).expect("Failed to load Pop!_OS torrent");
+        let expected = POPOS_SIZE - (3434 - 1) * 1_048_576;
+        assert_eq!(torrent.last_piece_size(), expected);
+        assert_eq!(torrent.last_piece_size(), 294_912);
+    }
+
+    #[test]
+    fn test_torrent_last_piece_size_without_pieces() {
+        let torrent = Torrent::default();
+        assert_eq!(torrent.num_pieces(), 0);
+        assert_eq!(torrent.last_piece_size(), 0);
+    }
+
+    #[test]
+    fn test_fixture_piece_count_health() {
+        let ubuntu = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        let popos = Torrent::from_file(POPOS_TORRENT).expect("Failed to load Pop!_OS torrent");
+        assert_eq!(ubuntu.piece_count_health(), PieceHealth::Ok);
+        assert_eq!(popos.piece_count_health(), PieceHealth::Ok);
+    }
+
+    #[test]
+    fn test_piece_count_health_too_few() {
+        let mut torrent = Torrent::default();
+        torrent.info.pieces = ByteBuf::from(vec![0; PIECE_HASH_LENGTH * (MIN_RECOMMENDED_PIECES - 1)]);
+        assert_eq!(torrent.piece_count_health(), PieceHealth::TooFew);
+    }
+
+    #[test]
+    fn test_piece_count_health_too_many() {
+        let mut torrent = Torrent::default();
+        torrent.info.pieces = ByteBuf::from(vec![0; PIECE_HASH_LENGTH * (MAX_RECOMMENDED_PIECES + 1)]);
+        assert_eq!(torrent.piece_count_health(), PieceHealth::TooMany);
+    }
+
+    #[test]
+    fn test_piece_count_health_boundaries() {
+        let mut torrent = Torrent::default();
+        torrent.info.pieces = ByteBuf::from(vec![0; PIECE_HASH_LENGTH * MIN_RECOMMENDED_PIECES]);
+        assert_eq!(torrent.piece_count_health(), PieceHealth::Ok);
+        torrent.info.pieces = ByteBuf::from(vec![0; PIECE_HASH_LENGTH * MAX_RECOMMENDED_PIECES]);
+        assert_eq!(torrent.piece_count_health(), PieceHealth::Ok);
+    }
+
+    #[test]
+    fn test_popos_torrent_trackers() {
+        let torrent = Torrent::from_file(POPOS_TORRENT).expect("Failed to load Pop!_OS torrent");
+        let trackers = torrent.trackers();
+        assert_eq!(trackers.len(), 10);
+        assert_eq!(trackers[0], "udp://fosstorrents.com:6969/announce");
+        assert_eq!(trackers[1], "http://fosstorrents.com:6969/announce");
+    }
+
     #[test]
     fn test_popos_torrent_from_buf() {
         let bytes = Torrent::read_bytes(Path::new(POPOS_TORRENT)).expect("Failed to read torrent bytes");
@@ -561,6 +3590,35 @@ mod tests {
         assert_eq!(torrent.total_size(), 6000);
     }
 
+    #[test]
+    fn test_torrent_total_size_bytes() {
+        let torrent = Torrent {
+            info: Info {
+                length: Some(5000),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert_eq!(torrent.total_size_bytes(), 5000);
+        assert_eq!(Torrent::default().total_size_bytes(), 0);
+    }
+
+    #[test]
+    fn test_torrent_total_size_bytes_clamps_negative() {
+        let torrent = Torrent {
+            info: Info {
+                files: Some(vec![
+                    File::new(100, vec!["a".to_string()]),
+                    File::new(-500, vec!["b".to_string()]),
+                ]),
+                ..Info::default()
+            },
+            ..Torrent::default()
+        };
+        assert_eq!(torrent.total_size(), -400);
+        assert_eq!(torrent.total_size_bytes(), 0);
+    }
+
     #[test]
     fn test_torrent_total_size_single_file() {
         let mut torrent = Torrent::default();
@@ -578,6 +3636,85 @@ mod tests {
         assert_eq!(torrent.num_files(), 2);
     }
 
+    #[test]
+    fn test_info_file_count_multiple_files() {
+        let info = Info {
+            files: Some(vec![
+                File::new(100, vec!["a.txt".to_string()]),
+                File::new(0, vec!["empty.txt".to_string()]),
+                File::new(200, vec!["b.txt".to_string()]),
+            ]),
+            ..Info::default()
+        };
+        assert_eq!(info.file_count(), 3);
+    }
+
+    #[test]
+    fn test_info_file_count_single_file() {
+        let info = Info {
+            length: Some(5000),
+            ..Info::default()
+        };
+        assert_eq!(info.file_count(), 1);
+
+        let empty_file = Info {
+            length: Some(0),
+            ..Info::default()
+        };
+        assert_eq!(empty_file.file_count(), 1);
+    }
+
+    #[test]
+    fn test_pieces_for_range() {
+        // 450 bytes in pieces of 100 bytes, the last piece holds 50 bytes
+        let info = Info {
+            piece_length: 100,
+            pieces: ByteBuf::from(vec![0; PIECE_HASH_LENGTH * 5]),
+            length: Some(450),
+            ..Info::default()
+        };
+        assert_eq!(info.pieces_for_range(0, 100), 0..1);
+        assert_eq!(info.pieces_for_range(0, 101), 0..2);
+        assert_eq!(info.pieces_for_range(99, 2), 0..2);
+        assert_eq!(info.pieces_for_range(150, 200), 1..4);
+        assert_eq!(info.pieces_for_range(420, 30), 4..5);
+        assert_eq!(info.pieces_for_range(0, 450), 0..5);
+    }
+
+    #[test]
+    fn test_pieces_for_range_edge_cases() {
+        let info = Info {
+            piece_length: 100,
+            pieces: ByteBuf::from(vec![0; PIECE_HASH_LENGTH * 5]),
+            length: Some(450),
+            ..Info::default()
+        };
+        assert_eq!(info.pieces_for_range(250, 0), 2..2);
+        assert_eq!(info.pieces_for_range(400, 1000), 4..5);
+        assert_eq!(info.pieces_for_range(900, 10), 5..5);
+        assert_eq!(info.pieces_for_range(u64::MAX, u64::MAX), 5..5);
+        assert_eq!(Info::default().pieces_for_range(0, 100), 0..0);
+    }
+
+    #[test]
+    fn test_pieces_for_range_matches_file_piece_range() {
+        let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
+        let range = torrent.info.pieces_for_range(0, torrent.total_size_bytes());
+        assert_eq!(range.len(), torrent.num_pieces());
+        assert_eq!(Some(range), torrent.file_piece_range(0));
+    }
+
+    #[test]
+    fn test_info_file_count_no_files() {
+        assert_eq!(Info::default().file_count(), 0);
+
+        let empty_list = Info {
+            files: Some(Vec::new()),
+            ..Info::default()
+        };
+        assert_eq!(empty_list.file_count(), 0);
+    }
+
     #[test]
     fn test_ubuntu_torrent_announce_list() {
         let torrent = Torrent::from_file(UBUNTU_TORRENT).expect("Failed to load Ubuntu torrent");
@@ -595,4 +3732,123 @@ mod tests {
             assert!(!announce_list.is_empty(), "Announce list should not be empty");
         }
     }
+
+    #[test]
+    fn test_info_hash_uses_info_bytes_as_stored() {
+        // Keys out of order, so re-encoding the info dictionary changes its bytes
+        let info = b"d4:name1:a6:lengthi5e12:piece lengthi16384e6:pieces0:e";
+        let mut buf = b"d4:info".to_vec();
+        buf.extend_from_slice(info);
+        buf.extend_from_slice(b"13:creation datei1700000000ee");
+
+        let info_hash = Sha1::digest(info).to_vec();
+        let torrent = Torrent::from_buf(&buf).unwrap();
+        assert_eq!(torrent.info_hash().unwrap(), info_hash);
+        assert_ne!(Sha1::digest(ser::to_bytes(&torrent.info).unwrap()).to_vec(), info_hash);
+        assert_eq!(Torrent::info_hash_from_bytes(&buf).unwrap(), info_hash);
+
+        let summary = Torrent::peek_buf(&buf).unwrap();
+        assert_eq!(summary.info_hash(), Some(info_hash.as_slice()));
+        assert_eq!(*summary.creation_date(), Some(1_700_000_000));
+        assert_eq!(summary.name().as_deref(), Some("a"));
+        assert_eq!(summary.total_size_bytes(), 5);
+    }
+
+    #[test]
+    fn test_raw_info_range() {
+        let buf = b"d8:announce3:url4:infod4:name1:ae3:agei1ee";
+        let range = raw_info_range(buf).unwrap();
+        assert_eq!(&buf[range], b"d4:name1:ae");
+        assert!(raw_info_range(b"d8:announce3:urle").is_none());
+        assert!(raw_info_range(b"d4:infod4:name5:ae").is_none());
+    }
+
+    /// Build a torrent with the given name and files for the suspicious torrent checks
+    fn suspicious_test_torrent(name: &str, files: &[(&str, i64)]) -> Torrent {
+        let info = match files {
+            [(file, length)] if *file == name => Info {
+                name: Some(name.to_string()),
+                length: Some(*length),
+                ..Info::default()
+            },
+            _ => Info {
+                name: Some(name.to_string()),
+                files: Some(
+                    files
+                        .iter()
+                        .map(|(path, length)| File::new(*length, vec![(*path).to_string()]))
+                        .collect(),
+                ),
+                ..Info::default()
+            },
+        };
+        Torrent {
+            announce: Some("https://tracker.example.org/announce".to_string()),
+            info,
+            ..Torrent::default()
+        }
+    }
+
+    #[test]
+    fn test_suspicious_tiny_content() {
+        let torrent = suspicious_test_torrent("Movie.2024.1080p.BluRay.x264", &[("Movie.2024.1080p.txt", 4_000)]);
+        assert_eq!(torrent.looks_suspicious(), vec![SuspiciousTrait::TinyContent]);
+
+        let large = suspicious_test_torrent(
+            "Movie.2024.1080p.BluRay.x264",
+            &[("Movie.2024.1080p.mkv", 4_000_000_000)],
+        );
+        assert!(large.looks_suspicious().is_empty());
+    }
+
+    #[test]
+    fn test_suspicious_executable_media() {
+        let torrent = suspicious_test_torrent("Movie.2024.2160p.WEB-DL", &[("Setup.exe", 50_000_000)]);
+        assert_eq!(torrent.looks_suspicious(), vec![SuspiciousTrait::ExecutableMedia]);
+
+        let software = suspicious_test_torrent("Installer", &[("Setup.exe", 50_000_000)]);
+        assert!(software.looks_suspicious().is_empty());
+    }
+
+    #[test]
+    fn test_suspicious_double_extension() {
+        let torrent = suspicious_test_torrent("Album", &[("01 Track.mp3", 5_000_000), ("02 Track.mp3.exe", 5_000_000)]);
+        assert_eq!(torrent.looks_suspicious(), vec![SuspiciousTrait::DoubleExtension]);
+    }
+
+    #[test]
+    fn test_suspicious_spam_marker() {
+        let mut torrent = suspicious_test_torrent("Linux ISO", &[("linux.iso", 2_000_000_000)]);
+        assert!(torrent.looks_suspicious().is_empty());
+
+        torrent.comment = Some("Get the PASSWORD at example.org".to_string());
+        assert_eq!(torrent.looks_suspicious(), vec![SuspiciousTrait::SpamMarker]);
+
+        torrent.comment = None;
+        torrent.announce = Some("https://bit.ly/tracker".to_string());
+        assert_eq!(torrent.looks_suspicious(), vec![SuspiciousTrait::SpamMarker]);
+    }
+
+    #[test]
+    fn test_suspicious_single_file_with_all_traits() {
+        let mut torrent = suspicious_test_torrent("Movie.1080p.mkv.exe", &[("Movie.1080p.mkv.exe", 200_000)]);
+        torrent.comment = Some("Free download, click here".to_string());
+        assert_eq!(
+            torrent.looks_suspicious(),
+            vec![
+                SuspiciousTrait::TinyContent,
+                SuspiciousTrait::ExecutableMedia,
+                SuspiciousTrait::DoubleExtension,
+                SuspiciousTrait::SpamMarker,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_example_torrents_are_not_suspicious() {
+        for path in [UBUNTU_TORRENT, POPOS_TORRENT] {
+            let torrent = Torrent::from_file(path).expect("Failed to parse torrent");
+            assert!(torrent.looks_suspicious().is_empty(), "{path}");
+        }
+    }
 }
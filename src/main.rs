@@ -18,30 +18,51 @@
  */
 
 mod cli;
+mod edit;
+mod html;
+mod layout;
 mod utils;
+#[cfg(feature = "watch")]
+mod watch;
 
 use std::path::PathBuf;
+use std::time::Instant;
 
 use anyhow::Result;
-use clap::{CommandFactory, Parser};
-use clap_complete::Shell;
+use clap::{ArgGroup, CommandFactory, Parser, Subcommand};
+
+/// Environment variable used to configure logging, using the `env_logger` filter syntax
+const LOG_ENV: &str = "TORRENTINFO_LOG";
 
 #[derive(Parser)]
 #[command(author, about, version)]
+#[command(group(ArgGroup::new("mode").multiple(false)))]
 #[allow(clippy::struct_excessive_bools)]
 struct Args {
-    /// Optional input directory or file
-    #[arg(value_hint = clap::ValueHint::AnyPath)]
-    path: Option<PathBuf>,
+    /// Optional input directories or files
+    #[arg(value_name = "PATH", value_hint = clap::ValueHint::AnyPath)]
+    paths: Vec<PathBuf>,
 
     /// Show detailed information about the torrent
     #[arg(short, long)]
     details: bool,
 
     /// Print everything about the torrent
-    #[arg(short, long)]
+    #[arg(short, long, group = "mode")]
     everything: bool,
 
+    /// Show byte strings up to this length in full with `--everything`, longer values are shown as a byte count
+    #[arg(long, value_name = "BYTES", default_value_t = utils::DEFAULT_INLINE_BYTES)]
+    inline_bytes: usize,
+
+    /// Show the piece count as a bar in detail mode
+    #[arg(long, requires = "details")]
+    pieces_bar: bool,
+
+    /// Width of the pieces bar, defaults to the terminal width
+    #[arg(long, value_name = "COLUMNS", requires = "pieces_bar")]
+    bar_width: Option<usize>,
+
     /// Show files within the torrent
     #[arg(
         short,
@@ -50,33 +71,366 @@ struct Args {
     )]
     files: bool,
 
+    /// Show long file paths in full instead of shortening them to fit the terminal width
+    #[arg(long)]
+    no_truncate: bool,
+
+    /// Prefix file paths of multi-file torrents with the torrent name, matching the layout a client creates on disk
+    #[arg(long)]
+    full_paths: bool,
+
+    /// Show the running total size next to each file in the file listing
+    #[arg(long)]
+    cumulative: bool,
+
     /// Disable colour output
     #[arg(short, long = "nocolour")]
     no_colour: bool,
 
+    /// Show badges after each torrent header: [P] private, [DHT] trackerless with DHT nodes, [WS] web seeds
+    #[arg(long)]
+    badges: bool,
+
     /// Recursive directory iteration
     #[arg(short, long)]
     recursive: bool,
 
+    /// Maximum directory depth to search, 1 only searches the given directory, implies recursive above 1
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    max_depth: Option<usize>,
+
     /// Sort files by size
     #[arg(short, long)]
     sort: bool,
 
+    /// Sort torrents by the given key
+    #[arg(long, value_name = "KEY")]
+    sort_by: Option<cli::SortKey>,
+
+    /// Reverse the sort order
+    #[arg(long)]
+    reverse: bool,
+
+    /// Sort directory contents and file listings with numbers in names ordered by value
+    #[arg(long)]
+    natural_sort: bool,
+
+    /// Print torrents as they are found instead of sorting the paths first, for directories with huge numbers of torrents.
+    /// Output starts immediately and memory use stays flat, but the order follows the directory walk.
+    /// Reports over all torrents, such as `--stats`, still collect the paths but skip sorting them.
+    #[arg(long, conflicts_with_all = ["sort", "sort_by", "natural_sort"])]
+    no_sort_paths: bool,
+
+    /// Include hidden files and directories when scanning directories
+    #[arg(long)]
+    include_hidden: bool,
+
+    /// Skip torrent files whose path matches the glob pattern when scanning directories
+    #[arg(short = 'x', long, value_name = "GLOB")]
+    exclude: Vec<String>,
+
+    /// Display torrent file paths relative to this directory instead of the scan root
+    #[arg(long, value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    relative_to: Option<PathBuf>,
+
+    /// Show torrent file paths with the native path separator instead of `/`
+    #[arg(long)]
+    native_separators: bool,
+
+    /// Show invalid Unicode in file names as replacement characters instead of removing it
+    #[arg(long)]
+    raw_names: bool,
+
+    /// Read a base64-encoded torrent from the argument instead of a file, use "-" to read it from stdin
+    #[arg(long, value_name = "STRING", conflicts_with_all = ["paths", "mode", "sort", "sort_by"])]
+    base64: Option<String>,
+
+    /// Do not calculate and print the info hash, which is slow for thousands of torrents
+    #[arg(long)]
+    no_hash: bool,
+
+    /// Do not print the total size
+    #[arg(long)]
+    no_size: bool,
+
+    /// Reject torrents that do not follow the specification, such as a piece length that is not a power of two
+    #[arg(long)]
+    strict: bool,
+
+    /// Report files that appear in more than one torrent
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    dedupe_files: bool,
+
+    /// Report torrents that are duplicates by info hash, or by content with `--find-dupes=content`
+    #[arg(
+        long,
+        group = "mode",
+        value_name = "KEY",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "hash",
+        conflicts_with_all = ["sort", "sort_by"]
+    )]
+    find_dupes: Option<cli::DuplicateKey>,
+
+    /// Suggest renaming torrent files to match the torrent name
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    rename_suggest: bool,
+
+    /// Rename torrent files to match the torrent name
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    rename: bool,
+
+    /// Print a single summary line per torrent
+    #[arg(long, group = "mode")]
+    summary_line: bool,
+
+    /// Print torrent information as JSON
+    #[arg(long, group = "mode")]
+    json: bool,
+
+    /// Print one JSON object per line, the default when stdout is not a terminal
+    #[arg(long, requires = "json", conflicts_with = "json_pretty")]
+    json_compact: bool,
+
+    /// Pretty-print JSON over multiple lines, the default when stdout is a terminal
+    #[arg(long, requires = "json")]
+    json_pretty: bool,
+
+    /// Print torrent information as a TOML document
+    #[arg(long, group = "mode")]
+    toml: bool,
+
+    /// Write a self-contained HTML page with the torrent information to the given file, or `-` for stdout
+    #[arg(long, group = "mode", value_name = "FILE")]
+    html: Option<PathBuf>,
+
+    /// Print a table with the given columns
+    #[arg(long, group = "mode", value_delimiter = ',', value_name = "COLUMNS")]
+    columns: Option<Vec<cli::Column>>,
+
+    /// Print only the info hashes for the given versions, for example `v1` or `v1,v2`
+    #[arg(long, group = "mode", value_delimiter = ',', value_name = "VERSIONS")]
+    hash_only: Option<Vec<cli::HashAlgorithm>>,
+
+    /// Print aggregate statistics over all torrents
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    stats: bool,
+
+    /// Print how many torrents use each tracker, most used first
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    tracker_report: bool,
+
+    /// Print how many torrents were made with each creation tool, most used first
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    creator_report: bool,
+
+    /// Print the announce request and scrape URLs for the primary HTTP tracker
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    announce_url: bool,
+
+    /// Print a magnet link for each torrent
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    magnet: bool,
+
+    /// Write a .magnet file next to each torrent file
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    magnet_file: bool,
+
+    /// Print the bencoded info dictionary as a hexdump
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    info_hexdump: bool,
+
+    /// Write the raw piece hashes to a file, or stdout with '-'
+    #[arg(long, group = "mode", value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    dump_pieces: Option<PathBuf>,
+
+    /// Write the piece hashes as hex to a file, or stdout with '-', one hash per line
+    #[arg(long, group = "mode", value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    dump_pieces_hex: Option<PathBuf>,
+
+    /// Compare the torrent size with the size of the matching files in a download directory
+    #[arg(long, group = "mode", value_name = "DIR", value_hint = clap::ValueHint::DirPath, conflicts_with_all = ["sort", "sort_by"])]
+    disk_usage: Option<PathBuf>,
+
+    /// Print the recommended piece length for the content of each torrent
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    recommend_piece_length: bool,
+
+    /// Grade each torrent file as OK, suspicious or corrupt, to tell damaged files from invalid ones
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    checksum_self: bool,
+
+    /// Print the files and byte ranges covered by each piece, to debug hash failures at file boundaries
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    piece_map: bool,
+
+    /// Maximum number of pieces printed per torrent with --piece-map
+    #[arg(long, value_name = "N", default_value_t = cli::DEFAULT_MAX_PIECES)]
+    max_pieces: usize,
+
+    /// Watch the input directories and print new torrent files as they appear
+    #[arg(long, visible_alias = "follow", group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    watch: bool,
+
+    /// List torrents without a comment
+    #[arg(long, conflicts_with_all = ["mode", "sort", "sort_by"])]
+    missing_comment: bool,
+
+    /// List torrents without a creator
+    #[arg(long, conflicts_with_all = ["mode", "sort", "sort_by"])]
+    missing_creator: bool,
+
+    /// List torrents without a creation date
+    #[arg(long, conflicts_with_all = ["mode", "sort", "sort_by"])]
+    missing_date: bool,
+
+    /// List torrents that look fake or spammy, such as executables in media torrents
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    flag_suspicious: bool,
+
+    /// Choose one torrent from a numbered list and print its full details
+    #[arg(long, group = "mode", conflicts_with_all = ["sort", "sort_by"])]
+    pick: bool,
+
+    /// Only include torrents without any trackers
+    #[arg(long, conflicts_with = "only_tracked")]
+    only_trackerless: bool,
+
+    /// Only include torrents with at least one tracker
+    #[arg(long)]
+    only_tracked: bool,
+
+    /// Only include torrents containing a file with this extension, can be given multiple times
+    #[arg(long, name = "EXT")]
+    contains_ext: Vec<String>,
+
     /// Generate shell completion
     #[arg(short = 'l', long, name = "SHELL")]
-    completion: Option<Shell>,
+    completion: Option<utils::CompletionShell>,
 
-    /// Verbose output
+    /// Verbose output, also enables debug logging unless a log level is given
     #[arg(short, long)]
     verbose: bool,
+
+    /// Log level for diagnostics printed to stderr, overrides the log environment variable
+    #[arg(long, value_name = "LEVEL")]
+    log_level: Option<log::LevelFilter>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Edit torrent metadata and write the modified torrent
+    Edit(EditArgs),
+    /// Check that the files of a torrent exist in a directory with the expected sizes, without hashing
+    #[command(visible_alias = "compare-dir")]
+    VerifyLayout(VerifyLayoutArgs),
+}
+
+#[derive(clap::Args)]
+struct EditArgs {
+    /// Input torrent files
+    #[arg(value_name = "INPUT", required = true, value_hint = clap::ValueHint::FilePath)]
+    inputs: Vec<PathBuf>,
+
+    /// Output file for a single input, defaults to overwriting the input files
+    #[arg(short, long, value_hint = clap::ValueHint::FilePath)]
+    output: Option<PathBuf>,
+
+    /// Set the torrent name, which renames the content root directory of multi-file torrents
+    #[arg(long)]
+    name: Option<String>,
+
+    /// Set or remove the private flag
+    #[arg(long, value_name = "BOOL")]
+    set_private: Option<bool>,
+
+    /// Remove all trackers
+    #[arg(long)]
+    clear_trackers: bool,
+
+    /// Replace a tracker URL, split at the `=` before the new URL. Can be given multiple times.
+    #[arg(long, value_name = "OLD=NEW", value_parser = edit::parse_replacement, conflicts_with = "clear_trackers")]
+    replace_tracker: Vec<(String, String)>,
+
+    /// Replace `OLD` wherever it appears in a tracker URL instead of only matching whole URLs
+    #[arg(long, requires = "replace_tracker")]
+    substring: bool,
+
+    /// Show the changes without writing the output file
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(clap::Args)]
+struct VerifyLayoutArgs {
+    /// Torrent file to check
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    torrent: PathBuf,
+
+    /// Directory containing the downloaded content, for example the client download directory
+    #[arg(value_hint = clap::ValueHint::DirPath)]
+    directory: PathBuf,
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+    if args.max_depth.is_some_and(|depth| depth > 1) {
+        args.recursive = true;
+    }
+
+    if args.no_colour {
+        colored::control::set_override(false);
+    }
+    init_logging(&args);
 
-    if let Some(ref shell) = args.completion {
+    let start = Instant::now();
+    let result = if let Some(ref shell) = args.completion {
         utils::generate_shell_completion(*shell, Args::command(), true, env!("CARGO_BIN_NAME"))
+    } else if let Some(Command::Edit(ref edit_args)) = args.command {
+        edit::run(edit_args)
+    } else if let Some(Command::VerifyLayout(ref layout_args)) = args.command {
+        layout::run(layout_args)
+    } else if let Some(encoded) = args.base64.take() {
+        cli::TorrentInfo::standalone(args).print_base64_torrent(&encoded)
+    } else if args.watch {
+        watch_directories(args)
+    } else if args.no_sort_paths {
+        cli::TorrentInfo::stream(args)
     } else {
-        cli::TorrentInfo::new(args)?.run()
+        cli::TorrentInfo::new(args).and_then(|info| info.run())
+    };
+    log::debug!("Finished in {:.2?}", start.elapsed());
+    result
+}
+
+/// Watch the input directories for new torrent files
+#[cfg(feature = "watch")]
+fn watch_directories(args: Args) -> Result<()> {
+    watch::run(args)
+}
+
+/// Watching directories is not available without the `watch` feature
+#[cfg(not(feature = "watch"))]
+fn watch_directories(_args: Args) -> Result<()> {
+    anyhow::bail!("Watching directories requires the `watch` feature")
+}
+
+/// Set up `env_logger` for diagnostics on stderr.
+///
+/// The `--log-level` argument takes precedence over the environment variable,
+/// which in turn takes precedence over the default `info` level or `debug` with `--verbose`.
+fn init_logging(args: &Args) {
+    let default_level = if args.verbose { "debug" } else { "info" };
+    let mut builder = env_logger::Builder::from_env(env_logger::Env::new().filter_or(LOG_ENV, default_level));
+    if let Some(level) = args.log_level {
+        builder.filter_level(level);
+    }
+    if args.no_colour {
+        builder.write_style(env_logger::WriteStyle::Never);
     }
+    builder.format_timestamp(None).format_target(false).init();
 }
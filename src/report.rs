@@ -0,0 +1,169 @@
+/*
+ * torrentinfo, A torrent file parser
+ * Copyright (C) 2025  Akseli Lukkarila
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+//! Machine-readable torrent reports.
+//!
+//! The report is a stable wire format that is decoupled from the internal `Torrent` model.
+//! Removing or changing a field bumps [`SCHEMA_VERSION`], while new fields are added without a bump.
+//! Reports only contain text and numbers, so they can also be written as TOML with the `toml` feature.
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::errors::Result;
+use crate::{Torrent, to_hex};
+
+/// Version of the report format
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Summary of a single torrent for machine-readable output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TorrentReport {
+    /// Report format version, see [`SCHEMA_VERSION`]
+    pub schema_version: u32,
+    /// Torrent name
+    pub name: Option<String>,
+    /// Free-form comment
+    pub comment: Option<String>,
+    /// Program that created the torrent
+    pub created_by: Option<String>,
+    /// Creation time as a Unix timestamp
+    pub creation_date: Option<i64>,
+    /// String encoding used for text fields
+    pub encoding: Option<String>,
+    /// SHA-1 info hash as lowercase hex
+    pub info_hash: String,
+    /// Total size of all files in bytes
+    pub total_size: i64,
+    /// Number of files
+    pub num_files: usize,
+    /// Piece length in bytes
+    pub piece_length: i64,
+    /// Number of pieces
+    pub num_pieces: usize,
+    /// Whether the private flag is set
+    pub private: bool,
+    /// All tracker URLs without duplicates
+    pub trackers: Vec<String>,
+    /// Tracker URLs grouped by announce-list tier, see [`Torrent::tracker_tiers`]
+    pub tracker_tiers: Vec<TrackerTier>,
+    /// Files in the torrent
+    pub files: Vec<FileReport>,
+}
+
+/// An announce-list tier in a [`TorrentReport`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackerTier {
+    /// Zero-based tier index, lower tiers are tried first
+    pub tier: usize,
+    /// Tracker URLs in the tier
+    pub trackers: Vec<String>,
+}
+
+/// A single file in a [`TorrentReport`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileReport {
+    /// Path relative to the torrent root, separated with `/`
+    pub path: String,
+    /// File size in bytes
+    pub length: i64,
+    /// MD5 checksum as lowercase hex
+    pub md5sum: Option<String>,
+}
+
+/// Reports of multiple torrents as a single TOML document,
+/// since the document root must be a table instead of an array
+#[cfg(feature = "toml")]
+#[derive(Serialize, Deserialize)]
+struct TomlReports<T> {
+    torrents: T,
+}
+
+/// Serialize reports to a TOML document with one `[[torrents]]` table per report
+#[cfg(feature = "toml")]
+pub fn to_toml(reports: &[TorrentReport]) -> Result<String> {
+    Ok(toml::to_string(&TomlReports { torrents: reports })?)
+}
+
+/// Parse reports from a TOML document written by [`to_toml`]
+#[cfg(feature = "toml")]
+pub fn from_toml(document: &str) -> Result<Vec<TorrentReport>> {
+    Ok(toml::from_str::<TomlReports<Vec<TorrentReport>>>(document)?.torrents)
+}
+
+impl TorrentReport {
+    /// Create a report from a parsed torrent
+    pub fn from_torrent(torrent: &Torrent) -> Result<Self> {
+        Ok(Self {
+            schema_version: SCHEMA_VERSION,
+            name: torrent.name().clone(),
+            comment: torrent.comment().clone(),
+            created_by: torrent.created_by().clone(),
+            creation_date: *torrent.creation_date(),
+            encoding: torrent.encoding().clone(),
+            info_hash: to_hex(&torrent.info_hash()?),
+            total_size: torrent.total_size(),
+            num_files: torrent.num_files(),
+            piece_length: *torrent.info().piece_length(),
+            num_pieces: torrent.num_pieces(),
+            private: torrent.info().private().is_some_and(|private| private > 0),
+            trackers: torrent.trackers().into_iter().map(ToString::to_string).collect(),
+            tracker_tiers: torrent
+                .tracker_tiers()
+                .into_iter()
+                .enumerate()
+                .map(|(tier, trackers)| TrackerTier {
+                    tier,
+                    trackers: trackers.into_iter().map(ToString::to_string).collect(),
+                })
+                .collect(),
+            files: Self::file_reports(torrent),
+        })
+    }
+
+    /// Convert the tracker tiers back to the nested bencode `announce-list` format, ordered by tier index
+    #[must_use]
+    pub fn announce_list(&self) -> Vec<Vec<String>> {
+        let mut tiers: Vec<&TrackerTier> = self.tracker_tiers.iter().collect();
+        tiers.sort_by_key(|tier| tier.tier);
+        tiers.into_iter().map(|tier| tier.trackers.clone()).collect()
+    }
+
+    /// Collect file paths and lengths for every torrent layout, see [`Torrent::files_normalized`]
+    fn file_reports(torrent: &Torrent) -> Vec<FileReport> {
+        torrent
+            .files_normalized()
+            .into_iter()
+            .map(|file| FileReport {
+                path: file.path().join("/"),
+                length: file.length(),
+                md5sum: file.md5sum.as_deref().map(md5_hex),
+            })
+            .collect()
+    }
+}
+
+/// Normalize an MD5 checksum to lowercase hex.
+///
+/// The specification stores it as 32 hex characters, but some creators write the raw digest instead.
+fn md5_hex(md5sum: &str) -> String {
+    if md5sum.len() == 32 && md5sum.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        md5sum.to_ascii_lowercase()
+    } else {
+        to_hex(md5sum.as_bytes())
+    }
+}
@@ -0,0 +1,149 @@
+/*
+ * torrentinfo, A torrent file parser
+ * Copyright (C) 2025  Akseli Lukkarila
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>
+ */
+
+//! Heuristic detection of fake or spam torrents.
+//!
+//! Each [`SuspiciousTrait`] is a single check that is cheap to compute from the metadata alone.
+//! The checks are deliberately conservative, but a flagged torrent is not necessarily malicious.
+
+use std::fmt;
+use std::path::Path;
+
+use crate::Torrent;
+
+/// Content smaller than this is considered tiny
+pub const TINY_CONTENT_BYTES: u64 = 1024 * 1024;
+
+/// Name fragments that suggest video releases or other large media
+const MEDIA_NAME_MARKERS: &[&str] = &[
+    "1080p", "2160p", "720p", "4k", "bdrip", "bluray", "brrip", "dvdrip", "hdrip", "hdtv", "hevc", "web-dl", "webrip",
+    "x264", "x265",
+];
+/// Extensions that indicate large media or disc images when they appear in a torrent name
+const LARGE_CONTENT_EXTENSIONS: &[&str] = &["avi", "iso", "m2ts", "mkv", "mp4", "wmv"];
+/// Extensions of files that run code when opened on Windows
+const EXECUTABLE_EXTENSIONS: &[&str] = &["bat", "cmd", "com", "exe", "js", "lnk", "msi", "pif", "scr", "vbs"];
+/// Extensions that executables use to pass as harmless files, like `movie.mkv.exe`
+const DISGUISE_EXTENSIONS: &[&str] = &[
+    "avi", "doc", "epub", "flac", "jpg", "mkv", "mov", "mp3", "mp4", "pdf", "png", "txt", "wmv",
+];
+/// Phrases found in the comments and URLs of spam and malware torrents
+const SPAM_MARKERS: &[&str] = &[
+    "bit.ly",
+    "click here",
+    "download now",
+    "free download",
+    "keygen",
+    "password",
+    "tinyurl.com",
+];
+
+/// A trait that makes a torrent look fake or spammy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuspiciousTrait {
+    /// The only file is smaller than [`TINY_CONTENT_BYTES`] but the name suggests large media
+    TinyContent,
+    /// The only file is an executable but the name suggests media
+    ExecutableMedia,
+    /// A file name hides an executable behind a media or document extension, like `movie.mkv.exe`
+    DoubleExtension,
+    /// The comment, creator or a tracker URL contains a known spam marker
+    SpamMarker,
+}
+
+impl SuspiciousTrait {
+    /// Run all checks on the torrent and return the traits that apply, in declaration order
+    #[must_use]
+    pub fn detect(torrent: &Torrent) -> Vec<Self> {
+        let name = torrent.name().as_deref().unwrap_or_default().to_lowercase();
+        let media_name = looks_like_media(&name);
+        let files: Vec<String> = torrent
+            .iter_files()
+            .filter_map(|file| file.path().last().map(|name| name.to_lowercase()))
+            .collect();
+        let single_file = match files.as_slice() {
+            [file] => Some(file.as_str()),
+            _ => None,
+        };
+
+        let text = [torrent.comment(), torrent.created_by()]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .chain(torrent.trackers())
+            .collect::<Vec<&str>>()
+            .join("\n")
+            .to_lowercase();
+
+        [
+            (
+                Self::TinyContent,
+                single_file.is_some() && media_name && torrent.total_size_bytes() < TINY_CONTENT_BYTES,
+            ),
+            (
+                Self::ExecutableMedia,
+                media_name && single_file.is_some_and(|file| has_extension(file, EXECUTABLE_EXTENSIONS)),
+            ),
+            (
+                Self::DoubleExtension,
+                files.iter().any(|file| has_double_extension(file)),
+            ),
+            (
+                Self::SpamMarker,
+                SPAM_MARKERS.iter().any(|marker| text.contains(marker)),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(flag, applies)| applies.then_some(flag))
+        .collect()
+    }
+}
+
+impl fmt::Display for SuspiciousTrait {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Self::TinyContent => "tiny content for a media name",
+            Self::ExecutableMedia => "executable in a media torrent",
+            Self::DoubleExtension => "executable with a double extension",
+            Self::SpamMarker => "spam marker in metadata",
+        };
+        write!(f, "{description}")
+    }
+}
+
+/// Check if a lowercase torrent name looks like a video release or a disc image
+fn looks_like_media(name: &str) -> bool {
+    MEDIA_NAME_MARKERS.iter().any(|marker| name.contains(marker)) || has_extension(name, LARGE_CONTENT_EXTENSIONS)
+}
+
+/// Check if a lowercase file name has one of the given extensions
+fn has_extension(name: &str, extensions: &[&str]) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extensions.contains(&extension))
+}
+
+/// Check if a lowercase file name is an executable with a media extension before the real one
+fn has_double_extension(name: &str) -> bool {
+    has_extension(name, EXECUTABLE_EXTENSIONS)
+        && Path::new(name)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| has_extension(stem, DISGUISE_EXTENSIONS))
+}
@@ -1,36 +1,187 @@
-const TORRENT_EXTENSION: &str = "torrent";
-const MAX_WALK_DEPTH: usize = 999;
+/// File extension for magnet link sidecar files
+pub const MAGNET_EXTENSION: &str = "magnet";
+/// Sizes above this are highlighted in red in file listings
+const LARGE_FILE_THRESHOLD: u64 = 1024 * 1024 * 1024;
+/// Sizes above this are highlighted in yellow in file listings
+const MEDIUM_FILE_THRESHOLD: u64 = 100 * 1024 * 1024;
+/// Pieces bar width used when the terminal width is not available
+pub const DEFAULT_BAR_WIDTH: usize = 50;
+/// Byte strings longer than this are shown as a byte count in the raw dump by default
+pub const DEFAULT_INLINE_BYTES: usize = 80;
+/// Number of leading bytes shown in the hex preview of binary values
+const HEX_PREVIEW_BYTES: usize = 16;
+/// Terminal width used for truncating file paths when stdout is not a terminal
+pub const DEFAULT_TERMINAL_WIDTH: usize = 80;
+/// Marker for the part of a truncated path that was left out
+const ELLIPSIS: char = '…';
+/// Number of bytes on each hexdump line
+const HEXDUMP_LINE_BYTES: usize = 16;
+/// Path segment prefix of announce URLs that is replaced to get the scrape URL
+const ANNOUNCE_SEGMENT: &str = "announce";
+/// Replacement path segment prefix for scrape URLs
+const SCRAPE_SEGMENT: &str = "scrape";
 
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
 use std::ffi::OsStr;
+use std::hash::BuildHasher;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, anyhow};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD_PAD_INDIFFERENT;
 use chrono::{TimeZone, Utc};
-use clap_complete::Shell;
+use clap_complete::{Generator, Shell};
+use clap_complete_nushell::Nushell;
 use colored::{ColoredString, Colorize};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use itertools::Itertools;
 use number_prefix::NumberPrefix;
-use walkdir::WalkDir;
+pub use torrentinfo::TORRENT_EXTENSION;
+use torrentinfo::{MAX_WALK_DEPTH, Torrent, TorrentSummary};
 
 /// Return file root and list of files from the input path that can be either a directory or single file.
-pub fn get_torrent_files(input: &PathBuf, recursive: bool, verbose: bool) -> anyhow::Result<(PathBuf, Vec<PathBuf>)> {
+///
+/// Files found in a directory are skipped if their path matches any of the exclude patterns.
+/// Hidden files and directories are only searched when `include_hidden` is set.
+/// Directory contents are sorted with [`natural_cmp`] when `natural_sort` is set.
+/// An explicit `max_depth` overrides the search depth selected by `recursive`.
+pub fn get_torrent_files(
+    input: &Path,
+    recursive: bool,
+    max_depth: Option<usize>,
+    include_hidden: bool,
+    natural_sort: bool,
+    exclude: &GlobSet,
+) -> anyhow::Result<(PathBuf, Vec<PathBuf>)> {
     if input.is_file() {
-        if verbose {
-            println!("{}", format!("Reading file: {}", input.display()).bold().magenta());
-        }
+        log::debug!("Reading file: {}", input.display());
         if input.extension() == Some(TORRENT_EXTENSION.as_ref()) {
             let parent = input.parent().context("Failed to get parent directory")?.to_path_buf();
-            Ok((parent, vec![input.clone()]))
+            Ok((parent, vec![input.to_path_buf()]))
         } else {
-            Err(anyhow!("Input path is not an XML file: {}", input.display()))
+            Err(anyhow!("Input path is not a torrent file: {}", input.display()))
         }
     } else {
-        if verbose {
-            println!(
-                "{}",
-                format!("Reading files from: {}", input.display()).bold().magenta()
-            );
-        }
-        Ok((input.clone(), get_all_torrent_files(input, recursive)))
+        log::debug!("Reading files from: {}", input.display());
+        Ok((
+            input.to_path_buf(),
+            get_all_torrent_files(input, recursive, max_depth, include_hidden, natural_sort, exclude),
+        ))
+    }
+}
+
+/// Resolves all input paths to absolute paths.
+///
+/// If no paths are given, the current working directory is used.
+pub fn resolve_input_paths(paths: &[PathBuf]) -> anyhow::Result<Vec<PathBuf>> {
+    if paths.is_empty() {
+        return Ok(vec![resolve_input_path(None)?]);
+    }
+    paths
+        .iter()
+        .map(|path| {
+            if is_url(path) {
+                Ok(path.clone())
+            } else {
+                resolve_input_path(Some(path))
+            }
+        })
+        .collect()
+}
+
+/// Build a glob set from exclude patterns, a path is excluded if it matches any pattern
+pub fn build_exclude_set(patterns: &[String]) -> anyhow::Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).with_context(|| format!("Invalid exclude pattern: {pattern}"))?);
+    }
+    Ok(builder.build()?)
+}
+
+/// Check if the input path is an HTTP(S) URL instead of a local path
+pub fn is_url(path: &Path) -> bool {
+    path.to_str()
+        .is_some_and(|path| path.starts_with("http://") || path.starts_with("https://"))
+}
+
+/// Read torrent file bytes from a local file or an HTTP(S) URL.
+///
+/// URLs are only supported when built with the `network` feature.
+pub fn read_torrent_bytes(path: &Path) -> anyhow::Result<Vec<u8>> {
+    if is_url(path) {
+        #[cfg(feature = "network")]
+        return Ok(Torrent::download_bytes(&path_to_string(path))?);
+        #[cfg(not(feature = "network"))]
+        anyhow::bail!(
+            "Reading torrents from URLs requires the `network` feature: {}",
+            path.display()
+        );
+    }
+    Ok(Torrent::read_bytes(path)?)
+}
+
+/// Read and parse a torrent from a local file or an HTTP(S) URL.
+///
+/// With `strict`, torrents that do not follow the specification are rejected.
+pub fn read_torrent(path: &Path, strict: bool) -> anyhow::Result<Torrent> {
+    let bytes = read_torrent_bytes(path)?;
+    if strict {
+        Ok(Torrent::from_buf_strict(&bytes)?)
+    } else {
+        Ok(Torrent::from_buf(&bytes)?)
+    }
+}
+
+/// Read a torrent from a local file or an HTTP(S) URL without the piece hashes, see [`Torrent::from_buf_metadata_only`]
+pub fn read_torrent_metadata(path: &Path) -> anyhow::Result<Torrent> {
+    let bytes = read_torrent_bytes(path)?;
+    Ok(Torrent::from_buf_metadata_only(&bytes)?)
+}
+
+/// Decode and parse a base64-encoded torrent.
+///
+/// Whitespace such as line breaks in pasted input is ignored and padding is optional.
+/// With `strict`, torrents that do not follow the specification are rejected.
+pub fn decode_base64_torrent(input: &str, strict: bool) -> anyhow::Result<Torrent> {
+    let encoded: String = input
+        .chars()
+        .filter(|character| !character.is_ascii_whitespace())
+        .collect();
+    let bytes = STANDARD_PAD_INDIFFERENT
+        .decode(encoded)
+        .context("Invalid base64 input")?;
+    let torrent = if strict {
+        Torrent::from_buf_strict(&bytes)
+    } else {
+        Torrent::from_buf(&bytes)
+    };
+    torrent.context("Base64 input is not a valid torrent")
+}
+
+/// Read only the fields needed for listing and sorting from a local file or an HTTP(S) URL.
+///
+/// Strict mode needs a full parse to check the torrent, so the summary is built from the parsed torrent.
+pub fn read_torrent_summary(path: &Path, strict: bool) -> anyhow::Result<TorrentSummary> {
+    let bytes = read_torrent_bytes(path)?;
+    if strict {
+        Ok(TorrentSummary::from_torrent(Torrent::from_buf_strict(&bytes)?, false)?)
+    } else {
+        Ok(TorrentSummary::from_buf(&bytes)?)
+    }
+}
+
+/// Read the name, size and info hash from a local file or an HTTP(S) URL.
+///
+/// Strict mode needs a full parse to check the torrent, so the summary is built from the parsed torrent.
+pub fn read_torrent_peek(path: &Path, strict: bool) -> anyhow::Result<TorrentSummary> {
+    let bytes = read_torrent_bytes(path)?;
+    if strict {
+        Ok(TorrentSummary::from_torrent(Torrent::from_buf_strict(&bytes)?, true)?)
+    } else {
+        Ok(Torrent::peek_buf(&bytes)?)
     }
 }
 
@@ -77,6 +228,8 @@ pub fn resolve_input_path(path: Option<&Path>) -> anyhow::Result<PathBuf> {
 /// If the full path is within the root directory, the function returns the relative path.
 /// Otherwise, it returns just the filename. If the filename cannot be determined, the
 /// full path is returned.
+/// Invalid Unicode is removed from the result unless `raw_names` is set,
+/// in which case it is shown as U+FFFD replacement characters.
 ///
 /// ```rust
 /// use std::path::Path;
@@ -84,34 +237,61 @@ pub fn resolve_input_path(path: Option<&Path>) -> anyhow::Result<PathBuf> {
 ///
 /// let root = Path::new("/root/dir");
 /// let full_path = root.join("subdir/file.txt");
-/// let relative_path = get_relative_path_or_filename(&full_path, root);
+/// let relative_path = get_relative_path_or_filename(&full_path, root, false);
 /// assert_eq!(relative_path, "subdir/file.txt");
 ///
 /// let outside_path = Path::new("/root/dir/another.txt");
-/// let relative_or_filename = get_relative_path_or_filename(&outside_path, root);
+/// let relative_or_filename = get_relative_path_or_filename(&outside_path, root, false);
 /// assert_eq!(relative_or_filename, "another.txt");
 /// ```
 #[must_use]
-pub fn get_relative_path_or_filename(full_path: &Path, root: &Path) -> String {
+pub fn get_relative_path_or_filename(full_path: &Path, root: &Path, raw_names: bool) -> String {
+    if is_url(full_path) {
+        return full_path.display().to_string();
+    }
     if full_path == root {
-        return full_path
-            .file_name()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .to_string()
-            .replace('\u{FFFD}', "");
+        return display_name(full_path.file_name().unwrap_or_default(), raw_names);
     }
     full_path.strip_prefix(root).map_or_else(
         |_| {
-            full_path.file_name().map_or_else(
-                || full_path.display().to_string(),
-                |name| name.to_string_lossy().to_string().replace('\u{FFFD}', ""),
-            )
+            full_path
+                .file_name()
+                .map_or_else(|| full_path.display().to_string(), |name| display_name(name, raw_names))
         },
-        |relative_path| relative_path.display().to_string(),
+        |relative_path| display_name(relative_path.as_os_str(), raw_names),
     )
 }
 
+/// Convert a file name for display, removing invalid Unicode unless `raw` is set.
+///
+/// With `raw`, invalid bytes are shown as U+FFFD replacement characters
+/// so that names differing only in invalid bytes remain distinguishable.
+pub fn display_name(name: &OsStr, raw: bool) -> String {
+    let lossy = name.to_string_lossy();
+    if raw {
+        lossy.into_owned()
+    } else {
+        lossy.replace('\u{FFFD}', "")
+    }
+}
+
+/// Replace the native path separator with `/`, matching the separators used inside torrents.
+///
+/// Does nothing on platforms where the native separator already is `/`.
+#[must_use]
+pub fn forward_slashes(path: String) -> String {
+    replace_separator(path, std::path::MAIN_SEPARATOR)
+}
+
+/// Replace the given path separator with `/`
+fn replace_separator(path: String, separator: char) -> String {
+    if separator == '/' {
+        path
+    } else {
+        path.replace(separator, "/")
+    }
+}
+
 /// Convert a path to string with invalid Unicode handling
 pub fn path_to_string(path: &Path) -> String {
     path.to_str().map_or_else(
@@ -147,38 +327,257 @@ pub fn format_creation_date(timestamp: i64) -> String {
 }
 
 /// Format file size with appropriate units
-pub fn format_file_size(size: f64) -> String {
-    match NumberPrefix::decimal(size) {
+pub fn format_file_size(size: u64) -> String {
+    match NumberPrefix::decimal(size as f64) {
         NumberPrefix::Standalone(bytes) => format!("{bytes} bytes"),
         NumberPrefix::Prefixed(prefix, n) => format!("{n:.2} {prefix}B"),
     }
 }
 
-/// Collect all torrent files from the given root path and sort by name.
-fn get_all_torrent_files<P: AsRef<Path>>(root: P, recursive: bool) -> Vec<PathBuf> {
-    let extension = OsStr::new(TORRENT_EXTENSION);
-    let max_depth = if recursive { MAX_WALK_DEPTH } else { 1 };
-    let mut files: Vec<PathBuf> = WalkDir::new(root)
-        .max_depth(max_depth)
-        .into_iter()
-        .filter_entry(|e| !is_hidden(e))
-        .filter_map(std::result::Result::ok)
-        .map(|e| e.path().to_owned())
-        .filter(|path| path.is_file() && path.extension() == Some(extension))
-        .collect();
+/// Derive the scrape URL from a tracker announce URL.
+///
+/// The last path segment starting with `announce` has that prefix replaced with `scrape`,
+/// keeping passkey segments, file extensions and the query string intact.
+/// Returns `None` if no path segment starts with `announce`, in which case the tracker does not support scraping.
+pub fn scrape_url(announce: &str) -> Option<String> {
+    let (base, suffix) = announce
+        .find(['?', '#'])
+        .map_or((announce, ""), |index| announce.split_at(index));
+    let authority_start = base.find("://").map_or(0, |index| index + 3);
+    let path_start = authority_start + base[authority_start..].find('/')?;
+    let segment_start = base[path_start..]
+        .rmatch_indices('/')
+        .map(|(index, _)| path_start + index + 1)
+        .find(|&start| base[start..].starts_with(ANNOUNCE_SEGMENT))?;
+    Some(format!(
+        "{}{SCRAPE_SEGMENT}{}{suffix}",
+        &base[..segment_start],
+        &base[segment_start + ANNOUNCE_SEGMENT.len()..]
+    ))
+}
+
+/// Get the bytes as text if they are valid UTF-8 without control characters
+#[must_use]
+pub fn printable_text(bytes: &[u8]) -> Option<&str> {
+    std::str::from_utf8(bytes)
+        .ok()
+        .filter(|text| !text.chars().any(char::is_control))
+}
+
+/// Format a hex preview of the first 16 bytes of a binary value together with its length
+#[must_use]
+pub fn hex_preview(bytes: &[u8]) -> String {
+    let preview = torrentinfo::to_hex(&bytes[..bytes.len().min(HEX_PREVIEW_BYTES)]);
+    let ellipsis = if bytes.len() > HEX_PREVIEW_BYTES { "..." } else { "" };
+    format!("{preview}{ellipsis} [{} Bytes]", bytes.len())
+}
+
+/// Format bytes as a hexdump like `xxd`, with an offset, 16 bytes as hex and their ASCII characters per line
+pub fn hexdump(bytes: &[u8]) -> String {
+    let mut lines = Vec::with_capacity(bytes.len().div_ceil(HEXDUMP_LINE_BYTES));
+    for (index, chunk) in bytes.chunks(HEXDUMP_LINE_BYTES).enumerate() {
+        let hex = chunk.chunks(2).map(torrentinfo::to_hex).join(" ");
+        let ascii: String = chunk
+            .iter()
+            .map(|&byte| {
+                if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        lines.push(format!("{:08x}: {hex:<39}  {ascii}", index * HEXDUMP_LINE_BYTES));
+    }
+    lines.join("\n")
+}
+
+/// Format a piece length with binary units, for example `256 KiB`
+pub fn format_piece_length(length: u64) -> String {
+    match NumberPrefix::binary(length as f64) {
+        NumberPrefix::Standalone(bytes) => format!("{bytes} bytes"),
+        NumberPrefix::Prefixed(prefix, n) => format!("{n} {prefix}B"),
+    }
+}
+
+/// Get a path that does not exist yet by adding a numbered suffix to the file stem if needed.
+///
+/// For example `name.torrent` becomes `name (1).torrent` if the original path is already taken.
+pub fn get_unique_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().map(OsStr::to_string_lossy).unwrap_or_default();
+    let extension = path.extension().map(OsStr::to_string_lossy);
+    (1..=u32::MAX)
+        .map(|number| {
+            let filename = extension.as_ref().map_or_else(
+                || format!("{stem} ({number})"),
+                |extension| format!("{stem} ({number}).{extension}"),
+            );
+            path.with_file_name(filename)
+        })
+        .find(|candidate| !candidate.exists())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Format file size with a colour based on its magnitude.
+///
+/// Sizes over 1 GiB are red, over 100 MiB yellow, and smaller sizes cyan.
+pub fn colorize_size(bytes: u64) -> ColoredString {
+    let size = format_file_size(bytes);
+    if bytes > LARGE_FILE_THRESHOLD {
+        size.red()
+    } else if bytes > MEDIUM_FILE_THRESHOLD {
+        size.yellow()
+    } else {
+        size.cyan()
+    }
+}
+
+/// Render the piece count as a bar of block characters that fits in the given width.
+///
+/// Each block stands for the same number of pieces, so torrents with more pieces than
+/// the width fill the whole bar and smaller torrents get one block per piece.
+pub fn pieces_bar(num_pieces: usize, width: usize) -> String {
+    let width = width.max(1);
+    let pieces_per_block = num_pieces.div_ceil(width).max(1);
+    let blocks = num_pieces.div_ceil(pieces_per_block);
+    format!(
+        "{} {num_pieces} pieces, {pieces_per_block} per block",
+        "█".repeat(blocks)
+    )
+}
 
-    files.sort_unstable_by(|a, b| {
-        let a_str = a.to_string_lossy().to_lowercase();
-        let b_str = b.to_string_lossy().to_lowercase();
-        a_str.cmp(&b_str)
-    });
+/// Get the terminal width in columns, if stdout is a terminal
+pub fn terminal_width() -> Option<usize> {
+    terminal_size::terminal_size().map(|(terminal_size::Width(width), _)| usize::from(width))
+}
+
+/// Shorten a path to at most `max_width` characters by replacing the middle with `…`.
+///
+/// The file name is kept whole when it fits, so only the leading directories are shortened.
+/// A file name that is too long on its own keeps its last characters, including the extension.
+#[must_use]
+pub fn truncate_middle(path: &str, max_width: usize) -> Cow<'_, str> {
+    let length = path.chars().count();
+    if length <= max_width {
+        return Cow::Borrowed(path);
+    }
+    let Some(available) = max_width.checked_sub(1) else {
+        return Cow::Borrowed("");
+    };
+    let tail_start = path.rfind('/').unwrap_or(0);
+    let tail_length = path[tail_start..].chars().count();
+    let (head_length, tail_length) = if tail_length <= available {
+        (available - tail_length, tail_length)
+    } else {
+        (0, available)
+    };
+    let head: String = path.chars().take(head_length).collect();
+    let tail: String = path.chars().skip(length - tail_length).collect();
+    Cow::Owned(format!("{head}{ELLIPSIS}{tail}"))
+}
+
+/// Collect all torrent files from the given root path sorted by name, skipping excluded paths
+fn get_all_torrent_files(
+    root: &Path,
+    recursive: bool,
+    max_depth: Option<usize>,
+    include_hidden: bool,
+    natural_sort: bool,
+    exclude: &GlobSet,
+) -> Vec<PathBuf> {
+    let found = max_depth.map_or_else(
+        || Torrent::find_in_dir(root, recursive, include_hidden),
+        |depth| Torrent::find_in_dir_to_depth(root, depth, include_hidden),
+    );
+    let mut files: Vec<PathBuf> = found.into_iter().filter(|path| !exclude.is_match(path)).collect();
+    if natural_sort {
+        files.sort_by(|left, right| natural_cmp(&left.to_string_lossy(), &right.to_string_lossy()));
+    }
     files
 }
 
-/// Check if entry is a hidden file or directory (starts with '.')
+/// Iterate over the torrent files in a directory as they are found, see [`get_torrent_files`].
+///
+/// The paths are not sorted, so [`natural_cmp`] ordering is not available.
+pub fn iter_torrent_files<'a>(
+    root: &Path,
+    recursive: bool,
+    max_depth: Option<usize>,
+    include_hidden: bool,
+    exclude: &'a GlobSet,
+) -> impl Iterator<Item = PathBuf> + use<'a> {
+    let depth = max_depth.unwrap_or(if recursive { MAX_WALK_DEPTH } else { 1 });
+    Torrent::iter_in_dir(root, depth, include_hidden).filter(|path| !exclude.is_match(path))
+}
+
+/// Parse a 1-based selection from a numbered list of `count` items into a 0-based index
 #[must_use]
-fn is_hidden(entry: &walkdir::DirEntry) -> bool {
-    entry.file_name().to_str().is_some_and(|s| s.starts_with('.'))
+pub fn parse_selection(input: &str, count: usize) -> Option<usize> {
+    input
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|number| (1..=count).contains(number))
+        .map(|number| number - 1)
+}
+
+/// Compare strings case-insensitively, with runs of digits compared by their numeric value.
+///
+/// For example `ep2` sorts before `ep10`. Strings that only differ by case or leading zeros
+/// fall back to a plain comparison so the order is always deterministic.
+pub fn natural_cmp(left: &str, right: &str) -> Ordering {
+    let mut left_chars = left.chars().peekable();
+    let mut right_chars = right.chars().peekable();
+    loop {
+        let ordering = match (left_chars.peek(), right_chars.peek()) {
+            (None, None) => return left.cmp(right),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(left_char), Some(right_char)) if left_char.is_ascii_digit() && right_char.is_ascii_digit() => {
+                let left_digits: String = left_chars.by_ref().peeking_take_while(char::is_ascii_digit).collect();
+                let right_digits: String = right_chars.by_ref().peeking_take_while(char::is_ascii_digit).collect();
+                let left_number = left_digits.trim_start_matches('0');
+                let right_number = right_digits.trim_start_matches('0');
+                left_number
+                    .len()
+                    .cmp(&right_number.len())
+                    .then_with(|| left_number.cmp(right_number))
+            }
+            (Some(left_char), Some(right_char)) => {
+                let ordering = left_char.to_lowercase().cmp(right_char.to_lowercase());
+                left_chars.next();
+                right_chars.next();
+                ordering
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Generate a random 20-byte peer id in the Azureus-style format `-TIxyz0-<random>`.
+pub fn generate_peer_id() -> [u8; 20] {
+    const CHARSET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let prefix = format!(
+        "-TI{}{}{}0-",
+        env!("CARGO_PKG_VERSION_MAJOR"),
+        env!("CARGO_PKG_VERSION_MINOR"),
+        env!("CARGO_PKG_VERSION_PATCH")
+    );
+    let random_state = RandomState::new();
+    let mut peer_id = [0u8; 20];
+    for (index, byte) in peer_id.iter_mut().enumerate() {
+        *byte = prefix.as_bytes().get(index).copied().unwrap_or_else(|| {
+            let random = random_state.hash_one(index) as usize;
+            CHARSET[random % CHARSET.len()]
+        });
+    }
+    peer_id
 }
 
 /// Format bool value as a coloured string.
@@ -187,6 +586,46 @@ pub fn colorize_bool(value: bool) -> ColoredString {
     if value { "true".green() } else { "false".red() }
 }
 
+/// Shell to generate a completion script for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Elvish,
+    Fish,
+    Nushell,
+    #[value(name = "powershell")]
+    PowerShell,
+    Zsh,
+}
+
+impl CompletionShell {
+    /// Get the matching `clap_complete` shell, `None` for shells with a separate generator crate
+    const fn clap_shell(self) -> Option<Shell> {
+        match self {
+            Self::Bash => Some(Shell::Bash),
+            Self::Elvish => Some(Shell::Elvish),
+            Self::Fish => Some(Shell::Fish),
+            Self::PowerShell => Some(Shell::PowerShell),
+            Self::Zsh => Some(Shell::Zsh),
+            Self::Nushell => None,
+        }
+    }
+}
+
+impl Generator for CompletionShell {
+    fn file_name(&self, name: &str) -> String {
+        self.clap_shell()
+            .map_or_else(|| Nushell.file_name(name), |shell| shell.file_name(name))
+    }
+
+    fn generate(&self, command: &clap::Command, buf: &mut dyn Write) {
+        match self.clap_shell() {
+            Some(shell) => shell.generate(command, buf),
+            None => Nushell.generate(command, buf),
+        }
+    }
+}
+
 /// Generate a shell completion script for the given shell.
 ///
 /// # Errors
@@ -194,7 +633,7 @@ pub fn colorize_bool(value: bool) -> ColoredString {
 /// - The shell completion directory cannot be determined or created
 /// - The completion file cannot be generated or written
 pub fn generate_shell_completion(
-    shell: Shell,
+    shell: CompletionShell,
     mut command: clap::Command,
     install: bool,
     command_name: &str,
@@ -212,14 +651,14 @@ pub fn generate_shell_completion(
 /// Determine the appropriate directory for storing shell completions.
 ///
 /// First checks if the user-specific directory exists,
-/// then checks for the global directory.
+/// then checks for the global directory for shells that have one.
 /// If neither exist, creates and uses the user-specific dir.
-fn get_shell_completion_dir(shell: Shell, name: &str) -> anyhow::Result<PathBuf> {
+fn get_shell_completion_dir(shell: CompletionShell, name: &str) -> anyhow::Result<PathBuf> {
     let home = dirs::home_dir().ok_or_else(|| anyhow!("Failed to get home directory"))?;
 
     // Special handling for oh-my-zsh.
     // Create custom "plugin", which will then have to be loaded in .zshrc
-    if shell == Shell::Zsh {
+    if shell == CompletionShell::Zsh {
         let omz_plugins = home.join(".oh-my-zsh/custom/plugins");
         if omz_plugins.exists() {
             let plugin_dir = omz_plugins.join(name);
@@ -229,21 +668,21 @@ fn get_shell_completion_dir(shell: Shell, name: &str) -> anyhow::Result<PathBuf>
     }
 
     let user_dir = match shell {
-        Shell::PowerShell => {
+        CompletionShell::PowerShell => {
             if cfg!(windows) {
                 home.join(r"Documents\PowerShell\completions")
             } else {
                 home.join(".config/powershell/completions")
             }
         }
-        Shell::Bash => home.join(".bash_completion.d"),
-        Shell::Elvish => {
+        CompletionShell::Bash => home.join(".bash_completion.d"),
+        CompletionShell::Elvish => {
             let config_base = dirs::config_dir().unwrap_or_else(|| home.clone());
             config_base.join("elvish").join("lib")
         }
-        Shell::Fish => home.join(".config/fish/completions"),
-        Shell::Zsh => home.join(".zsh/completions"),
-        _ => anyhow::bail!("Unsupported shell"),
+        CompletionShell::Fish => home.join(".config/fish/completions"),
+        CompletionShell::Nushell => home.join(".config/nushell/completions"),
+        CompletionShell::Zsh => home.join(".zsh/completions"),
     };
 
     if user_dir.exists() {
@@ -251,20 +690,13 @@ fn get_shell_completion_dir(shell: Shell, name: &str) -> anyhow::Result<PathBuf>
     }
 
     let global_dir = match shell {
-        Shell::PowerShell => {
-            if cfg!(windows) {
-                home.join(r"Documents\PowerShell\completions")
-            } else {
-                home.join(".config/powershell/completions")
-            }
-        }
-        Shell::Bash => PathBuf::from("/etc/bash_completion.d"),
-        Shell::Fish => PathBuf::from("/usr/share/fish/completions"),
-        Shell::Zsh => PathBuf::from("/usr/share/zsh/site-functions"),
-        _ => anyhow::bail!("Unsupported shell"),
+        CompletionShell::Bash => Some(PathBuf::from("/etc/bash_completion.d")),
+        CompletionShell::Fish => Some(PathBuf::from("/usr/share/fish/completions")),
+        CompletionShell::Zsh => Some(PathBuf::from("/usr/share/zsh/site-functions")),
+        CompletionShell::Elvish | CompletionShell::Nushell | CompletionShell::PowerShell => None,
     };
 
-    if global_dir.exists() {
+    if let Some(global_dir) = global_dir.filter(|dir| dir.exists()) {
         return Ok(global_dir);
     }
 
@@ -278,3 +710,249 @@ fn get_shell_completion_dir(shell: Shell, name: &str) -> anyhow::Result<PathBuf>
     })?;
     Ok(user_dir)
 }
+
+#[cfg(test)]
+mod tests {
+    use colored::Color;
+
+    use super::*;
+
+    #[test]
+    fn test_nushell_completion_contains_binary_name() {
+        let mut buffer = Vec::new();
+        clap_complete::generate(
+            CompletionShell::Nushell,
+            &mut <crate::Args as clap::CommandFactory>::command(),
+            "torrentinfo",
+            &mut buffer,
+        );
+        let script = String::from_utf8(buffer).expect("Completion script should be UTF-8");
+        assert!(script.contains("export extern torrentinfo"));
+        assert!(script.contains("--completion"));
+        assert_eq!(CompletionShell::Nushell.file_name("torrentinfo"), "torrentinfo.nu");
+        assert_eq!(CompletionShell::Zsh.file_name("torrentinfo"), "_torrentinfo");
+    }
+
+    #[test]
+    fn test_colorize_size_small() {
+        let size = colorize_size(1000);
+        assert_eq!(size.input, "1.00 kB");
+        assert_eq!(size.fgcolor, Some(Color::Cyan));
+        assert_eq!(colorize_size(MEDIUM_FILE_THRESHOLD).fgcolor, Some(Color::Cyan));
+    }
+
+    #[test]
+    fn test_colorize_size_medium() {
+        assert_eq!(colorize_size(MEDIUM_FILE_THRESHOLD + 1).fgcolor, Some(Color::Yellow));
+        assert_eq!(colorize_size(LARGE_FILE_THRESHOLD).fgcolor, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn test_pieces_bar_scales_to_width() {
+        assert_eq!(
+            pieces_bar(100, 10),
+            format!("{} 100 pieces, 10 per block", "█".repeat(10))
+        );
+        assert_eq!(
+            pieces_bar(101, 10),
+            format!("{} 101 pieces, 11 per block", "█".repeat(10))
+        );
+        assert_eq!(
+            pieces_bar(24208, 50)
+                .chars()
+                .filter(|character| *character == '█')
+                .count(),
+            50
+        );
+    }
+
+    #[test]
+    fn test_pieces_bar_fewer_pieces_than_width() {
+        assert_eq!(pieces_bar(3, 50), "███ 3 pieces, 1 per block");
+        assert_eq!(pieces_bar(0, 50), " 0 pieces, 1 per block");
+        assert_eq!(pieces_bar(5, 0), "█ 5 pieces, 5 per block");
+    }
+
+    #[test]
+    fn test_format_piece_length() {
+        assert_eq!(format_piece_length(262_144), "256 KiB");
+        assert_eq!(format_piece_length(16 * 1024 * 1024), "16 MiB");
+        assert_eq!(format_piece_length(1536 * 1024), "1.5 MiB");
+        assert_eq!(format_piece_length(1000), "1000 bytes");
+    }
+
+    #[test]
+    fn test_replace_separator() {
+        assert_eq!(
+            replace_separator(r"dir\sub\name.torrent".to_string(), '\\'),
+            "dir/sub/name.torrent"
+        );
+        assert_eq!(
+            replace_separator("dir/sub/name.torrent".to_string(), '/'),
+            "dir/sub/name.torrent"
+        );
+        assert_eq!(forward_slashes("name.torrent".to_string()), "name.torrent");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_get_relative_path_or_filename_invalid_unicode() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let root = Path::new("/data");
+        let path = root.join(OsStr::from_bytes(b"dir/na\xfeme.torrent"));
+        assert_eq!(get_relative_path_or_filename(&path, root, false), "dir/name.torrent");
+        assert_eq!(
+            get_relative_path_or_filename(&path, root, true),
+            "dir/na\u{FFFD}me.torrent"
+        );
+        let outside = Path::new("/other").join(OsStr::from_bytes(b"na\xfeme.torrent"));
+        assert_eq!(get_relative_path_or_filename(&outside, root, false), "name.torrent");
+        assert_eq!(
+            get_relative_path_or_filename(&outside, root, true),
+            "na\u{FFFD}me.torrent"
+        );
+    }
+
+    #[test]
+    fn test_hexdump_format() {
+        assert_eq!(
+            hexdump(b"d4:name3:abce\x00\xff"),
+            "00000000: 6434 3a6e 616d 6533 3a61 6263 6500 ff    d4:name3:abce.."
+        );
+        let lines: Vec<String> = hexdump(&[b'a'; 17]).lines().map(str::to_string).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            format!("00000000: {}  {}", "6161 ".repeat(8).trim_end(), "a".repeat(16))
+        );
+        assert_eq!(lines[1], format!("00000010: 61{}  a", " ".repeat(37)));
+        assert_eq!(hexdump(&[]), "");
+    }
+
+    #[test]
+    fn test_scrape_url_replaces_announce() {
+        assert_eq!(
+            scrape_url("https://torrent.ubuntu.com/announce").as_deref(),
+            Some("https://torrent.ubuntu.com/scrape")
+        );
+        assert_eq!(
+            scrape_url("http://tracker.example.org:8080/announce.php").as_deref(),
+            Some("http://tracker.example.org:8080/scrape.php")
+        );
+    }
+
+    #[test]
+    fn test_scrape_url_keeps_passkey_segments() {
+        assert_eq!(
+            scrape_url("https://tracker.example.org/0123456789abcdef/announce").as_deref(),
+            Some("https://tracker.example.org/0123456789abcdef/scrape")
+        );
+        assert_eq!(
+            scrape_url("https://tracker.example.org/announce/0123456789abcdef").as_deref(),
+            Some("https://tracker.example.org/scrape/0123456789abcdef")
+        );
+        assert_eq!(
+            scrape_url("https://tracker.example.org/announce/announce/key").as_deref(),
+            Some("https://tracker.example.org/announce/scrape/key")
+        );
+    }
+
+    #[test]
+    fn test_scrape_url_keeps_query_string() {
+        assert_eq!(
+            scrape_url("https://tracker.example.org/announce?passkey=abc&announce=1").as_deref(),
+            Some("https://tracker.example.org/scrape?passkey=abc&announce=1")
+        );
+        assert_eq!(
+            scrape_url("https://tracker.example.org/x/announce.php?uk=abc#top").as_deref(),
+            Some("https://tracker.example.org/x/scrape.php?uk=abc#top")
+        );
+    }
+
+    #[test]
+    fn test_scrape_url_without_announce_segment() {
+        assert_eq!(scrape_url("https://tracker.example.org/"), None);
+        assert_eq!(scrape_url("https://announce.example.org/track"), None);
+        assert_eq!(scrape_url("https://tracker.example.org/track?announce"), None);
+        assert_eq!(scrape_url("https://tracker.example.org"), None);
+    }
+
+    #[test]
+    fn test_natural_cmp_orders_numbers_by_value() {
+        let mut names = vec!["ep10", "ep2", "ep1", "Ep3", "ep01x", "ep"];
+        names.sort_by(|left, right| natural_cmp(left, right));
+        assert_eq!(names, ["ep", "ep1", "ep01x", "ep2", "Ep3", "ep10"]);
+    }
+
+    #[test]
+    fn test_natural_cmp_multiple_numbers() {
+        let mut names = vec!["s2e10.mkv", "s10e1.mkv", "s2e9.mkv", "s1e2.mkv"];
+        names.sort_by(|left, right| natural_cmp(left, right));
+        assert_eq!(names, ["s1e2.mkv", "s2e9.mkv", "s2e10.mkv", "s10e1.mkv"]);
+    }
+
+    #[test]
+    fn test_natural_cmp_is_deterministic_for_equal_values() {
+        assert_eq!(natural_cmp("ep1", "ep01"), "ep1".cmp("ep01"));
+        assert_eq!(natural_cmp("EP1", "ep1"), Ordering::Less);
+        assert_eq!(natural_cmp("ep1", "ep1"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_printable_text() {
+        assert_eq!(printable_text(b"mktorrent 1.1"), Some("mktorrent 1.1"));
+        assert_eq!(printable_text("Ääkköset".as_bytes()), Some("Ääkköset"));
+        assert_eq!(printable_text(b""), Some(""));
+        assert_eq!(printable_text(b"\x00\x01"), None);
+        assert_eq!(printable_text(b"\xff\xfe"), None);
+    }
+
+    #[test]
+    fn test_hex_preview() {
+        assert_eq!(hex_preview(b"\x00\x01\x02\xff"), "000102ff [4 Bytes]");
+        assert_eq!(
+            hex_preview(&[0xab; 20]),
+            "abababababababababababababababab... [20 Bytes]"
+        );
+    }
+
+    #[test]
+    fn test_truncate_middle_keeps_short_paths() {
+        assert_eq!(truncate_middle("data/part1.bin", 14), "data/part1.bin");
+        assert_eq!(truncate_middle("data/part1.bin", 80), "data/part1.bin");
+    }
+
+    #[test]
+    fn test_truncate_middle_keeps_file_name() {
+        let path = "Season 01/Extras/Behind the scenes/episode-01.mkv";
+        let truncated = truncate_middle(path, 30);
+        assert_eq!(truncated, "Season 01/Extr…/episode-01.mkv");
+        assert_eq!(truncated.chars().count(), 30);
+    }
+
+    #[test]
+    fn test_truncate_middle_long_file_name() {
+        let truncated = truncate_middle("dir/a-very-long-file-name-without-any-breaks.txt", 12);
+        assert_eq!(truncated, "…-breaks.txt");
+        assert_eq!(truncate_middle("abc", 0), "");
+        assert_eq!(truncate_middle("abc", 1), "…");
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(parse_selection("1", 3), Some(0));
+        assert_eq!(parse_selection(" 3\n", 3), Some(2));
+        assert_eq!(parse_selection("0", 3), None);
+        assert_eq!(parse_selection("4", 3), None);
+        assert_eq!(parse_selection("two", 3), None);
+        assert_eq!(parse_selection("", 3), None);
+    }
+
+    #[test]
+    fn test_colorize_size_large() {
+        let size = colorize_size(6_345_887_744);
+        assert_eq!(size.input, "6.35 GB");
+        assert_eq!(size.fgcolor, Some(Color::Red));
+    }
+}
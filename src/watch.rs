@@ -0,0 +1,134 @@
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::Context;
+use globset::GlobSet;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use torrentinfo::{TORRENT_EXTENSION, Torrent};
+
+use crate::Args;
+use crate::cli::TorrentInfo;
+use crate::utils;
+
+/// Quiet period after the last filesystem event before pending files are processed
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(500);
+/// Number of parse attempts for a file that may still be written
+const PARSE_ATTEMPTS: usize = 5;
+/// Delay between parse attempts
+const RETRY_DELAY: Duration = Duration::from_millis(300);
+
+/// Watch the input directories and print new torrent files as they appear.
+///
+/// Runs until the watcher fails or the process is interrupted.
+pub fn run(args: Args) -> anyhow::Result<()> {
+    let directories = utils::resolve_input_paths(&args.paths)?;
+    if let Some(path) = directories.iter().find(|path| !path.is_dir()) {
+        anyhow::bail!("Watch path is not a directory: {}", path.display());
+    }
+    let exclude = utils::build_exclude_set(&args.exclude)?;
+    let mode = if args.recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+
+    let (sender, receiver) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(sender).context("Failed to create file watcher")?;
+    for directory in &directories {
+        watcher
+            .watch(directory, mode)
+            .with_context(|| format!("Failed to watch directory: {}", directory.display()))?;
+        log::info!("Watching {} for new torrent files", directory.display());
+    }
+
+    let root = match (&args.relative_to, directories.as_slice()) {
+        (Some(base), _) => utils::resolve_input_path(Some(base))?,
+        (None, [directory]) => directory.clone(),
+        _ => std::env::current_dir().context("Failed to get current working directory")?,
+    };
+    let include_hidden = args.include_hidden;
+    let strict = args.strict;
+    let info = TorrentInfo::watching(args, root);
+    let mut printed: HashSet<PathBuf> = HashSet::new();
+    loop {
+        // Block until something happens, then collect events until the directory is quiet
+        let mut pending = BTreeSet::new();
+        collect_paths(receiver.recv()?, &mut pending, &mut printed);
+        while let Ok(event) = receiver.recv_timeout(DEBOUNCE_DELAY) {
+            collect_paths(event, &mut pending, &mut printed);
+        }
+
+        for path in pending {
+            if printed.contains(&path) || !is_watched_torrent(&path, &directories, &exclude, include_hidden) {
+                continue;
+            }
+            match parse_with_retry(&path, strict) {
+                Ok(torrent) => {
+                    if let Err(error) = info.print_parsed_torrent(&path, &torrent) {
+                        log::error!("{}: {error:#}", path.display());
+                    }
+                    printed.insert(path);
+                }
+                Err(error) => log::error!("{}: {error:#}", path.display()),
+            }
+        }
+    }
+}
+
+/// Add the paths of created, modified or renamed files from a watcher event.
+///
+/// Removed files are forgotten so they are printed again if they reappear.
+fn collect_paths(event: notify::Result<Event>, pending: &mut BTreeSet<PathBuf>, printed: &mut HashSet<PathBuf>) {
+    match event {
+        Ok(event) => match event.kind {
+            EventKind::Create(_) | EventKind::Modify(_) => pending.extend(event.paths),
+            EventKind::Remove(_) => {
+                for path in &event.paths {
+                    pending.remove(path);
+                    printed.remove(path);
+                }
+            }
+            _ => {}
+        },
+        Err(error) => log::warn!("File watcher error: {error}"),
+    }
+}
+
+/// Check if the path is an existing torrent file that should be printed.
+///
+/// The file is hidden if any path component below the watched directory starts with a dot.
+fn is_watched_torrent(path: &Path, directories: &[PathBuf], exclude: &GlobSet, include_hidden: bool) -> bool {
+    let relative = directories
+        .iter()
+        .find_map(|directory| path.strip_prefix(directory).ok())
+        .or_else(|| path.file_name().map(Path::new))
+        .unwrap_or(path);
+    let hidden = relative
+        .components()
+        .any(|component| component.as_os_str().to_string_lossy().starts_with('.'));
+    path.is_file()
+        && path.extension() == Some(TORRENT_EXTENSION.as_ref())
+        && (include_hidden || !hidden)
+        && !exclude.is_match(path)
+}
+
+/// Try to parse the torrent file, retrying after a delay in case it is still being written
+fn parse_with_retry(path: &Path, strict: bool) -> anyhow::Result<Torrent> {
+    let mut attempt = 1;
+    loop {
+        match utils::read_torrent(path, strict) {
+            Ok(torrent) => return Ok(torrent),
+            Err(error) if attempt >= PARSE_ATTEMPTS => {
+                return Err(error).with_context(|| format!("Failed to parse after {PARSE_ATTEMPTS} attempts"));
+            }
+            Err(error) => {
+                log::debug!("{}: parse attempt {attempt} failed: {error:#}", path.display());
+                attempt += 1;
+                std::thread::sleep(RETRY_DELAY);
+            }
+        }
+    }
+}
@@ -0,0 +1,1879 @@
+//! Integration tests for the torrentinfo command line interface
+
+use std::ffi::OsStr;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+/// Directory containing the synthetic test torrents
+const FIXTURES_DIR: &str = "tests/fixtures";
+
+/// Path to the Ubuntu test torrent file
+const UBUNTU_TORRENT_PATH: &str = "tests/ubuntu-24.04.3-desktop-amd64.iso.torrent";
+
+/// Run the torrentinfo binary with the given arguments and colours disabled
+fn run_torrentinfo<I, S>(args: I) -> Output
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    Command::new(env!("CARGO_BIN_EXE_torrentinfo"))
+        .arg("--nocolour")
+        .args(args)
+        .output()
+        .expect("Failed to run torrentinfo")
+}
+
+/// Run the torrentinfo binary with the given arguments and stdin contents, with colours disabled
+fn run_torrentinfo_with_stdin<I, S>(args: I, input: &str) -> Output
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let mut child = Command::new(env!("CARGO_BIN_EXE_torrentinfo"))
+        .arg("--nocolour")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to run torrentinfo");
+    child
+        .stdin
+        .take()
+        .expect("Stdin should be piped")
+        .write_all(input.as_bytes())
+        .expect("Failed to write to stdin");
+    child.wait_with_output().expect("Failed to wait for torrentinfo")
+}
+
+/// Create an empty temporary directory for a single test
+fn fresh_temp_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).expect("Failed to remove temporary directory");
+    }
+    std::fs::create_dir_all(&dir).expect("Failed to create temporary directory");
+    dir
+}
+
+/// Get stdout of a finished command as a string
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_dedupe_files_reports_shared_files() {
+    let output = run_torrentinfo(["--dedupe-files", FIXTURES_DIR]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    assert!(stdout.contains("README.txt"));
+    assert!(stdout.contains("data/part1.bin"));
+    assert!(
+        !stdout.contains("data/part2.bin"),
+        "Unique files should not be reported"
+    );
+    assert!(stdout.contains("multi-file.torrent"));
+    assert!(stdout.contains("multi-file-repack.torrent"));
+    assert!(stdout.contains("in 2 files"));
+}
+
+#[test]
+fn test_find_dupes_by_hash_and_content() {
+    let dir = fresh_temp_dir("find_dupes");
+    std::fs::copy("tests/fixtures/multi-file.torrent", dir.join("original.torrent")).expect("Failed to copy fixture");
+    std::fs::copy("tests/fixtures/multi-file.torrent", dir.join("copy.torrent")).expect("Failed to copy fixture");
+    let mut repieced =
+        torrentinfo::Torrent::from_file("tests/fixtures/multi-file.torrent").expect("Failed to parse fixture");
+    repieced.info_mut().piece_length = 16_384;
+    repieced.info_mut().pieces = vec![0xab; 20 * 34].into();
+    std::fs::write(
+        dir.join("repieced.torrent"),
+        repieced.to_bytes().expect("Failed to encode torrent"),
+    )
+    .expect("Failed to write torrent");
+
+    let by_hash = run_torrentinfo([OsStr::new("--find-dupes"), dir.as_os_str()]);
+    assert!(by_hash.status.success());
+    let by_hash = stdout(&by_hash);
+    assert!(by_hash.contains("82144473bf148e85009ebd7327194d80511654e5\n    copy.torrent\n    original.torrent\n"));
+    assert!(!by_hash.contains("repieced.torrent"));
+    assert!(by_hash.contains("Duplicate groups: 1"));
+
+    let by_content = run_torrentinfo([OsStr::new("--find-dupes=content"), dir.as_os_str()]);
+    assert!(by_content.status.success());
+    let by_content = stdout(&by_content);
+    assert!(by_content.contains("    copy.torrent\n    original.torrent\n    repieced.torrent\n"));
+    assert!(by_content.contains("Duplicate groups: 1"));
+}
+
+#[test]
+fn test_find_dupes_without_duplicates() {
+    let output = run_torrentinfo(["--find-dupes", "tests"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("No duplicate torrents found"));
+}
+
+#[test]
+fn test_details_show_content_fingerprint() {
+    let output = run_torrentinfo(["--details", "tests/fixtures/multi-file.torrent"]);
+    let fingerprint = torrentinfo::Torrent::from_file("tests/fixtures/multi-file.torrent")
+        .expect("Failed to parse fixture")
+        .content_fingerprint();
+    assert!(stdout(&output).contains(&format!("content fingerprint {fingerprint}")));
+}
+
+#[test]
+fn test_dedupe_files_without_duplicates() {
+    let output = run_torrentinfo(["--dedupe-files", "tests"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("No duplicate files found"));
+}
+
+#[test]
+fn test_rename_suggest_prints_torrent_name() {
+    let dir = fresh_temp_dir("rename_suggest");
+    let torrent_path = dir.join("download.torrent");
+    std::fs::copy(UBUNTU_TORRENT_PATH, &torrent_path).expect("Failed to copy fixture");
+
+    let output = run_torrentinfo([OsStr::new("--rename-suggest"), torrent_path.as_os_str()]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("download.torrent -> ubuntu-24.04.3-desktop-amd64.iso.torrent"));
+    assert!(torrent_path.exists(), "Suggest mode should not rename files");
+}
+
+#[test]
+fn test_rename_avoids_collisions() {
+    let dir = fresh_temp_dir("rename");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("first.torrent")).expect("Failed to copy fixture");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("second.torrent")).expect("Failed to copy fixture");
+
+    let output = run_torrentinfo([OsStr::new("--rename"), dir.as_os_str()]);
+    assert!(output.status.success());
+    assert!(!dir.join("first.torrent").exists());
+    assert!(!dir.join("second.torrent").exists());
+    assert!(dir.join("ubuntu-24.04.3-desktop-amd64.iso.torrent").exists());
+    assert!(dir.join("ubuntu-24.04.3-desktop-amd64.iso (1).torrent").exists());
+}
+
+/// Get the order in which the given names appear in the output
+fn output_order<'a>(output: &str, names: &[&'a str]) -> Vec<&'a str> {
+    let mut found: Vec<(usize, &str)> = names
+        .iter()
+        .map(|name| (output.find(name).expect("Name should be in the output"), *name))
+        .collect();
+    found.sort_unstable();
+    found.into_iter().map(|(_, name)| name).collect()
+}
+
+#[test]
+fn test_natural_sort_directory_scan() {
+    let dir = fresh_temp_dir("natural_sort");
+    for name in ["ep1", "ep2", "ep10"] {
+        std::fs::copy(UBUNTU_TORRENT_PATH, dir.join(format!("{name}.torrent"))).expect("Failed to copy fixture");
+    }
+    let names = ["ep1.torrent", "ep2.torrent", "ep10.torrent"];
+
+    let lexicographic = run_torrentinfo([dir.as_os_str()]);
+    assert!(lexicographic.status.success());
+    assert_eq!(
+        output_order(&stdout(&lexicographic), &names),
+        ["ep1.torrent", "ep10.torrent", "ep2.torrent"]
+    );
+
+    let natural = run_torrentinfo([OsStr::new("--natural-sort"), dir.as_os_str()]);
+    assert!(natural.status.success());
+    assert_eq!(output_order(&stdout(&natural), &names), names);
+}
+
+#[test]
+fn test_no_sort_paths_visits_all_fixtures() {
+    let mut expected: Vec<String> = std::fs::read_dir(FIXTURES_DIR)
+        .expect("Failed to read fixtures")
+        .map(|entry| entry.expect("Failed to read fixture entry").file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".torrent"))
+        .collect();
+    expected.sort();
+
+    let output = run_torrentinfo(["--no-sort-paths", FIXTURES_DIR]);
+    assert!(output.status.success());
+    let mut headers: Vec<String> = stdout(&output)
+        .lines()
+        .filter(|line| !line.starts_with(' '))
+        .map(str::to_string)
+        .collect();
+    headers.sort();
+    assert_eq!(headers, expected);
+
+    let output = run_torrentinfo(["--no-sort-paths", "--sort", FIXTURES_DIR]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_no_sort_paths_with_filters_and_modes() {
+    let output = run_torrentinfo(["--no-sort-paths", "--only-trackerless", "--summary-line", FIXTURES_DIR]);
+    assert!(output.status.success());
+    let lines: Vec<String> = stdout(&output).lines().map(str::to_string).collect();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].starts_with("trackerless.bin"));
+
+    let streamed = run_torrentinfo([
+        "--no-sort-paths",
+        "--json",
+        "--json-compact",
+        "--only-tracked",
+        FIXTURES_DIR,
+    ]);
+    let sorted = run_torrentinfo(["--json", "--json-compact", "--only-tracked", FIXTURES_DIR]);
+    assert!(streamed.status.success());
+    let mut streamed_lines: Vec<String> = stdout(&streamed).lines().map(str::to_string).collect();
+    let mut sorted_lines: Vec<String> = stdout(&sorted).lines().map(str::to_string).collect();
+    streamed_lines.sort();
+    sorted_lines.sort();
+    assert_eq!(streamed_lines, sorted_lines);
+    assert!(!stdout(&streamed).contains("trackerless.bin"));
+
+    let stats = run_torrentinfo(["--no-sort-paths", "--stats", "--contains-ext", "mkv", FIXTURES_DIR]);
+    assert!(stats.status.success());
+    assert!(stdout(&stats).contains("torrents            3\n"));
+}
+
+#[test]
+fn test_no_sort_paths_skips_overlapping_inputs() {
+    let output = run_torrentinfo([
+        "--no-sort-paths",
+        FIXTURES_DIR,
+        "tests/fixtures/multi-file.torrent",
+        UBUNTU_TORRENT_PATH,
+    ]);
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    let headers: Vec<&str> = stdout.lines().filter(|line| !line.starts_with(' ')).collect();
+    assert_eq!(
+        headers
+            .iter()
+            .filter(|line| line.ends_with("multi-file.torrent"))
+            .count(),
+        1
+    );
+    assert!(
+        headers
+            .iter()
+            .any(|line| line.ends_with("ubuntu-24.04.3-desktop-amd64.iso.torrent"))
+    );
+}
+
+#[test]
+fn test_natural_sort_file_listing() {
+    let names = ["ep1.mkv", "ep2.mkv", "ep10.mkv"];
+    let torrent_order = run_torrentinfo(["--files", "tests/fixtures/episodes.torrent"]);
+    assert!(torrent_order.status.success());
+    assert_eq!(
+        output_order(&stdout(&torrent_order), &names),
+        ["ep1.mkv", "ep10.mkv", "ep2.mkv"]
+    );
+
+    let natural = run_torrentinfo(["--files", "--natural-sort", "tests/fixtures/episodes.torrent"]);
+    assert!(natural.status.success());
+    assert_eq!(output_order(&stdout(&natural), &names), names);
+}
+
+/// Parse a formatted size such as `1.00 kB` or `500 bytes` back into bytes
+fn parse_size(number: &str, unit: &str) -> f64 {
+    let value: f64 = number.parse().expect("Size should be a number");
+    match unit {
+        "bytes" => value,
+        "kB" => value * 1e3,
+        "MB" => value * 1e6,
+        "GB" => value * 1e9,
+        _ => panic!("Unexpected size unit: {unit}"),
+    }
+}
+
+#[test]
+fn test_cumulative_file_sizes() {
+    let output = run_torrentinfo(["--files", "--cumulative", "tests/fixtures/multi-file.torrent"]);
+    assert!(output.status.success());
+
+    let rows: Vec<(f64, f64)> = stdout(&output)
+        .lines()
+        .skip_while(|line| line.trim() != "files")
+        .skip(1)
+        .map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            (parse_size(columns[1], columns[2]), parse_size(columns[3], columns[4]))
+        })
+        .collect();
+    assert_eq!(rows.len(), 3);
+
+    let mut total = 0.0;
+    for (size, cumulative) in rows {
+        total += size;
+        assert!((cumulative - total).abs() < f64::EPSILON, "{cumulative} != {total}");
+    }
+}
+
+#[test]
+fn test_nested_paths_use_forward_slashes() {
+    let dir = fresh_temp_dir("forward_slashes");
+    let nested = dir.join("level1").join("level2");
+    std::fs::create_dir_all(&nested).expect("Failed to create nested directories");
+    std::fs::copy(UBUNTU_TORRENT_PATH, nested.join("nested.torrent")).expect("Failed to copy fixture");
+
+    let output = run_torrentinfo([OsStr::new("--recursive"), dir.as_os_str()]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("level1/level2/nested.torrent\n"));
+
+    let native = run_torrentinfo([
+        OsStr::new("--recursive"),
+        OsStr::new("--native-separators"),
+        dir.as_os_str(),
+    ]);
+    let native_path = PathBuf::from("level1").join("level2").join("nested.torrent");
+    assert!(stdout(&native).starts_with(&format!("{}\n", native_path.display())));
+}
+
+#[test]
+fn test_max_depth_limits_directory_scan() {
+    let dir = fresh_temp_dir("max_depth");
+    let nested = dir.join("level1").join("level2");
+    std::fs::create_dir_all(&nested).expect("Failed to create nested directories");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("top.torrent")).expect("Failed to copy fixture");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("level1").join("middle.torrent")).expect("Failed to copy fixture");
+    std::fs::copy(UBUNTU_TORRENT_PATH, nested.join("bottom.torrent")).expect("Failed to copy fixture");
+
+    let scan = |args: &[&str]| {
+        let mut command_args: Vec<&OsStr> = args.iter().map(OsStr::new).collect();
+        command_args.push(dir.as_os_str());
+        let output = run_torrentinfo(command_args);
+        assert!(output.status.success());
+        let stdout = stdout(&output);
+        ["top.torrent", "middle.torrent", "bottom.torrent"]
+            .into_iter()
+            .filter(|name| stdout.contains(name))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(scan(&["--max-depth", "1"]), ["top.torrent"]);
+    assert_eq!(scan(&["--max-depth", "2"]), ["top.torrent", "middle.torrent"]);
+    assert_eq!(
+        scan(&["--max-depth", "3"]),
+        ["top.torrent", "middle.torrent", "bottom.torrent"]
+    );
+    assert_eq!(scan(&["--recursive", "--max-depth", "1"]), ["top.torrent"]);
+    assert_eq!(
+        scan(&["--recursive"]),
+        ["top.torrent", "middle.torrent", "bottom.torrent"]
+    );
+}
+
+#[test]
+fn test_max_depth_rejects_zero() {
+    let output = run_torrentinfo(["--max-depth", "0", FIXTURES_DIR]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_announce_url_encodes_info_hash() {
+    let output = run_torrentinfo(["--announce-url", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    assert!(stdout.contains(
+        "https://torrent.ubuntu.com/announce?info_hash=%D1%60%B8%D8%EA5%A5%B4%E5%287F%8F%C8%F0%3DU%CE%F1%F7&peer_id=-TI"
+    ));
+    assert!(stdout.contains("&port=6881&uploaded=0&downloaded=0&left=6345887744&compact=1"));
+    assert!(stdout.contains("scrape url          https://torrent.ubuntu.com/scrape"));
+}
+
+#[test]
+fn test_summary_line_aligned_columns() {
+    let output = run_torrentinfo(["--summary-line", "tests"]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], "pop-os_24.04_amd64_nvidia_22.iso | 3.60 GB | 1 | d4d16dbb");
+    assert_eq!(lines[1], "ubuntu-24.04.3-desktop-amd64.iso | 6.35 GB | 1 | d160b8d8");
+}
+
+#[test]
+fn test_summary_line_pads_columns() {
+    let output = run_torrentinfo(["--summary-line", "--sort", FIXTURES_DIR]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    let separator_positions: Vec<Option<usize>> = stdout.lines().map(|line| line.find(" | ")).collect();
+    assert!(separator_positions.len() > 1);
+    assert!(separator_positions.windows(2).all(|pair| pair[0] == pair[1]));
+}
+
+#[test]
+fn test_summary_line_hash_matches_info_mode() {
+    let output = run_torrentinfo(["--summary-line", "tests/fixtures/info-source.torrent"]);
+    assert!(stdout(&output).ends_with("| ef9466db\n"));
+
+    let output = run_torrentinfo(["tests/fixtures/info-source.torrent"]);
+    assert!(stdout(&output).contains("info hash           ef9466db92eb4e75a4510afc6d85a20c819fa4ae"));
+}
+
+#[test]
+fn test_edit_set_private_and_clear_trackers() {
+    let dir = fresh_temp_dir("edit_private");
+    let output_path = dir.join("private.torrent");
+
+    let output = run_torrentinfo([
+        OsStr::new("edit"),
+        OsStr::new("--set-private"),
+        OsStr::new("true"),
+        OsStr::new("--clear-trackers"),
+        OsStr::new(UBUNTU_TORRENT_PATH),
+        OsStr::new("-o"),
+        output_path.as_os_str(),
+    ]);
+    assert!(output.status.success());
+
+    let edit_stdout = stdout(&output);
+    assert!(edit_stdout.contains("old info hash       d160b8d8ea35a5b4e52837468fc8f03d55cef1f7"));
+    let new_hash_line = edit_stdout
+        .lines()
+        .find(|line| line.trim_start().starts_with("new info hash"))
+        .expect("Output should contain the new info hash");
+    assert!(!new_hash_line.contains("d160b8d8ea35a5b4e52837468fc8f03d55cef1f7"));
+
+    let edited = run_torrentinfo([OsStr::new("-d"), output_path.as_os_str()]);
+    let edited_stdout = stdout(&edited);
+    assert!(edited_stdout.contains("private             true"));
+    assert!(!edited_stdout.contains("announce url"));
+}
+
+#[test]
+fn test_edit_replace_tracker_in_place() {
+    let dir = fresh_temp_dir("edit_replace_tracker");
+    let ubuntu = dir.join("ubuntu.torrent");
+    let popos = dir.join("popos.torrent");
+    std::fs::copy(UBUNTU_TORRENT_PATH, &ubuntu).unwrap();
+    std::fs::copy("tests/pop-os_24.04_amd64_nvidia_22.iso.torrent", &popos).unwrap();
+
+    let output = run_torrentinfo([
+        OsStr::new("edit"),
+        OsStr::new("--replace-tracker"),
+        OsStr::new("https://torrent.ubuntu.com/announce=https://tracker.example.org/announce"),
+        ubuntu.as_os_str(),
+        popos.as_os_str(),
+    ]);
+    assert!(output.status.success());
+
+    let edited = torrentinfo::Torrent::from_file(&ubuntu).unwrap();
+    assert_eq!(
+        edited.announce().as_deref(),
+        Some("https://tracker.example.org/announce")
+    );
+    assert!(edited.trackers().contains(&"https://ipv6.torrent.ubuntu.com/announce"));
+    assert_eq!(
+        torrentinfo::to_hex(&edited.info_hash().unwrap()),
+        "d160b8d8ea35a5b4e52837468fc8f03d55cef1f7"
+    );
+    let original = torrentinfo::Torrent::from_file("tests/pop-os_24.04_amd64_nvidia_22.iso.torrent").unwrap();
+    let unmatched = torrentinfo::Torrent::from_file(&popos).unwrap();
+    assert_eq!(unmatched.trackers(), original.trackers());
+    assert_eq!(unmatched.info_hash().unwrap(), original.info_hash().unwrap());
+}
+
+#[test]
+fn test_edit_replace_passkey_tracker_keeps_info_hash() {
+    let dir = fresh_temp_dir("edit_replace_passkey");
+    let input_path = dir.join("source.torrent");
+    std::fs::copy("tests/fixtures/info-source.torrent", &input_path).expect("Failed to copy fixture");
+    let original = std::fs::read(&input_path).expect("Failed to read input");
+
+    let output = run_torrentinfo([
+        OsStr::new("edit"),
+        OsStr::new("--replace-tracker"),
+        OsStr::new(
+            "https://tracker.example.org/announce?passkey=0123456789abcdef=https://tracker.example.net/announce?passkey=fedcba9876543210",
+        ),
+        input_path.as_os_str(),
+    ]);
+    assert!(output.status.success());
+
+    let edited = std::fs::read(&input_path).expect("Failed to read edited torrent");
+    let torrent = torrentinfo::Torrent::from_buf(&edited).expect("Failed to parse edited torrent");
+    assert_eq!(
+        torrent.announce().as_deref(),
+        Some("https://tracker.example.net/announce?passkey=fedcba9876543210")
+    );
+    assert_eq!(
+        torrentinfo::Torrent::info_hash_from_bytes(&edited).unwrap(),
+        torrentinfo::Torrent::info_hash_from_bytes(&original).unwrap()
+    );
+}
+
+#[test]
+fn test_edit_without_changes_keeps_input() {
+    let dir = fresh_temp_dir("edit_no_changes_in_place");
+    let input_path = dir.join("source.torrent");
+    std::fs::copy("tests/fixtures/info-source.torrent", &input_path).expect("Failed to copy fixture");
+    let modified = std::fs::metadata(&input_path)
+        .and_then(|metadata| metadata.modified())
+        .unwrap();
+
+    let output = run_torrentinfo([
+        OsStr::new("edit"),
+        OsStr::new("--replace-tracker"),
+        OsStr::new("https://unknown.example.org/announce=https://tracker.example.net/announce"),
+        input_path.as_os_str(),
+    ]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("No changes, nothing written"));
+    assert_eq!(
+        std::fs::metadata(&input_path)
+            .and_then(|metadata| metadata.modified())
+            .unwrap(),
+        modified
+    );
+}
+
+#[test]
+fn test_edit_replace_tracker_substring_dry_run() {
+    let dir = fresh_temp_dir("edit_replace_tracker_dry_run");
+    let ubuntu = dir.join("ubuntu.torrent");
+    std::fs::copy(UBUNTU_TORRENT_PATH, &ubuntu).unwrap();
+
+    let output = run_torrentinfo([
+        OsStr::new("edit"),
+        OsStr::new("--replace-tracker"),
+        OsStr::new("ubuntu.com=example.org"),
+        OsStr::new("--substring"),
+        OsStr::new("--dry-run"),
+        ubuntu.as_os_str(),
+    ]);
+    assert!(output.status.success());
+    let edit_stdout = stdout(&output);
+    assert!(edit_stdout.contains("https://ipv6.torrent.example.org/announce"));
+    assert!(edit_stdout.contains("new info hash       d160b8d8ea35a5b4e52837468fc8f03d55cef1f7"));
+    assert_eq!(
+        std::fs::read(&ubuntu).unwrap(),
+        std::fs::read(UBUNTU_TORRENT_PATH).unwrap()
+    );
+}
+
+#[test]
+fn test_edit_name_renames_content_root() {
+    let dir = fresh_temp_dir("edit_name");
+    let output_path = dir.join("renamed.torrent");
+
+    let output = run_torrentinfo([
+        OsStr::new("edit"),
+        OsStr::new("--name"),
+        OsStr::new("New Name"),
+        OsStr::new("tests/fixtures/multi-file.torrent"),
+        OsStr::new("-o"),
+        output_path.as_os_str(),
+    ]);
+    assert!(output.status.success());
+
+    let original =
+        torrentinfo::Torrent::from_file("tests/fixtures/multi-file.torrent").expect("Failed to parse fixture");
+    let renamed = torrentinfo::Torrent::from_file(&output_path).expect("Failed to parse renamed torrent");
+    assert_eq!(renamed.name().as_deref(), Some("New Name"));
+    let file_paths = |torrent: &torrentinfo::Torrent| {
+        torrent
+            .iter_files()
+            .map(|file| file.path().to_vec())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(file_paths(&renamed), file_paths(&original));
+
+    let old_hash = torrentinfo::to_hex(&original.info_hash().expect("Failed to hash fixture"));
+    let new_hash = torrentinfo::to_hex(&renamed.info_hash().expect("Failed to hash renamed torrent"));
+    assert_ne!(old_hash, new_hash);
+    let edit_stdout = stdout(&output);
+    assert!(edit_stdout.contains(&format!("old info hash       {old_hash}")));
+    assert!(edit_stdout.contains(&new_hash));
+}
+
+#[test]
+fn test_edit_name_replaces_utf8_name() {
+    let dir = fresh_temp_dir("edit_name_utf8");
+    let output_path = dir.join("renamed.torrent");
+
+    let output = run_torrentinfo([
+        OsStr::new("edit"),
+        OsStr::new("--name"),
+        OsStr::new("new.bin"),
+        OsStr::new("tests/fixtures/name-utf8.torrent"),
+        OsStr::new("-o"),
+        output_path.as_os_str(),
+    ]);
+    assert!(output.status.success());
+
+    let edited = std::fs::read(&output_path).expect("Failed to read edited torrent");
+    let contains = |needle: &[u8]| edited.windows(needle.len()).any(|window| window == needle);
+    assert!(contains(b"4:name7:new.bin10:name.utf-87:new.bin"));
+    assert!(!contains(b"old-name.bin"));
+}
+
+#[test]
+fn test_edit_keeps_unmodeled_info_keys() {
+    let dir = fresh_temp_dir("edit_unmodeled_keys");
+    let output_path = dir.join("edited.torrent");
+    let output = run_torrentinfo([
+        OsStr::new("edit"),
+        OsStr::new("--clear-trackers"),
+        OsStr::new("tests/fixtures/info-source.torrent"),
+        OsStr::new("-o"),
+        output_path.as_os_str(),
+    ]);
+    assert!(output.status.success());
+
+    let edited = std::fs::read(&output_path).expect("Failed to read edited torrent");
+    assert!(
+        edited
+            .windows(b"6:source7:EXAMPLE".len())
+            .any(|window| window == b"6:source7:EXAMPLE")
+    );
+    let original = std::fs::read("tests/fixtures/info-source.torrent").expect("Failed to read fixture");
+    let old_hash = torrentinfo::to_hex(&torrentinfo::Torrent::info_hash_from_bytes(&original).unwrap());
+    assert_eq!(
+        torrentinfo::to_hex(&torrentinfo::Torrent::info_hash_from_bytes(&edited).unwrap()),
+        old_hash
+    );
+    assert!(stdout(&output).contains(&format!("new info hash       {old_hash}")));
+}
+
+#[test]
+fn test_missing_info_error_message() {
+    let dir = fresh_temp_dir("missing_info");
+    let torrent_path = dir.join("missing-info.torrent");
+    std::fs::copy("tests/fixtures/missing-info.bencode", &torrent_path).expect("Failed to copy fixture");
+
+    let output = run_torrentinfo([torrent_path.as_os_str()]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Missing required field: info"));
+}
+
+#[test]
+fn test_strict_rejects_mangled_pieces() {
+    let path = "tests/fixtures/mangled-pieces.torrent";
+    let output = run_torrentinfo([path]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("ERROR"));
+    assert!(stdout(&output).contains("total size"));
+
+    let output = run_torrentinfo(["--strict", path]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid pieces length: 30 bytes"));
+    assert!(!stdout(&output).contains("total size"));
+}
+
+#[test]
+fn test_pick_selects_torrent_from_stdin() {
+    let output = run_torrentinfo_with_stdin(
+        [
+            "--pick",
+            "tests/fixtures/attributes.torrent",
+            "tests/fixtures/episodes.torrent",
+        ],
+        "2\n",
+    );
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    assert!(stdout.starts_with("tests/fixtures/episodes.torrent\n"));
+    assert!(stdout.contains("name                episodes"));
+    assert!(stdout.contains("ep10.mkv"));
+    assert!(!stdout.contains("attributes"));
+}
+
+#[test]
+fn test_pick_without_selection_prints_all() {
+    let output = run_torrentinfo_with_stdin(["--pick", FIXTURES_DIR], "");
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    assert!(stdout.contains("attributes.torrent"));
+    assert!(stdout.contains("episodes.torrent"));
+}
+
+#[test]
+fn test_pick_invalid_selection() {
+    let output = run_torrentinfo_with_stdin(["--pick", FIXTURES_DIR], "999\n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid selection: 999"));
+}
+
+#[test]
+fn test_invalid_torrent_error_includes_cause() {
+    let dir = fresh_temp_dir("invalid_torrent_error");
+    std::fs::write(dir.join("broken.torrent"), b"not bencode").unwrap();
+
+    let output = run_torrentinfo([dir.as_os_str()]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr
+        .lines()
+        .find_map(|line| line.split_once("Failed to decode bencode: "))
+        .map(|(_, cause)| cause)
+        .expect("Error should be logged");
+    assert!(!message.is_empty());
+}
+
+#[test]
+fn test_stats_error_includes_cause() {
+    let dir = fresh_temp_dir("stats_error_cause");
+    std::fs::write(dir.join("broken.torrent"), b"garbage").unwrap();
+
+    let output = run_torrentinfo([OsStr::new("--stats"), dir.as_os_str()]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("broken.torrent: Failed to decode bencode: Invalid character `g`"));
+}
+
+#[test]
+fn test_full_paths_prefixes_torrent_name() {
+    let output = run_torrentinfo(["--files", "--full-paths", "tests/fixtures/multi-file.torrent"]);
+    assert!(output.status.success());
+    let listing = stdout(&output);
+    assert!(listing.contains("    multi-file/README.txt\n"));
+    assert!(listing.contains("    multi-file/data/part1.bin\n"));
+
+    let output = run_torrentinfo(["--files", "tests/fixtures/multi-file.torrent"]);
+    assert!(!stdout(&output).contains("multi-file/README.txt"));
+}
+
+#[test]
+fn test_full_paths_single_file_unchanged() {
+    let output = run_torrentinfo(["--files", "--full-paths", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("files               ubuntu-24.04.3-desktop-amd64.iso\n"));
+}
+
+/// Create a file of the given length, creating parent directories as needed
+fn create_sized_file(path: &std::path::Path, length: u64) {
+    std::fs::create_dir_all(path.parent().expect("Path should have a parent")).expect("Failed to create directory");
+    std::fs::File::create(path)
+        .and_then(|file| file.set_len(length))
+        .expect("Failed to create file");
+}
+
+#[test]
+fn test_verify_layout_matching_directory() {
+    let dir = fresh_temp_dir("verify_layout_matching");
+    create_sized_file(&dir.join("multi-file/README.txt"), 1000);
+    create_sized_file(&dir.join("multi-file/data/part1.bin"), 300_000);
+    create_sized_file(&dir.join("multi-file/data/part2.bin"), 250_000);
+
+    let output = run_torrentinfo([
+        OsStr::new("verify-layout"),
+        OsStr::new("tests/fixtures/multi-file.torrent"),
+        dir.as_os_str(),
+    ]);
+    assert!(output.status.success());
+    let layout = stdout(&output);
+    assert!(layout.contains("OK"));
+    assert!(layout.contains("multi-file/data/part1.bin"));
+    assert!(layout.contains("All files match the torrent"));
+}
+
+#[test]
+fn test_verify_layout_mismatching_directory() {
+    let dir = fresh_temp_dir("verify_layout_mismatching");
+    create_sized_file(&dir.join("multi-file/README.txt"), 1000);
+    create_sized_file(&dir.join("multi-file/data/part1.bin"), 299_999);
+
+    let output = run_torrentinfo([
+        OsStr::new("verify-layout"),
+        OsStr::new("tests/fixtures/multi-file.torrent"),
+        dir.as_os_str(),
+    ]);
+    assert!(!output.status.success());
+    let layout = stdout(&output);
+    let lines: Vec<&str> = layout.lines().collect();
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.starts_with("OK") && line.ends_with("multi-file/README.txt"))
+    );
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.starts_with("wrong size") && line.ends_with("299999  multi-file/data/part1.bin"))
+    );
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.starts_with("missing") && line.ends_with("multi-file/data/part2.bin"))
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("2 of 3 files do not match the torrent"));
+}
+
+#[test]
+fn test_verify_layout_single_file() {
+    let dir = fresh_temp_dir("verify_layout_single_file");
+    create_sized_file(&dir.join("httpseeds-string.bin"), 10_000);
+
+    let output = run_torrentinfo([
+        OsStr::new("compare-dir"),
+        OsStr::new("tests/fixtures/httpseeds-string.torrent"),
+        dir.as_os_str(),
+    ]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("All files match the torrent"));
+}
+
+#[test]
+fn test_no_hash_skips_info_hash() {
+    let output = run_torrentinfo(["--no-hash", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    assert!(!stdout.contains("info hash"));
+    assert!(stdout.contains("total size          6.35 GB"));
+}
+
+#[test]
+fn test_no_size_skips_total_size() {
+    let output = run_torrentinfo(["--no-size", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+    let info = stdout(&output);
+    assert!(!info.contains("total size"));
+    assert!(info.contains("info hash           d160b8d8ea35a5b4e52837468fc8f03d55cef1f7"));
+
+    let output = run_torrentinfo(["--sort", "--no-size", FIXTURES_DIR]);
+    assert!(output.status.success());
+    assert!(!stdout(&output).contains("Total size:"));
+}
+
+#[test]
+fn test_base64_argument() {
+    let bytes = std::fs::read("tests/fixtures/multi-file.torrent").unwrap();
+    let output = run_torrentinfo(["--files", "--base64", &STANDARD.encode(bytes)]);
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    assert!(stdout.contains("name                multi-file"));
+    assert!(stdout.contains("num files           3"));
+    assert!(stdout.contains("data/part1.bin"));
+}
+
+#[test]
+fn test_base64_from_stdin() {
+    let bytes = std::fs::read(UBUNTU_TORRENT_PATH).unwrap();
+    let encoded = STANDARD.encode(bytes);
+    // Pasted input is often wrapped over multiple lines
+    let wrapped: Vec<&str> = encoded
+        .as_bytes()
+        .chunks(76)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect();
+    let output = run_torrentinfo_with_stdin(["--base64", "-"], &wrapped.join("\n"));
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    assert!(stdout.contains("name                ubuntu-24.04.3-desktop-amd64.iso"));
+    assert!(stdout.contains("info hash           d160b8d8ea35a5b4e52837468fc8f03d55cef1f7"));
+}
+
+#[test]
+fn test_base64_invalid_input() {
+    let output = run_torrentinfo(["--base64", "not base64!"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid base64 input"));
+
+    let output = run_torrentinfo(["--base64", &STANDARD.encode("hello")]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Base64 input is not a valid torrent"));
+}
+
+#[test]
+fn test_html_report_escapes_user_strings() {
+    let dir = fresh_temp_dir("html_report");
+    let report_path = dir.join("report.html");
+    let output = run_torrentinfo([
+        OsStr::new("--html"),
+        report_path.as_os_str(),
+        OsStr::new("tests/fixtures/html-escaping.torrent"),
+    ]);
+    assert!(output.status.success());
+
+    let page = std::fs::read_to_string(&report_path).expect("Failed to read HTML report");
+    assert!(page.starts_with("<!DOCTYPE html>"));
+    assert!(page.contains("&lt;script&gt;alert(1)&lt;/script&gt; &amp; friends"));
+    assert!(page.contains("&lt;img src=x onerror=alert(1)&gt;.txt"));
+    assert!(page.contains("Tom &amp; Jerry&#39;s &quot;&lt;b&gt;bold&lt;/b&gt;&quot; comment"));
+    assert!(page.contains("92b9ee05a4d97b3bd922f97737ddcda85f489160"));
+    assert!(!page.contains("<script>alert"));
+    assert!(!page.contains("<img"));
+    assert!(!page.contains("src=\"http"));
+}
+
+#[test]
+fn test_html_report_to_stdout_has_section_per_torrent() {
+    let output = run_torrentinfo([
+        "--html",
+        "-",
+        UBUNTU_TORRENT_PATH,
+        "tests/pop-os_24.04_amd64_nvidia_22.iso.torrent",
+    ]);
+    assert!(output.status.success());
+
+    let page = stdout(&output);
+    assert_eq!(page.matches("<section>").count(), 2);
+    assert!(page.contains("<h2>ubuntu-24.04.3-desktop-amd64.iso</h2>"));
+    assert!(page.contains("d160b8d8ea35a5b4e52837468fc8f03d55cef1f7"));
+    assert!(page.contains("data-sort=\"6345887744\""));
+}
+
+#[test]
+fn test_everything_shows_hex_preview_for_binary_values() {
+    let output = run_torrentinfo(["--everything", "tests/fixtures/binary-value.torrent"]);
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    assert!(stdout.contains("000102ff [4 Bytes]"));
+    assert!(stdout.contains("e0e1e2e3e4e5e6e7e8e9eaebecedeeef... [24 Bytes]"));
+    assert!(stdout.contains("torrentinfo test fixtures"));
+    assert!(!stdout.contains("invalid utf-8"));
+}
+
+#[test]
+fn test_everything_output_is_stable_and_sorted() {
+    let first = run_torrentinfo(["--everything", "tests/fixtures/multi-file.torrent"]);
+    let second = run_torrentinfo(["--everything", "tests/fixtures/multi-file.torrent"]);
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+
+    let dump = stdout(&first);
+    let top_level_keys: Vec<&str> = dump
+        .lines()
+        .filter(|line| line.starts_with("    ") && !line.starts_with("     "))
+        .map(str::trim)
+        .collect();
+    assert_eq!(
+        top_level_keys,
+        ["announce", "comment", "created by", "creation date", "info"]
+    );
+}
+
+#[test]
+fn test_inline_bytes_threshold() {
+    let output = run_torrentinfo([
+        "--everything",
+        "--inline-bytes",
+        "2",
+        "tests/fixtures/binary-value.torrent",
+    ]);
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    assert!(stdout.contains("[4 Bytes]"));
+    assert!(!stdout.contains("000102ff"));
+    assert!(stdout.contains("[25 Bytes]"));
+    assert!(!stdout.contains("torrentinfo test fixtures"));
+}
+
+#[test]
+fn test_edit_dry_run_shows_diff_without_writing() {
+    let dir = fresh_temp_dir("edit_dry_run");
+    let input_path = dir.join("input.torrent");
+    std::fs::copy(UBUNTU_TORRENT_PATH, &input_path).expect("Failed to copy fixture");
+    let original_bytes = std::fs::read(&input_path).expect("Failed to read input");
+
+    let output = run_torrentinfo([
+        OsStr::new("edit"),
+        OsStr::new("--dry-run"),
+        OsStr::new("--name"),
+        OsStr::new("renamed.iso"),
+        OsStr::new("--clear-trackers"),
+        input_path.as_os_str(),
+    ]);
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read(&input_path).expect("Failed to read input"),
+        original_bytes
+    );
+    assert_eq!(std::fs::read_dir(&dir).expect("Failed to list directory").count(), 1);
+
+    let edit_stdout = stdout(&output);
+    assert!(edit_stdout.contains("name                ubuntu-24.04.3-desktop-amd64.iso -> renamed.iso"));
+    assert!(edit_stdout.contains(
+        "trackers            https://torrent.ubuntu.com/announce, https://ipv6.torrent.ubuntu.com/announce -> none"
+    ));
+    assert!(!edit_stdout.contains("private "));
+    assert!(edit_stdout.contains("old info hash       d160b8d8ea35a5b4e52837468fc8f03d55cef1f7"));
+    assert!(edit_stdout.contains("Dry run, no changes written"));
+}
+
+#[test]
+fn test_edit_without_changes() {
+    let dir = fresh_temp_dir("edit_no_changes");
+    let output_path = dir.join("copy.torrent");
+    let output = run_torrentinfo([
+        OsStr::new("edit"),
+        OsStr::new(UBUNTU_TORRENT_PATH),
+        OsStr::new("-o"),
+        output_path.as_os_str(),
+    ]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("changes             none"));
+    assert!(output_path.exists());
+}
+
+#[test]
+fn test_multiple_path_arguments() {
+    let output = run_torrentinfo([
+        "--summary-line",
+        UBUNTU_TORRENT_PATH,
+        "tests/fixtures/multi-file.torrent",
+        UBUNTU_TORRENT_PATH,
+    ]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "Duplicate paths should only be processed once");
+    assert!(lines[0].starts_with("ubuntu-24.04.3-desktop-amd64.iso"));
+    assert!(lines[1].starts_with("multi-file"));
+}
+
+#[test]
+fn test_multiple_paths_show_relative_headers() {
+    let output = run_torrentinfo([UBUNTU_TORRENT_PATH, FIXTURES_DIR]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    assert!(stdout.contains(": tests/ubuntu-24.04.3-desktop-amd64.iso.torrent"));
+    assert!(stdout.contains(": tests/fixtures/multi-file.torrent"));
+}
+
+#[test]
+fn test_files_show_v2_pieces_root() {
+    let output = run_torrentinfo(["--files", "tests/fixtures/v2-only.torrent"]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    assert!(stdout.contains("docs/guide.txt    83ca68be6227af2feb15f227485ed18aff8ecae99416a4bd6df3be1b5e8059b4"));
+    assert!(stdout.contains("empty.dat    -"));
+}
+
+#[test]
+fn test_json_output() {
+    let output = run_torrentinfo(["--json", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Output should be valid JSON");
+    assert_eq!(json["schema_version"], 1);
+    assert_eq!(json["name"], "ubuntu-24.04.3-desktop-amd64.iso");
+    assert_eq!(json["info_hash"], "d160b8d8ea35a5b4e52837468fc8f03d55cef1f7");
+}
+
+#[test]
+fn test_hash_only_v1_and_v2_columns() {
+    let output = run_torrentinfo([
+        "--hash-only",
+        "v1,v2",
+        UBUNTU_TORRENT_PATH,
+        "tests/fixtures/hybrid.torrent",
+        "tests/fixtures/v2-only.torrent",
+    ]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    let lines: Vec<Vec<&str>> = stdout.lines().map(|line| line.split('\t').collect()).collect();
+    assert_eq!(lines[0], ["v1", "v2", "file"]);
+    assert_eq!(
+        lines[1],
+        [
+            "d160b8d8ea35a5b4e52837468fc8f03d55cef1f7",
+            "-",
+            "tests/ubuntu-24.04.3-desktop-amd64.iso.torrent"
+        ]
+    );
+
+    let hybrid = torrentinfo::Torrent::from_file("tests/fixtures/hybrid.torrent").expect("Failed to parse fixture");
+    let hybrid_v1 = torrentinfo::to_hex(&hybrid.info_hash().expect("Failed to hash fixture"));
+    let hybrid_v2 = torrentinfo::to_hex(&hybrid.info_hash_v2().expect("Failed to hash fixture"));
+    assert_eq!(lines[2][..2], [hybrid_v1.as_str(), hybrid_v2.as_str()]);
+    assert_eq!(hybrid_v2.len(), 64);
+
+    assert_eq!(lines[3][0], "-");
+    assert_eq!(lines[3][1].len(), 64);
+    assert_eq!(lines.len(), 4);
+}
+
+#[test]
+fn test_hash_only_single_version() {
+    let output = run_torrentinfo(["--hash-only", "v2", "tests/fixtures/hybrid.torrent"]);
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "v2\tfile");
+    assert_eq!(lines[1].split('\t').count(), 2);
+}
+
+#[test]
+fn test_columns_selection_and_order() {
+    let output = run_torrentinfo(["--columns", "hash,name,files", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[0],
+        "INFO HASH                                 NAME                              FILES"
+    );
+    assert_eq!(
+        lines[1],
+        "d160b8d8ea35a5b4e52837468fc8f03d55cef1f7  ubuntu-24.04.3-desktop-amd64.iso      1"
+    );
+}
+
+#[test]
+fn test_json_compact_by_default_when_piped() {
+    let output = run_torrentinfo(["--json", UBUNTU_TORRENT_PATH, "tests/fixtures/multi-file.torrent"]);
+    assert!(output.status.success());
+    let json_lines = stdout(&output);
+    let lines: Vec<&str> = json_lines.lines().collect();
+    assert_eq!(lines.len(), 2);
+    for line in lines {
+        let json: serde_json::Value = serde_json::from_str(line).expect("Each line should be a JSON object");
+        assert!(json.is_object());
+    }
+
+    let compact = run_torrentinfo(["--json", "--json-compact", UBUNTU_TORRENT_PATH]);
+    assert_eq!(stdout(&compact).lines().count(), 1);
+}
+
+#[test]
+fn test_json_pretty() {
+    let output = run_torrentinfo(["--json", "--json-pretty", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+    assert!(stdout(&output).lines().count() > 1);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Output should be valid JSON");
+    assert_eq!(json["name"], "ubuntu-24.04.3-desktop-amd64.iso");
+
+    let conflict = run_torrentinfo(["--json", "--json-pretty", "--json-compact", UBUNTU_TORRENT_PATH]);
+    assert!(!conflict.status.success());
+}
+
+#[test]
+fn test_columns_invalid_name() {
+    let output = run_torrentinfo(["--columns", "name,bogus", UBUNTU_TORRENT_PATH]);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("bogus"));
+    assert!(stderr.contains("possible values: name, size, files, hash, date, trackers"));
+}
+
+#[cfg(not(feature = "network"))]
+#[test]
+fn test_url_requires_network_feature() {
+    let output = run_torrentinfo(["https://example.com/example.torrent"]);
+    assert!(stdout(&output).contains("https://example.com/example.torrent"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("requires the `network` feature"));
+}
+
+#[test]
+fn test_details_show_category() {
+    let output = run_torrentinfo(["--details", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("category            software/iso"));
+}
+
+#[test]
+fn test_dump_pieces_hex_line_count() {
+    let output = run_torrentinfo(["--dump-pieces-hex", "-", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 24208);
+    assert!(lines.iter().all(|line| line.len() == 40));
+}
+
+#[test]
+fn test_dump_pieces_raw_to_file() {
+    let dir = fresh_temp_dir("dump_pieces");
+    let output_path = dir.join("pieces.bin");
+
+    let output = run_torrentinfo([
+        OsStr::new("--dump-pieces"),
+        output_path.as_os_str(),
+        OsStr::new(UBUNTU_TORRENT_PATH),
+    ]);
+    assert!(output.status.success());
+
+    let pieces = std::fs::read(&output_path).expect("Failed to read dumped pieces");
+    assert_eq!(pieces.len(), 24208 * 20);
+}
+
+#[test]
+fn test_dump_pieces_requires_single_torrent() {
+    let output = run_torrentinfo(["--dump-pieces-hex", "-", "tests"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("requires a single torrent file"));
+}
+
+#[test]
+fn test_files_list_zero_length_file() {
+    let output = run_torrentinfo(["--files", "tests/fixtures/zero-length.torrent"]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    let marker_line = stdout
+        .lines()
+        .find(|line| line.contains("content/marker.empty"))
+        .expect("Zero-length file should be listed");
+    assert!(marker_line.contains("0 bytes"));
+}
+
+#[test]
+fn test_only_trackerless_filter() {
+    let output = run_torrentinfo(["--summary-line", "--only-trackerless", "tests", FIXTURES_DIR]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].starts_with("trackerless.bin"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("torrents with trackers"));
+}
+
+#[test]
+fn test_only_tracked_filter() {
+    let output = run_torrentinfo(["--summary-line", "--only-tracked", FIXTURES_DIR]);
+    assert!(output.status.success());
+
+    assert!(!stdout(&output).contains("trackerless.bin"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Skipped 1 torrents without trackers"));
+}
+
+#[test]
+fn test_contains_ext_filter() {
+    let output = run_torrentinfo(["--summary-line", "--contains-ext", "srt", FIXTURES_DIR]);
+    assert!(output.status.success());
+    let summary = stdout(&output);
+    assert_eq!(summary.lines().count(), 1);
+    assert!(summary.contains("mixed-extensions"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("without files matching: srt"));
+}
+
+#[test]
+fn test_contains_ext_multiple_values_match_any() {
+    let output = run_torrentinfo([
+        "--summary-line",
+        "--contains-ext",
+        "nfo",
+        "--contains-ext",
+        ".iso",
+        UBUNTU_TORRENT_PATH,
+        "tests/fixtures/mixed-extensions.torrent",
+        "tests/fixtures/multi-file.torrent",
+    ]);
+    assert!(output.status.success());
+    let summary = stdout(&output);
+    assert_eq!(summary.lines().count(), 2);
+    assert!(summary.contains("ubuntu-24.04.3-desktop-amd64.iso"));
+    assert!(summary.contains("mixed-extensions"));
+}
+
+#[test]
+fn test_details_show_dht_allowed() {
+    let private = run_torrentinfo(["--details", "tests/fixtures/private-string.torrent"]);
+    assert!(stdout(&private).contains("dht allowed         false"));
+
+    let public = run_torrentinfo(["--details", "tests/fixtures/trackerless.torrent"]);
+    assert!(stdout(&public).contains("dht allowed         true"));
+    assert!(stdout(&public).contains("trackerless capable true"));
+}
+
+#[test]
+fn test_verbose_warns_about_single_tracker() {
+    let output = run_torrentinfo(["--verbose", "tests/fixtures/attributes.torrent"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("tracker advisory    single tracker and no DHT nodes"));
+
+    let backup = run_torrentinfo(["--verbose", UBUNTU_TORRENT_PATH]);
+    assert!(!stdout(&backup).contains("tracker advisory"));
+
+    let quiet = run_torrentinfo(["tests/fixtures/attributes.torrent"]);
+    assert!(!stdout(&quiet).contains("tracker advisory"));
+}
+
+#[test]
+fn test_flag_suspicious_lists_only_flagged_torrents() {
+    let output = run_torrentinfo(["--flag-suspicious", "tests", FIXTURES_DIR]);
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.contains(
+        "suspicious.torrent: tiny content for a media name, executable in a media torrent, \
+         executable with a double extension, spam marker in metadata"
+    ));
+}
+
+#[test]
+fn test_details_show_largest_file() {
+    let output = run_torrentinfo(["--details", "tests/fixtures/multi-file.torrent"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("largest file        data/part1.bin (300.00 kB)"));
+
+    let single = run_torrentinfo(["--details", UBUNTU_TORRENT_PATH]);
+    assert!(!stdout(&single).contains("largest file"));
+}
+
+#[test]
+fn test_magnet_contains_info_hash_and_trackers() {
+    let output = run_torrentinfo(["--magnet", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    let magnet = stdout.trim();
+    assert!(magnet.starts_with("magnet:?xt=urn:btih:d160b8d8ea35a5b4e52837468fc8f03d55cef1f7"));
+    assert!(magnet.contains("&dn=ubuntu-24.04.3-desktop-amd64.iso"));
+    assert!(magnet.contains("&tr=https%3A%2F%2Ftorrent.ubuntu.com%2Fannounce"));
+    assert!(magnet.contains("&tr=https%3A%2F%2Fipv6.torrent.ubuntu.com%2Fannounce"));
+}
+
+#[test]
+fn test_magnet_file_writes_sidecar() {
+    let dir = fresh_temp_dir("magnet_file");
+    let torrent_path = dir.join("ubuntu.torrent");
+    std::fs::copy(UBUNTU_TORRENT_PATH, &torrent_path).expect("Failed to copy fixture");
+
+    let output = run_torrentinfo([OsStr::new("--magnet-file"), dir.as_os_str()]);
+    assert!(output.status.success());
+
+    let magnet = std::fs::read_to_string(dir.join("ubuntu.magnet")).expect("Magnet file should be written");
+    assert!(magnet.starts_with("magnet:?xt=urn:btih:d160b8d8ea35a5b4e52837468fc8f03d55cef1f7"));
+    assert!(magnet.ends_with('\n'));
+    assert!(torrent_path.exists(), "Torrent file should be kept");
+}
+
+#[test]
+fn test_exclude_skips_matching_paths() {
+    let dir = fresh_temp_dir("exclude");
+    for subdir in ["current", "backup", "old"] {
+        std::fs::create_dir_all(dir.join(subdir)).expect("Failed to create directory");
+    }
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("current/ubuntu.torrent")).expect("Failed to copy fixture");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("backup/ubuntu.torrent")).expect("Failed to copy fixture");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("old/ubuntu.torrent")).expect("Failed to copy fixture");
+
+    let output = run_torrentinfo([
+        OsStr::new("--recursive"),
+        OsStr::new("--exclude"),
+        OsStr::new("*/backup/*"),
+        OsStr::new("--exclude"),
+        OsStr::new("*/old/*"),
+        dir.as_os_str(),
+    ]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    assert!(stdout.contains("current/ubuntu.torrent"));
+    assert!(!stdout.contains("backup/ubuntu.torrent"));
+    assert!(!stdout.contains("old/ubuntu.torrent"));
+}
+
+#[test]
+fn test_exclude_invalid_pattern() {
+    let output = run_torrentinfo(["--exclude", "[unclosed", "tests"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid exclude pattern"));
+}
+
+#[test]
+fn test_pieces_bar_with_width() {
+    let output = run_torrentinfo(["--details", "--pieces-bar", "--bar-width", "20", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    let bar_line = stdout
+        .lines()
+        .find(|line| line.trim_start().starts_with("pieces "))
+        .expect("Output should contain the pieces bar");
+    assert_eq!(bar_line.chars().filter(|character| *character == '█').count(), 20);
+    assert!(bar_line.ends_with("24208 pieces, 1211 per block"));
+}
+
+#[test]
+fn test_pieces_bar_requires_details() {
+    let output = run_torrentinfo(["--pieces-bar", UBUNTU_TORRENT_PATH]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_files_details_show_attributes() {
+    let output = run_torrentinfo(["--files", "--details", "tests/fixtures/attributes.torrent"]);
+    assert!(output.status.success());
+
+    let details_stdout = stdout(&output);
+    assert!(details_stdout.contains("bin/run.sh [x]"));
+    assert!(details_stdout.contains("bin/latest [l] -> bin/run.sh"));
+    assert!(details_stdout.contains(".pad/15184 [p]"));
+    assert!(details_stdout.contains("settings.conf [h]"));
+
+    let plain = run_torrentinfo(["--files", "tests/fixtures/attributes.torrent"]);
+    assert!(!stdout(&plain).contains("[x]"));
+}
+
+#[test]
+fn test_include_hidden_directories() {
+    let default = run_torrentinfo(["--summary-line", "--recursive", FIXTURES_DIR]);
+    assert!(default.status.success());
+    assert!(!stdout(&default).contains("hidden-dir.bin"));
+
+    let output = run_torrentinfo(["--summary-line", "--recursive", "--include-hidden", FIXTURES_DIR]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("hidden-dir.bin"));
+}
+
+/// Small fixture set with a size tie and identical creation dates for the sort tests
+const SORT_FIXTURES: [&str; 4] = [
+    "tests/fixtures/multi-file.torrent",
+    "tests/fixtures/trackerless.torrent",
+    "tests/fixtures/attributes.torrent",
+    "tests/fixtures/info-source.torrent",
+];
+
+/// Get the torrent names in the order they are listed with the given sort arguments
+fn sorted_names(sort_args: &[&str], paths: &[&str]) -> Vec<String> {
+    let output = run_torrentinfo(["--columns", "name"].iter().chain(sort_args).chain(paths));
+    assert!(output.status.success());
+    stdout(&output)
+        .lines()
+        .skip(1)
+        .map(|line| line.trim().to_string())
+        .collect()
+}
+
+#[test]
+fn test_sort_by_size_ties_fall_back_to_filename() {
+    let names = sorted_names(&["--sort-by", "size"], &SORT_FIXTURES);
+    assert_eq!(
+        names,
+        ["attributes", "source-tagged.bin", "trackerless.bin", "multi-file"]
+    );
+    assert_eq!(sorted_names(&["--sort"], &SORT_FIXTURES), names);
+}
+
+#[test]
+fn test_sort_by_name() {
+    let names = sorted_names(&["--sort-by", "name"], &SORT_FIXTURES);
+    assert_eq!(
+        names,
+        ["attributes", "multi-file", "source-tagged.bin", "trackerless.bin"]
+    );
+}
+
+#[test]
+fn test_sort_by_files() {
+    let names = sorted_names(&["--sort-by", "files"], &SORT_FIXTURES);
+    assert_eq!(
+        names,
+        ["source-tagged.bin", "trackerless.bin", "multi-file", "attributes"]
+    );
+}
+
+#[test]
+fn test_sort_by_date() {
+    let names = sorted_names(&["--sort-by", "date"], &["tests"]);
+    assert_eq!(
+        names,
+        ["ubuntu-24.04.3-desktop-amd64.iso", "pop-os_24.04_amd64_nvidia_22.iso"]
+    );
+
+    // All fixtures share the same creation date so the filename decides
+    let names = sorted_names(&["--sort-by", "date"], &SORT_FIXTURES);
+    assert_eq!(
+        names,
+        ["attributes", "source-tagged.bin", "multi-file", "trackerless.bin"]
+    );
+}
+
+#[test]
+fn test_sort_reverse() {
+    let names = sorted_names(&["--sort-by", "files", "--reverse"], &SORT_FIXTURES);
+    assert_eq!(
+        names,
+        ["attributes", "multi-file", "trackerless.bin", "source-tagged.bin"]
+    );
+
+    let output = run_torrentinfo(["--sort", "--reverse", "tests"]);
+    assert!(output.status.success());
+    let sorted_stdout = stdout(&output);
+    let ubuntu = sorted_stdout.find("ubuntu-24.04.3").expect("Missing Ubuntu torrent");
+    let pop_os = sorted_stdout.find("pop-os_24.04").expect("Missing Pop!_OS torrent");
+    assert!(ubuntu < pop_os);
+    assert!(sorted_stdout.contains("9.95 GB"));
+}
+
+#[test]
+fn test_log_level_controls_diagnostics() {
+    let output = run_torrentinfo(["--summary-line", "--only-tracked", "--log-level", "warn", FIXTURES_DIR]);
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Skipped"));
+
+    let output = run_torrentinfo(["--summary-line", "--verbose", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Reading file:"));
+    assert!(!stdout(&output).contains("Reading file:"));
+}
+
+#[test]
+fn test_log_level_from_environment() {
+    let output = Command::new(env!("CARGO_BIN_EXE_torrentinfo"))
+        .env("TORRENTINFO_LOG", "debug")
+        .args(["--nocolour", "--summary-line", UBUNTU_TORRENT_PATH])
+        .output()
+        .expect("Failed to run torrentinfo");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Finished in"));
+}
+
+#[cfg(not(feature = "toml"))]
+#[test]
+fn test_toml_requires_toml_feature() {
+    let output = run_torrentinfo(["--toml", UBUNTU_TORRENT_PATH]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("requires the `toml` feature"));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_toml_output() {
+    let output = run_torrentinfo(["--toml", UBUNTU_TORRENT_PATH, "tests/fixtures/multi-file.torrent"]);
+    assert!(output.status.success());
+    let reports = torrentinfo::report::from_toml(&stdout(&output)).expect("Output should be valid TOML");
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports[1].files.len(), 3);
+}
+
+#[cfg(not(feature = "watch"))]
+#[test]
+fn test_watch_requires_watch_feature() {
+    let output = run_torrentinfo(["--watch", FIXTURES_DIR]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("requires the `watch` feature"));
+}
+
+#[test]
+fn test_recommend_piece_length() {
+    let output = run_torrentinfo(["--recommend-piece-length", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+
+    let recommend_stdout = stdout(&output);
+    assert!(recommend_stdout.contains("256 KiB (24208 pieces)"));
+    assert!(recommend_stdout.contains("4 MiB (1513 pieces)"));
+}
+
+#[test]
+fn test_piece_map_lists_both_files_at_boundary() {
+    let output = run_torrentinfo(["--piece-map", "tests/fixtures/multi-file.torrent"]);
+    assert!(output.status.success());
+
+    let map_stdout = stdout(&output);
+    let lines: Vec<&str> = map_stdout.lines().collect();
+    let piece_four = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("4 "))
+        .expect("Piece 4 should be listed");
+    assert!(lines[piece_four].contains("data/part1.bin bytes 261144-299999"));
+    assert!(lines[piece_four + 1].contains("data/part2.bin bytes 0-26679"));
+    assert!(map_stdout.contains("README.txt bytes 0-999"));
+    assert!(!map_stdout.contains("more pieces"));
+}
+
+#[test]
+fn test_piece_map_max_pieces() {
+    let output = run_torrentinfo(["--piece-map", "--max-pieces", "2", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+
+    let map_stdout = stdout(&output);
+    assert!(map_stdout.contains("bytes 262144-524287"));
+    assert!(!map_stdout.contains("bytes 524288-"));
+    assert!(map_stdout.contains("24206 more pieces"));
+}
+
+#[test]
+fn test_disk_usage_partial_directory() {
+    let dir = fresh_temp_dir("disk_usage_partial");
+    let content = dir.join("zero-length").join("content");
+    std::fs::create_dir_all(&content).expect("Failed to create test data directory");
+    std::fs::write(content.join("alpha.bin"), vec![0; 70_000]).expect("Failed to write test data");
+
+    let output = run_torrentinfo([
+        OsStr::new("--disk-usage"),
+        dir.as_os_str(),
+        OsStr::new("tests/fixtures/zero-length.torrent"),
+    ]);
+    assert!(output.status.success());
+
+    let usage_stdout = stdout(&output);
+    assert!(usage_stdout.contains("71.00 kB"));
+    assert!(usage_stdout.contains("70.00 kB"));
+    assert!(usage_stdout.contains("1.00 kB"));
+    assert!(usage_stdout.contains("2/3 complete"));
+    assert!(usage_stdout.contains("98.59%"));
+}
+
+#[test]
+fn test_missing_metadata_complete_torrent() {
+    let output = run_torrentinfo([
+        "--missing-comment",
+        "--missing-creator",
+        "--missing-date",
+        UBUNTU_TORRENT_PATH,
+    ]);
+    assert!(output.status.success());
+    assert!(stdout(&output).is_empty());
+}
+
+#[test]
+fn test_missing_metadata_stripped_torrent() {
+    let output = run_torrentinfo([
+        "--missing-comment",
+        "--missing-creator",
+        "--missing-date",
+        "tests/fixtures/stripped.torrent",
+    ]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output).trim(),
+        "stripped.torrent: comment, created by, creation date"
+    );
+}
+
+#[test]
+fn test_missing_metadata_filters_compose() {
+    let output = run_torrentinfo(["--missing-date", FIXTURES_DIR]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output).trim(), "stripped.torrent: creation date");
+
+    let output = run_torrentinfo(["--missing-comment", FIXTURES_DIR]);
+    let missing_stdout = stdout(&output);
+    assert!(missing_stdout.contains("stripped.torrent: comment\n"));
+    assert!(missing_stdout.contains("trackerless.torrent: comment\n"));
+    assert!(!missing_stdout.contains("multi-file.torrent"));
+}
+
+#[test]
+fn test_relative_to_sets_display_base() {
+    let output = run_torrentinfo(["--relative-to", "tests", "--recursive", FIXTURES_DIR]);
+    assert!(output.status.success());
+    let relative_stdout = stdout(&output);
+    assert!(relative_stdout.contains(": fixtures/attributes.torrent\n"));
+    assert!(!relative_stdout.contains(": attributes.torrent\n"));
+
+    let default = run_torrentinfo(["--recursive", FIXTURES_DIR]);
+    assert!(stdout(&default).contains(": attributes.torrent\n"));
+}
+
+#[test]
+fn test_relative_to_outside_base_uses_filename() {
+    let output = run_torrentinfo(["--relative-to", "src", "tests/fixtures/attributes.torrent"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("attributes.torrent\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_raw_names_shows_invalid_unicode() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = fresh_temp_dir("raw_names");
+    let filename = OsStr::from_bytes(b"bad\xffname.torrent");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join(filename)).expect("Failed to copy torrent");
+
+    let output = run_torrentinfo([dir.as_os_str()]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("badname.torrent\n"));
+
+    let output = run_torrentinfo([OsStr::new("--raw-names"), dir.as_os_str()]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("bad\u{FFFD}name.torrent\n"));
+}
+
+#[test]
+fn test_details_show_tracker_schemes() {
+    let output = run_torrentinfo(["--details", "tests/pop-os_24.04_amd64_nvidia_22.iso.torrent"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("tracker schemes     http: 1, udp: 9"));
+}
+
+#[test]
+fn test_stats_aggregates_torrents() {
+    let output = run_torrentinfo(["--stats", "tests"]);
+    assert!(output.status.success());
+
+    let stats_stdout = stdout(&output);
+    assert!(stats_stdout.contains("torrents            2\n"));
+    assert!(stats_stdout.contains("total size          9.95 GB"));
+    assert!(stats_stdout.contains("tracker schemes     http: 1, https: 2, udp: 9"));
+    assert!(stats_stdout.contains("trackerless         0\n"));
+}
+
+#[test]
+fn test_tracker_report_counts() {
+    let dir = fresh_temp_dir("tracker_report");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("ubuntu-1.torrent")).expect("Failed to copy fixture");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("ubuntu-2.torrent")).expect("Failed to copy fixture");
+    std::fs::copy(
+        "tests/pop-os_24.04_amd64_nvidia_22.iso.torrent",
+        dir.join("pop-os.torrent"),
+    )
+    .expect("Failed to copy fixture");
+
+    let output = run_torrentinfo([OsStr::new("--tracker-report"), dir.as_os_str()]);
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 12);
+    assert_eq!(lines[0], "2    https://ipv6.torrent.ubuntu.com/announce");
+    assert_eq!(lines[1], "2    https://torrent.ubuntu.com/announce");
+    assert!(lines.contains(&"1    udp://fosstorrents.com:6969/announce"));
+    assert!(lines.contains(&"1    http://fosstorrents.com:6969/announce"));
+}
+
+#[test]
+fn test_creator_report_counts() {
+    let dir = fresh_temp_dir("creator_report");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("ubuntu.torrent")).expect("Failed to copy fixture");
+    std::fs::copy(
+        "tests/pop-os_24.04_amd64_nvidia_22.iso.torrent",
+        dir.join("pop-os.torrent"),
+    )
+    .expect("Failed to copy fixture");
+    std::fs::copy("tests/fixtures/multi-file.torrent", dir.join("multi-file.torrent")).expect("Failed to copy fixture");
+    std::fs::copy("tests/fixtures/episodes.torrent", dir.join("episodes.torrent")).expect("Failed to copy fixture");
+    std::fs::copy("tests/fixtures/stripped.torrent", dir.join("stripped.torrent")).expect("Failed to copy fixture");
+
+    let output = run_torrentinfo([OsStr::new("--creator-report"), dir.as_os_str()]);
+    assert!(output.status.success());
+    let report = stdout(&output);
+    let lines: Vec<&str> = report.lines().collect();
+    assert!(lines.contains(&"1    mktorrent 1.1"));
+    assert!(lines.contains(&"1    FOSS Torrents (https://fosstorrents.com/)"));
+    assert_eq!(lines[0], "2    torrentinfo test fixtures");
+    assert!(lines.contains(&"1    (unknown)"));
+    assert_eq!(lines.len(), 4);
+}
+
+#[test]
+fn test_files_listing_prefers_path_utf8() {
+    let output = run_torrentinfo(["--files", "tests/fixtures/path-utf8.torrent"]);
+    assert!(output.status.success());
+
+    let listing = stdout(&output);
+    assert!(listing.contains("Café/menü.txt"));
+    assert!(listing.contains("日本語.txt"));
+    assert!(listing.contains("plain.txt"));
+    assert!(!listing.contains("???.txt"));
+    assert!(!listing.contains("Cafe/menu.txt"));
+}
+
+#[test]
+fn test_checksum_self_grades_torrents() {
+    let dir = fresh_temp_dir("checksum_self");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("a-ubuntu.torrent")).expect("Failed to copy fixture");
+    std::fs::copy("tests/fixtures/multi-file.torrent", dir.join("b-multi-file.torrent"))
+        .expect("Failed to copy fixture");
+    std::fs::copy(
+        "tests/fixtures/bom-prefixed.torrent",
+        dir.join("c-bom-prefixed.torrent"),
+    )
+    .expect("Failed to copy fixture");
+
+    let output = run_torrentinfo([OsStr::new("--checksum-self"), dir.as_os_str()]);
+    assert!(output.status.success());
+    let grades = stdout(&output);
+    assert!(grades.contains("OK          a-ubuntu.torrent"));
+    assert!(grades.contains("OK          b-multi-file.torrent"));
+    assert!(grades.contains("suspicious  c-bom-prefixed.torrent"));
+
+    let data = std::fs::read(UBUNTU_TORRENT_PATH).expect("Failed to read fixture");
+    std::fs::write(dir.join("d-truncated.torrent"), &data[..1000]).expect("Failed to write torrent");
+    let output = run_torrentinfo([OsStr::new("--checksum-self"), dir.as_os_str()]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("corrupt     d-truncated.torrent"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("1 of 4 torrent files are corrupt"));
+
+    let output = run_torrentinfo(["--checksum-self", "tests/fixtures/empty-pieces.torrent"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("corrupt     empty-pieces.torrent"));
+}
+
+#[test]
+fn test_announce_url_from_announce_list() {
+    let output = run_torrentinfo(["tests/fixtures/announce-list-only.torrent"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("announce url        https://tier1.example.org/announce"));
+}
+
+#[test]
+fn test_badges() {
+    let dir = fresh_temp_dir("badges");
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("a-ubuntu.torrent")).expect("Failed to copy fixture");
+    std::fs::copy(
+        "tests/pop-os_24.04_amd64_nvidia_22.iso.torrent",
+        dir.join("b-pop-os.torrent"),
+    )
+    .expect("Failed to copy fixture");
+    std::fs::copy("tests/fixtures/private-string.torrent", dir.join("c-private.torrent"))
+        .expect("Failed to copy fixture");
+    std::fs::write(
+        dir.join("d-dht-only.torrent"),
+        b"d4:infod6:lengthi1e4:name12:dht-only.bin12:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaae5:nodesll9:192.0.2.1i6881eeee",
+    )
+    .expect("Failed to write torrent");
+    std::fs::write(
+        dir.join("e-private-nodes.torrent"),
+        b"d4:infod6:lengthi1e4:name17:private-nodes.bin12:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaa7:privatei1ee5:nodesll9:192.0.2.1i6881eeee",
+    )
+    .expect("Failed to write torrent");
+
+    let output = run_torrentinfo([OsStr::new("--badges"), dir.as_os_str()]);
+    assert!(output.status.success());
+    let listing = stdout(&output);
+    let headers: Vec<&str> = listing
+        .lines()
+        .filter(|line| line.starts_with(char::is_numeric))
+        .collect();
+    assert_eq!(
+        headers,
+        [
+            "1/5: a-ubuntu.torrent",
+            "2/5: b-pop-os.torrent [WS]",
+            "3/5: c-private.torrent [P]",
+            "4/5: d-dht-only.torrent [DHT]",
+            "5/5: e-private-nodes.torrent [P]",
+        ]
+    );
+
+    let output = run_torrentinfo([dir.as_os_str()]);
+    assert!(!stdout(&output).contains("[WS]"));
+}
+
+#[test]
+fn test_info_hexdump_matches_info_bytes() {
+    let output = run_torrentinfo(["--info-hexdump", UBUNTU_TORRENT_PATH]);
+    assert!(output.status.success());
+
+    let info_bytes = torrentinfo::Torrent::from_file(UBUNTU_TORRENT_PATH)
+        .expect("Failed to parse torrent")
+        .info_bytes()
+        .expect("Failed to encode info");
+    let hexdump_stdout = stdout(&output);
+    let dump_lines: Vec<&str> = hexdump_stdout.lines().skip(1).collect();
+    assert_eq!(dump_lines.len(), info_bytes.len().div_ceil(16));
+    let dumped_bytes: usize = dump_lines
+        .iter()
+        .map(|line| {
+            line[10..49]
+                .split_whitespace()
+                .map(|group| group.len() / 2)
+                .sum::<usize>()
+        })
+        .sum();
+    assert_eq!(dumped_bytes, info_bytes.len());
+    assert!(dump_lines[0].starts_with("00000000: 6436 3a6c 656e 6774 6869"));
+}
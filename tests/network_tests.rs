@@ -0,0 +1,111 @@
+//! Integration tests for downloading torrents over HTTP, requires the `network` feature
+
+#![cfg(feature = "network")]
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+use torrentinfo::Torrent;
+use torrentinfo::errors::Error;
+
+/// Path to the Ubuntu test torrent file
+const UBUNTU_TORRENT_PATH: &str = "tests/ubuntu-24.04.3-desktop-amd64.iso.torrent";
+
+/// A canned HTTP response served by the mock server
+struct MockResponse {
+    status: &'static str,
+    headers: Vec<String>,
+    body: Vec<u8>,
+}
+
+/// Serve the given responses in order, one per connection, and return the server URL
+fn mock_server(responses: Vec<MockResponse>) -> (String, JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock server");
+    let url = format!("http://{}", listener.local_addr().expect("Failed to get address"));
+    let handle = std::thread::spawn(move || {
+        for response in responses {
+            let (mut stream, _) = listener.accept().expect("Failed to accept connection");
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request).expect("Failed to read request");
+
+            let mut head = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\n",
+                response.status,
+                response.body.len()
+            );
+            for header in &response.headers {
+                head.push_str(header);
+                head.push_str("\r\n");
+            }
+            head.push_str("Connection: close\r\n\r\n");
+            stream.write_all(head.as_bytes()).expect("Failed to write response");
+            stream.write_all(&response.body).expect("Failed to write body");
+        }
+    });
+    (url, handle)
+}
+
+#[test]
+fn test_from_url() {
+    let body = std::fs::read(UBUNTU_TORRENT_PATH).expect("Failed to read fixture");
+    let (url, handle) = mock_server(vec![MockResponse {
+        status: "200 OK",
+        headers: vec!["Content-Type: application/x-bittorrent".to_string()],
+        body,
+    }]);
+
+    let torrent = Torrent::from_url(&format!("{url}/ubuntu.torrent")).expect("Should download torrent");
+    assert_eq!(torrent.name().as_deref(), Some("ubuntu-24.04.3-desktop-amd64.iso"));
+    handle.join().expect("Mock server panicked");
+}
+
+#[test]
+fn test_from_url_follows_redirect() {
+    let body = std::fs::read(UBUNTU_TORRENT_PATH).expect("Failed to read fixture");
+    let (url, handle) = mock_server(vec![
+        MockResponse {
+            status: "302 Found",
+            headers: vec!["Location: /moved.torrent".to_string()],
+            body: Vec::new(),
+        },
+        MockResponse {
+            status: "200 OK",
+            headers: vec!["Content-Type: application/x-bittorrent".to_string()],
+            body,
+        },
+    ]);
+
+    let torrent = Torrent::from_url(&format!("{url}/original.torrent")).expect("Should follow redirect");
+    assert_eq!(torrent.name().as_deref(), Some("ubuntu-24.04.3-desktop-amd64.iso"));
+    handle.join().expect("Mock server panicked");
+}
+
+#[test]
+fn test_from_url_not_found() {
+    let (url, handle) = mock_server(vec![MockResponse {
+        status: "404 Not Found",
+        headers: Vec::new(),
+        body: b"not found".to_vec(),
+    }]);
+
+    let result = Torrent::from_url(&format!("{url}/missing.torrent"));
+    let error = result.expect_err("404 should be an error");
+    assert!(matches!(error, Error::Http(_)));
+    let source = std::error::Error::source(&error).expect("HTTP error should have a source");
+    assert!(source.to_string().contains("404"));
+    handle.join().expect("Mock server panicked");
+}
+
+#[test]
+fn test_from_url_rejects_html() {
+    let (url, handle) = mock_server(vec![MockResponse {
+        status: "200 OK",
+        headers: vec!["Content-Type: text/html; charset=utf-8".to_string()],
+        body: b"<html>login required</html>".to_vec(),
+    }]);
+
+    let result = Torrent::from_url(&format!("{url}/login.torrent"));
+    assert!(matches!(result, Err(Error::UnexpectedContentType(_))));
+    handle.join().expect("Mock server panicked");
+}
@@ -1,8 +1,11 @@
 //! Integration tests for torrentinfo library
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use torrentinfo::{File, Info, Torrent, to_hex};
+use torrentinfo::category::Category;
+use torrentinfo::health::{self, Grade};
+use torrentinfo::report::{SCHEMA_VERSION, TorrentReport};
+use torrentinfo::{File, FileAttributes, FileMatch, Info, PieceSpan, Torrent, TorrentSummary, Value, to_hex};
 
 /// Path to the Ubuntu test torrent file
 const UBUNTU_TORRENT_PATH: &str = "tests/ubuntu-24.04.3-desktop-amd64.iso.torrent";
@@ -10,6 +13,41 @@ const UBUNTU_TORRENT_PATH: &str = "tests/ubuntu-24.04.3-desktop-amd64.iso.torren
 /// Path to the Pop!_OS test torrent file
 const POPOS_TORRENT_PATH: &str = "tests/pop-os_24.04_amd64_nvidia_22.iso.torrent";
 
+/// Path to a torrent with the private flag stored as a string
+const PRIVATE_STRING_TORRENT_PATH: &str = "tests/fixtures/private-string.torrent";
+
+/// Path to a torrent with the creation date stored as a string
+const STRING_DATE_TORRENT_PATH: &str = "tests/fixtures/string-date.torrent";
+
+/// Path to a multi-file torrent with a file length stored as a string
+const STRING_LENGTH_TORRENT_PATH: &str = "tests/fixtures/string-length.torrent";
+
+/// Path to a v2-only torrent with a BEP 52 file tree
+const V2_TORRENT_PATH: &str = "tests/fixtures/v2-only.torrent";
+
+/// Path to a torrent with string-typed DHT node ports and the `x-nodes` and `dht-nodes` keys
+const DHT_NODES_TORRENT_PATH: &str = "tests/fixtures/dht-nodes.torrent";
+
+/// Path to a torrent with a UTF-8 byte order mark before and a CRLF after the bencode data
+const BOM_PREFIXED_TORRENT_PATH: &str = "tests/fixtures/bom-prefixed.torrent";
+
+/// Path to a multi-file torrent with `path.utf-8` next to the legacy `path` for some files
+const PATH_UTF8_TORRENT_PATH: &str = "tests/fixtures/path-utf8.torrent";
+
+/// Path to a multi-file torrent containing a zero-length marker file
+const ZERO_LENGTH_TORRENT_PATH: &str = "tests/fixtures/zero-length.torrent";
+
+/// Path to a multi-file torrent with BEP 47 file attributes
+const ATTRIBUTES_TORRENT_PATH: &str = "tests/fixtures/attributes.torrent";
+
+/// Path to a multi-file torrent with subtitle, info and image files
+const MIXED_EXTENSIONS_TORRENT_PATH: &str = "tests/fixtures/mixed-extensions.torrent";
+
+/// Path to bencoded torrent metadata without an info dictionary.
+///
+/// Not named `.torrent` so directory scans over the fixtures skip it.
+const MISSING_INFO_PATH: &str = "tests/fixtures/missing-info.bencode";
+
 /// Expected values for the Ubuntu torrent
 mod ubuntu {
     pub const NAME: &str = "ubuntu-24.04.3-desktop-amd64.iso";
@@ -20,6 +58,7 @@ mod ubuntu {
     pub const NUM_FILES: usize = 1;
     pub const TOTAL_SIZE: i64 = 6_345_887_744;
     pub const PIECE_LENGTH: i64 = 262_144;
+    pub const NUM_PIECES: usize = 24_208;
 }
 
 /// Expected values for the Pop!_OS torrent
@@ -32,6 +71,7 @@ mod popos {
     pub const NUM_FILES: usize = 1;
     pub const TOTAL_SIZE: i64 = 3_600_056_320;
     pub const PIECE_LENGTH: i64 = 1_048_576;
+    pub const NUM_PIECES: usize = 3_434;
 }
 
 // Ubuntu torrent tests
@@ -131,6 +171,32 @@ fn test_ubuntu_torrent_announce_list() {
     }
 }
 
+#[test]
+fn test_ubuntu_torrent_tracker_tiers() {
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    assert_eq!(
+        torrent.tracker_tiers(),
+        vec![
+            vec!["https://torrent.ubuntu.com/announce"],
+            vec!["https://ipv6.torrent.ubuntu.com/announce"],
+        ]
+    );
+}
+
+#[test]
+fn test_iso_torrents_category() {
+    for path in [UBUNTU_TORRENT_PATH, POPOS_TORRENT_PATH] {
+        let torrent = Torrent::from_file(path).unwrap();
+        assert_eq!(torrent.guess_category(), Category::Software);
+    }
+}
+
+#[test]
+fn test_v2_torrent_category() {
+    let torrent = Torrent::from_file(V2_TORRENT_PATH).unwrap();
+    assert_eq!(torrent.guess_category(), Category::Video);
+}
+
 #[test]
 fn test_ubuntu_torrent_read_bytes() {
     let bytes = Torrent::read_bytes(Path::new(UBUNTU_TORRENT_PATH));
@@ -205,6 +271,22 @@ fn test_popos_torrent_announce_url() {
     assert_eq!(torrent.announce().as_deref(), Some(popos::ANNOUNCE_URL));
 }
 
+#[test]
+fn test_popos_torrent_tracker_tiers() {
+    let torrent = Torrent::from_file(POPOS_TORRENT_PATH).unwrap();
+    let tiers = torrent.tracker_tiers();
+    assert_eq!(tiers.len(), 9);
+    assert_eq!(
+        tiers[0],
+        vec![
+            "udp://fosstorrents.com:6969/announce",
+            "http://fosstorrents.com:6969/announce"
+        ]
+    );
+    assert!(tiers[1..].iter().all(|tier| tier.len() == 1));
+    assert_eq!(tiers.iter().flatten().count(), torrent.trackers().len());
+}
+
 #[test]
 fn test_popos_torrent_created_by() {
     let torrent = Torrent::from_file(POPOS_TORRENT_PATH).unwrap();
@@ -302,6 +384,535 @@ fn test_popos_torrent_files_accessor() {
     }
 }
 
+// BitTorrent v2 tests
+
+#[test]
+fn test_v2_torrent_file_tree_entries() {
+    let torrent = Torrent::from_file(V2_TORRENT_PATH).expect("Should parse v2 torrent");
+    assert!(torrent.info().is_v2());
+    assert_eq!(*torrent.info().meta_version(), Some(2));
+
+    let entries = torrent.info().file_tree_entries();
+    let paths: Vec<String> = entries.iter().map(|entry| entry.path().join("/")).collect();
+    assert_eq!(paths, vec!["docs/guide.txt", "empty.dat", "video.mkv"]);
+    assert_eq!(entries[0].length(), 1000);
+    assert_eq!(entries[2].length(), 50000);
+}
+
+#[test]
+fn test_v2_torrent_pieces_roots() {
+    let torrent = Torrent::from_file(V2_TORRENT_PATH).expect("Should parse v2 torrent");
+    let entries = torrent.info().file_tree_entries();
+
+    let guide_root = entries[0]
+        .pieces_root()
+        .expect("Non-empty file should have a pieces root");
+    assert_eq!(guide_root.len(), 32);
+    assert_eq!(
+        to_hex(guide_root),
+        "83ca68be6227af2feb15f227485ed18aff8ecae99416a4bd6df3be1b5e8059b4"
+    );
+    assert!(
+        entries[1].pieces_root().is_none(),
+        "Empty file should not have a pieces root"
+    );
+    assert_eq!(
+        to_hex(
+            entries[2]
+                .pieces_root()
+                .expect("Non-empty file should have a pieces root")
+        ),
+        "0cab1c9617404faf2b24e221e189ca5945813e14d3f766345b09ca13bbe28ffc"
+    );
+}
+
+#[test]
+fn test_v2_torrent_size_from_file_tree() {
+    let torrent = Torrent::from_file(V2_TORRENT_PATH).expect("Should parse v2 torrent");
+    assert_eq!(torrent.num_files(), 3);
+    assert_eq!(torrent.total_size(), 51000);
+}
+
+#[test]
+fn test_v2_torrent_roundtrip_preserves_info() {
+    let bytes = Torrent::read_bytes(Path::new(V2_TORRENT_PATH)).unwrap();
+    let torrent = Torrent::from_buf(&bytes).unwrap();
+    let serialized = torrent.to_bytes().expect("Should serialize v2 torrent");
+    assert_eq!(serialized, bytes, "v2 torrent should serialize back to identical bytes");
+}
+
+#[test]
+fn test_v1_torrent_has_no_file_tree() {
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    assert!(!torrent.info().is_v2());
+    assert!(torrent.info().file_tree_entries().is_empty());
+}
+
+// Raw info hash tests
+
+#[test]
+fn test_v2_info_hash() {
+    let torrent = Torrent::from_file(V2_TORRENT_PATH).unwrap();
+    let v1_hash = torrent.info_hash_with::<sha1::Sha1>().unwrap();
+    let v2_hash = torrent.info_hash_with::<sha2::Sha256>().unwrap();
+    assert_eq!(v1_hash.len(), 20);
+    assert_eq!(v2_hash.len(), 32);
+    assert_eq!(v2_hash, torrent.info_hash_v2().unwrap());
+}
+
+#[test]
+fn test_info_hash_from_bytes_matches_fixtures() {
+    for path in [UBUNTU_TORRENT_PATH, POPOS_TORRENT_PATH] {
+        let bytes = Torrent::read_bytes(Path::new(path)).unwrap();
+        let torrent = Torrent::from_buf(&bytes).unwrap();
+        let raw_hash = Torrent::info_hash_from_bytes(&bytes).expect("Should hash raw info dict");
+        assert_eq!(raw_hash, torrent.info_hash().unwrap(), "Hashes should match for {path}");
+    }
+}
+
+#[test]
+fn test_info_hash_from_bytes_includes_unmodeled_keys() {
+    let bytes = Torrent::read_bytes(Path::new("tests/fixtures/info-source.torrent")).unwrap();
+    let raw_hash = to_hex(&Torrent::info_hash_from_bytes(&bytes).expect("Should hash raw info dict"));
+    assert_eq!(raw_hash, "ef9466db92eb4e75a4510afc6d85a20c819fa4ae");
+
+    let mut torrent = Torrent::from_buf(&bytes).unwrap();
+    assert_eq!(to_hex(&torrent.info_hash().unwrap()), raw_hash);
+
+    // Editing the info dictionary re-encodes it from the modeled keys
+    torrent.set_private(false);
+    assert_ne!(to_hex(&torrent.info_hash().unwrap()), raw_hash);
+}
+
+#[test]
+fn test_info_hash_from_bytes_missing_info() {
+    let result = Torrent::info_hash_from_bytes(b"d8:announce3:urle");
+    assert!(matches!(result, Err(torrentinfo::errors::Error::MissingField("info"))));
+    assert!(Torrent::info_hash_from_bytes(b"i42e").is_err());
+}
+
+// Report tests
+
+#[test]
+fn test_report_schema_version() {
+    assert_eq!(SCHEMA_VERSION, 1, "Changing the schema version is a breaking change");
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    let report = TorrentReport::from_torrent(&torrent).expect("Should create report");
+    assert_eq!(report.schema_version, SCHEMA_VERSION);
+}
+
+#[test]
+fn test_report_json_fields() {
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    let report = TorrentReport::from_torrent(&torrent).expect("Should create report");
+    let json = serde_json::to_value(&report).expect("Should serialize report");
+    let object = json.as_object().expect("Report should be a JSON object");
+
+    let expected_fields = [
+        "schema_version",
+        "name",
+        "comment",
+        "created_by",
+        "creation_date",
+        "encoding",
+        "info_hash",
+        "total_size",
+        "num_files",
+        "piece_length",
+        "num_pieces",
+        "private",
+        "trackers",
+        "tracker_tiers",
+        "files",
+    ];
+    for field in expected_fields {
+        assert!(object.contains_key(field), "Report should contain field {field}");
+    }
+    assert_eq!(object.len(), expected_fields.len());
+
+    assert_eq!(json["schema_version"], 1);
+    assert_eq!(json["name"], ubuntu::NAME);
+    assert_eq!(json["info_hash"], ubuntu::INFO_HASH);
+    assert_eq!(json["total_size"], ubuntu::TOTAL_SIZE);
+    assert_eq!(json["files"][0]["path"], ubuntu::NAME);
+    assert_eq!(json["files"][0]["length"], ubuntu::TOTAL_SIZE);
+    assert!(json["files"][0]["md5sum"].is_null());
+}
+
+#[test]
+fn test_report_md5sum_as_hex() {
+    let data = b"d4:infod5:filesld6:lengthi1e6:md5sum32:0123456789ABCDEF0123456789ABCDEF4:pathl1:aeed6:lengthi1e6:md5sum4:\x01\x02\x03\x044:pathl1:beee4:name3:dir12:piece lengthi16384e6:pieces0:ee";
+    let torrent = Torrent::from_buf(data).unwrap();
+    let report = TorrentReport::from_torrent(&torrent).expect("Should create report");
+    let checksums: Vec<Option<&str>> = report.files.iter().map(|file| file.md5sum.as_deref()).collect();
+    assert_eq!(checksums, [Some("0123456789abcdef0123456789abcdef"), Some("01020304")]);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_report_toml_roundtrip() {
+    let reports: Vec<TorrentReport> = [
+        UBUNTU_TORRENT_PATH,
+        "tests/fixtures/multi-file.torrent",
+        "tests/fixtures/trackerless.torrent",
+        V2_TORRENT_PATH,
+    ]
+    .into_iter()
+    .map(|path| TorrentReport::from_torrent(&Torrent::from_file(path).unwrap()).unwrap())
+    .collect();
+
+    let document = torrentinfo::report::to_toml(&reports).expect("Should serialize reports");
+    assert_eq!(document.matches("[[torrents]]").count(), reports.len());
+    assert!(document.contains("[[torrents.files]]"));
+    assert!(document.contains(&format!("info_hash = \"{}\"", ubuntu::INFO_HASH)));
+
+    let parsed = torrentinfo::report::from_toml(&document).expect("Should parse reports");
+    assert_eq!(parsed, reports);
+}
+
+#[test]
+fn test_report_tracker_tiers_json_shape() {
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    let report = TorrentReport::from_torrent(&torrent).expect("Should create report");
+    let json = serde_json::to_value(&report).expect("Should serialize report");
+    assert_eq!(
+        json["tracker_tiers"],
+        serde_json::json!([
+            {"tier": 0, "trackers": ["https://torrent.ubuntu.com/announce"]},
+            {"tier": 1, "trackers": ["https://ipv6.torrent.ubuntu.com/announce"]},
+        ])
+    );
+}
+
+#[test]
+fn test_report_tracker_tiers_roundtrip() {
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    let report = TorrentReport::from_torrent(&torrent).expect("Should create report");
+    let tiers: Vec<usize> = report.tracker_tiers.iter().map(|tier| tier.tier).collect();
+    assert_eq!(tiers, vec![0, 1]);
+    assert_eq!(
+        report.announce_list(),
+        vec![
+            vec!["https://torrent.ubuntu.com/announce".to_string()],
+            vec!["https://ipv6.torrent.ubuntu.com/announce".to_string()],
+        ]
+    );
+
+    let torrent = Torrent::from_file(POPOS_TORRENT_PATH).unwrap();
+    let report = TorrentReport::from_torrent(&torrent).expect("Should create report");
+    let announce_list = report.announce_list();
+    assert_eq!(announce_list.len(), 9);
+    assert_eq!(
+        announce_list[0],
+        vec![
+            "udp://fosstorrents.com:6969/announce".to_string(),
+            "http://fosstorrents.com:6969/announce".to_string(),
+        ]
+    );
+    assert_eq!(announce_list[8], vec!["udp://opentracker.io:6969/announce".to_string()]);
+
+    let torrent = Torrent::from_file("tests/fixtures/trackerless.torrent").unwrap();
+    let report = TorrentReport::from_torrent(&torrent).expect("Should create report");
+    assert!(report.tracker_tiers.is_empty());
+    assert!(report.announce_list().is_empty());
+}
+
+#[test]
+fn test_report_multi_file_paths() {
+    let torrent = Torrent::from_file("tests/fixtures/multi-file.torrent").unwrap();
+    let report = TorrentReport::from_torrent(&torrent).expect("Should create report");
+    let paths: Vec<&str> = report.files.iter().map(|file| file.path.as_str()).collect();
+    assert_eq!(paths, vec!["README.txt", "data/part1.bin", "data/part2.bin"]);
+}
+
+// Tolerant parsing tests
+
+#[test]
+fn test_private_flag_as_string() {
+    let torrent = Torrent::from_file(PRIVATE_STRING_TORRENT_PATH).expect("Should parse string private flag");
+    assert_eq!(*torrent.info().private(), Some(1));
+    assert_eq!(torrent.name().as_deref(), Some("private-string.bin"));
+}
+
+#[test]
+fn test_private_flag_as_integer() {
+    let data = b"d4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:7:privatei1eee";
+    let torrent = Torrent::from_buf(data).expect("Should parse integer private flag");
+    assert_eq!(*torrent.info().private(), Some(1));
+}
+
+#[test]
+fn test_dht_disabled_for_private_torrents() {
+    let private = Torrent::from_file(PRIVATE_STRING_TORRENT_PATH).unwrap();
+    assert!(!private.can_use_dht());
+    assert!(!private.is_trackerless_capable());
+
+    let data = b"d4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:7:privatei0eee";
+    assert!(Torrent::from_buf(data).unwrap().can_use_dht());
+}
+
+#[test]
+fn test_dht_allowed_for_public_torrents() {
+    let public = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    assert!(public.can_use_dht());
+    // DHT is allowed, but there are no bootstrap nodes to find peers without the trackers
+    assert!(!public.is_trackerless_capable());
+
+    let trackerless = Torrent::from_file("tests/fixtures/trackerless.torrent").unwrap();
+    assert!(trackerless.can_use_dht());
+    assert!(trackerless.is_trackerless_capable());
+}
+
+#[test]
+fn test_private_flag_invalid_string() {
+    let data = b"d4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:7:private3:yeseee";
+    assert!(Torrent::from_buf(data).is_err(), "Non-numeric private flag should fail");
+}
+
+#[test]
+fn test_string_creation_date() {
+    let torrent = Torrent::from_file(STRING_DATE_TORRENT_PATH).unwrap();
+    assert_eq!(torrent.creation_date(), &Some(1_735_689_600));
+
+    let reparsed = Torrent::from_buf(&torrent.to_bytes().unwrap()).unwrap();
+    assert_eq!(reparsed.creation_date(), &Some(1_735_689_600));
+}
+
+#[test]
+fn test_file_length_as_string() {
+    let torrent = Torrent::from_file(STRING_LENGTH_TORRENT_PATH).unwrap();
+    assert_eq!(torrent.total_size(), 42_000);
+    let lengths: Vec<i64> = torrent.iter_files().map(|file| file.length()).collect();
+    assert_eq!(lengths, [30_000, 12_000]);
+
+    assert_eq!(
+        TorrentSummary::from_file(STRING_LENGTH_TORRENT_PATH)
+            .unwrap()
+            .total_size_bytes(),
+        42_000
+    );
+    assert_eq!(
+        Torrent::peek(STRING_LENGTH_TORRENT_PATH).unwrap().total_size_bytes(),
+        42_000
+    );
+
+    // The length is always serialized back as an integer
+    let reparsed = Torrent::from_buf(&torrent.to_bytes().unwrap()).unwrap();
+    assert_eq!(reparsed.total_size(), 42_000);
+}
+
+#[test]
+fn test_single_file_length_as_string() {
+    let data = b"d4:infod6:length4:12344:name1:a12:piece lengthi16384e6:pieces0:ee";
+    let torrent = Torrent::from_buf(data).unwrap();
+    assert_eq!(torrent.info.length, Some(1234));
+    assert_eq!(torrent.total_size(), 1234);
+}
+
+#[test]
+fn test_length_invalid_string() {
+    let data = b"d4:infod6:length3:big4:name1:a12:piece lengthi16384e6:pieces0:ee";
+    assert!(Torrent::from_buf(data).is_err());
+
+    let data = b"d4:infod5:filesld6:length2:-14:pathl1:beee4:name1:a12:piece lengthi16384e6:pieces0:ee";
+    assert!(Torrent::from_buf(data).is_err());
+}
+
+#[test]
+fn test_httpseeds_as_single_string() {
+    let torrent = Torrent::from_file("tests/fixtures/httpseeds-string.torrent").unwrap();
+    assert_eq!(
+        torrent.httpseeds,
+        Some(vec!["https://seed.example.org/seed.php".to_string()])
+    );
+
+    // The seeds are always serialized back as a list
+    let reparsed = Torrent::from_buf(&torrent.to_bytes().unwrap()).unwrap();
+    assert_eq!(reparsed.httpseeds, torrent.httpseeds);
+}
+
+#[test]
+fn test_httpseeds_as_list() {
+    let data = b"d9:httpseedsl5:seed15:seed2e4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:ee";
+    let torrent = Torrent::from_buf(data).unwrap();
+    assert_eq!(torrent.httpseeds, Some(vec!["seed1".to_string(), "seed2".to_string()]));
+    assert!(
+        Torrent::from_buf(b"d9:httpseedsi1e4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:ee").is_err()
+    );
+}
+
+#[test]
+fn test_path_utf8_is_preferred() {
+    let torrent = Torrent::from_file(PATH_UTF8_TORRENT_PATH).unwrap();
+    let files = torrent.files_normalized();
+    assert_eq!(files[0].path(), ["Café", "menü.txt"]);
+    assert_eq!(files[0].path, ["Cafe", "menu.txt"]);
+    assert_eq!(files[1].path(), ["日本語.txt"]);
+    assert_eq!(files[2].path(), ["plain.txt"]);
+    assert!(files[2].path_utf8.is_none());
+
+    let reparsed = Torrent::from_buf(&torrent.to_bytes().unwrap()).unwrap();
+    assert_eq!(reparsed.info_hash().unwrap(), torrent.info_hash().unwrap());
+    assert_eq!(reparsed.files_normalized()[1].path(), ["日本語.txt"]);
+}
+
+#[test]
+fn test_health_of_valid_fixtures() {
+    for path in [
+        UBUNTU_TORRENT_PATH,
+        POPOS_TORRENT_PATH,
+        "tests/fixtures/multi-file.torrent",
+        V2_TORRENT_PATH,
+        ZERO_LENGTH_TORRENT_PATH,
+    ] {
+        let report = health::check(&std::fs::read(path).unwrap());
+        assert_eq!(report.grade, Grade::Ok, "{path}: {:?}", report.problems);
+        assert!(report.problems.is_empty());
+    }
+}
+
+#[test]
+fn test_health_of_truncated_torrent_is_corrupt() {
+    let data = std::fs::read(UBUNTU_TORRENT_PATH).unwrap();
+    let report = health::check(&data[..data.len() / 2]);
+    assert_eq!(report.grade, Grade::Corrupt);
+    assert!(report.problems[0].starts_with("not valid bencode"));
+
+    assert_eq!(health::check(b"li1ee").grade, Grade::Corrupt);
+    assert_eq!(
+        health::check(b"d8:announce3:urle").problems,
+        ["missing info dictionary"]
+    );
+}
+
+#[test]
+fn test_health_of_damaged_pieces_is_corrupt() {
+    let report = health::check(&std::fs::read("tests/fixtures/mangled-pieces.torrent").unwrap());
+    assert_eq!(report.grade, Grade::Corrupt);
+    assert_eq!(report.problems, ["pieces length of 30 bytes is not a multiple of 20"]);
+
+    let report = health::check(&std::fs::read("tests/fixtures/empty-pieces.torrent").unwrap());
+    assert_eq!(report.grade, Grade::Corrupt);
+    assert_eq!(report.problems, ["no piece hashes for 100000 bytes of content"]);
+}
+
+#[test]
+fn test_health_of_nonstandard_torrents_is_suspicious() {
+    let report = health::check(&std::fs::read(BOM_PREFIXED_TORRENT_PATH).unwrap());
+    assert_eq!(report.grade, Grade::Suspicious);
+    assert_eq!(
+        report.problems,
+        ["byte order mark or whitespace around the bencode data"]
+    );
+
+    // Two piece hashes for a single byte of content
+    let report = health::check(
+        b"d4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces40:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaee",
+    );
+    assert_eq!(report.grade, Grade::Suspicious);
+    assert_eq!(report.problems, ["2 piece hashes but the content needs 1"]);
+}
+
+#[test]
+fn test_primary_tracker_falls_back_to_announce_list() {
+    let torrent = Torrent::from_file("tests/fixtures/announce-list-only.torrent").unwrap();
+    assert!(torrent.announce().is_none());
+    assert_eq!(torrent.primary_tracker(), Some("https://tier1.example.org/announce"));
+
+    let ubuntu = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    assert_eq!(ubuntu.primary_tracker(), ubuntu.announce().as_deref());
+    assert!(
+        Torrent::from_file("tests/fixtures/trackerless.torrent")
+            .unwrap()
+            .primary_tracker()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_web_seeds_from_url_list_and_httpseeds() {
+    let popos = Torrent::from_file(POPOS_TORRENT_PATH).unwrap();
+    assert_eq!(
+        popos.web_seeds(),
+        [
+            "https://iso.pop-os.org/24.04/amd64/nvidia/22/pop-os_24.04_amd64_nvidia_22.iso",
+            "http://fosstorrents.com/direct-links/pop-os_24.04_amd64_nvidia_22.iso",
+        ]
+    );
+    assert!(Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap().web_seeds().is_empty());
+
+    let data = b"d9:httpseedsl26:https://seed.example.org/a26:https://http.example.org/ae4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:e8:url-list26:https://seed.example.org/ae";
+    let torrent = Torrent::from_buf(data).unwrap();
+    assert_eq!(torrent.url_list, Some(vec!["https://seed.example.org/a".to_string()]));
+    assert_eq!(
+        torrent.web_seeds(),
+        ["https://seed.example.org/a", "https://http.example.org/a"]
+    );
+}
+
+/// Load the multi-file fixture with a different piece length, piece hashes and tracker
+fn repieced_multi_file() -> Torrent {
+    let mut torrent = Torrent::from_file("tests/fixtures/multi-file.torrent").expect("Failed to parse fixture");
+    torrent.info_mut().piece_length = 16_384;
+    torrent.info_mut().pieces = vec![0xab; 20 * 34].into();
+    torrent.announce = Some("https://other.example.org/announce".to_string());
+    torrent.announce_list = None;
+    torrent
+}
+
+#[test]
+fn test_content_fingerprint_ignores_piece_layout() {
+    let original = Torrent::from_file("tests/fixtures/multi-file.torrent").unwrap();
+    let repieced = repieced_multi_file();
+    assert_ne!(original.info_hash().unwrap(), repieced.info_hash().unwrap());
+    assert_eq!(original.content_fingerprint(), repieced.content_fingerprint());
+    assert_eq!(original.content_fingerprint().len(), 64);
+
+    let repack = Torrent::from_file("tests/fixtures/multi-file-repack.torrent").unwrap();
+    assert_ne!(original.content_fingerprint(), repack.content_fingerprint());
+}
+
+#[test]
+fn test_content_fingerprint_agrees_with_same_content() {
+    let nested =
+        Torrent::from_buf(b"d4:infod5:filesld6:lengthi1e4:pathl1:a1:beee4:name1:d12:piece lengthi16384e6:pieces0:ee")
+            .unwrap();
+    let slash =
+        Torrent::from_buf(b"d4:infod5:filesld6:lengthi1e4:pathl3:a/beee4:name1:d12:piece lengthi16384e6:pieces0:ee")
+            .unwrap();
+    assert!(!nested.same_content(&slash));
+    assert_ne!(nested.content_fingerprint(), slash.content_fingerprint());
+
+    let mut unnamed = nested.clone();
+    unnamed.info_mut().name = None;
+    assert!(!nested.same_content(&unnamed));
+    assert_ne!(nested.content_fingerprint(), unnamed.content_fingerprint());
+}
+
+#[test]
+fn test_content_fingerprint_ignores_file_order_and_padding() {
+    let ordered = Torrent::from_buf(
+        b"d4:infod5:filesld6:lengthi1e4:pathl1:aeed6:lengthi2e4:pathl1:beee4:name1:d12:piece lengthi16384e6:pieces0:ee",
+    )
+    .unwrap();
+    let reordered = Torrent::from_buf(b"d4:infod5:filesld6:lengthi2e4:pathl1:beed4:attr1:p6:lengthi5e4:pathl4:.pad1:5eed6:lengthi1e4:pathl1:aeee4:name1:d12:piece lengthi16384e6:pieces0:ee").unwrap();
+    assert_eq!(ordered.content_fingerprint(), reordered.content_fingerprint());
+}
+
+#[test]
+fn test_pieces_overhead_bytes() {
+    let ubuntu = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    assert_eq!(ubuntu.num_pieces(), ubuntu::NUM_PIECES);
+    assert_eq!(ubuntu.pieces_overhead_bytes(), ubuntu::NUM_PIECES * 20);
+
+    let popos = Torrent::from_file(POPOS_TORRENT_PATH).unwrap();
+    assert_eq!(popos.num_pieces(), popos::NUM_PIECES);
+    assert_eq!(popos.pieces_overhead_bytes(), popos::NUM_PIECES * 20);
+
+    // v2-only torrents have no v1 piece hashes
+    assert_eq!(Torrent::from_file(V2_TORRENT_PATH).unwrap().pieces_overhead_bytes(), 0);
+}
+
 // Error handling tests
 
 #[test]
@@ -335,6 +946,318 @@ fn test_to_hex_function() {
     assert_eq!(to_hex(&[0xff]), "ff");
 }
 
+// Zero-length file tests
+
+/// Write the data described by the zero-length fixture into a fresh directory.
+///
+/// The zero-length marker file is not created.
+fn write_zero_length_data(name: &str, include_omega: bool) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    if root.exists() {
+        std::fs::remove_dir_all(&root).expect("Failed to remove test data");
+    }
+    let content = root.join("zero-length").join("content");
+    std::fs::create_dir_all(&content).expect("Failed to create test data directory");
+    let alpha: Vec<u8> = (0..70_000u32).map(|index| (index * 7 % 256) as u8).collect();
+    std::fs::write(content.join("alpha.bin"), alpha).expect("Failed to write test data");
+    if include_omega {
+        std::fs::write(content.join("omega.bin"), [0x5a; 1000]).expect("Failed to write test data");
+    }
+    root
+}
+
+#[test]
+fn test_zero_length_file_listed() {
+    let torrent = Torrent::from_file(ZERO_LENGTH_TORRENT_PATH).unwrap();
+    let files = torrent.files().as_ref().unwrap();
+    assert_eq!(files.len(), 3);
+    assert_eq!(files[1].path(), &["content", "marker.empty"]);
+    assert_eq!(files[1].length(), 0);
+    assert_eq!(torrent.total_size(), 71_000);
+}
+
+#[test]
+fn test_zero_length_file_piece_range() {
+    let torrent = Torrent::from_file(ZERO_LENGTH_TORRENT_PATH).unwrap();
+    assert_eq!(torrent.file_piece_range(0), Some(0..3));
+    assert_eq!(torrent.file_piece_range(1), Some(2..2));
+    assert!(torrent.file_piece_range(1).unwrap().is_empty());
+    assert_eq!(torrent.file_piece_range(2), Some(2..3));
+    assert_eq!(torrent.file_piece_range(3), None);
+}
+
+#[test]
+fn test_piece_file_spans_at_file_boundary() {
+    let torrent = Torrent::from_file("tests/fixtures/multi-file.torrent").unwrap();
+    let spans = torrent.piece_file_spans(4).unwrap();
+    assert_eq!(spans.len(), 2);
+    assert_eq!((spans[0].file_index, spans[0].range.clone()), (1, 261_144..300_000));
+    assert_eq!((spans[1].file_index, spans[1].range.clone()), (2, 0..26_680));
+    assert!(torrent.piece_file_spans(torrent.num_pieces()).is_none());
+}
+
+#[test]
+fn test_piece_file_spans_skip_zero_length_file() {
+    let torrent = Torrent::from_file(ZERO_LENGTH_TORRENT_PATH).unwrap();
+    let spans = torrent.piece_file_spans(2).unwrap();
+    assert!(spans.iter().all(|span| span.file_index != 1));
+    assert!(spans.iter().all(|span| !span.range.is_empty()));
+}
+
+#[test]
+fn test_piece_file_map_matches_piece_file_spans() {
+    for path in [
+        "tests/fixtures/multi-file.torrent",
+        ZERO_LENGTH_TORRENT_PATH,
+        UBUNTU_TORRENT_PATH,
+    ] {
+        let torrent = Torrent::from_file(path).unwrap();
+        let map: Vec<Vec<PieceSpan>> = torrent.piece_file_map().collect();
+        assert_eq!(map.len(), torrent.num_pieces(), "{path}");
+        for (piece, spans) in map.iter().enumerate() {
+            assert_eq!(
+                Some(spans),
+                torrent.piece_file_spans(piece).as_ref(),
+                "{path} piece {piece}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_verify_files_ignores_absent_zero_length_file() {
+    let torrent = Torrent::from_file(ZERO_LENGTH_TORRENT_PATH).unwrap();
+    let root = write_zero_length_data("verify_zero_length", true);
+
+    let report = torrent.verify_files(&root).unwrap();
+    assert!(report.is_complete(), "{report:?}");
+    assert!(report.missing_files.is_empty());
+    assert_eq!(report.passed_pieces(), 3);
+    assert_eq!(report.bytes_read, 71_000);
+}
+
+#[test]
+fn test_verify_files_reports_missing_file() {
+    let torrent = Torrent::from_file(ZERO_LENGTH_TORRENT_PATH).unwrap();
+    let root = write_zero_length_data("verify_missing_file", false);
+
+    let report = torrent.verify_files(&root).unwrap();
+    assert_eq!(report.missing_pieces, vec![2]);
+    assert!(report.failed_pieces.is_empty());
+    assert_eq!(
+        report.missing_files,
+        vec![root.join("zero-length").join("content").join("omega.bin")]
+    );
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_verify_files_parallel_matches_serial() {
+    let torrent = Torrent::from_file(ZERO_LENGTH_TORRENT_PATH).unwrap();
+    for (name, include_omega) in [("verify_parallel_complete", true), ("verify_parallel_missing", false)] {
+        let root = write_zero_length_data(name, include_omega);
+        let serial = torrent.verify_files(&root).unwrap();
+        let parallel = torrent.verify_files_parallel(&root).unwrap();
+        assert_eq!(parallel, serial);
+    }
+}
+
+#[test]
+fn test_match_existing_partial_directory() {
+    let torrent = Torrent::from_file(ZERO_LENGTH_TORRENT_PATH).unwrap();
+    let root = write_zero_length_data("match_existing_partial", false);
+    let content = root.join("zero-length").join("content");
+
+    let matches = torrent.match_existing(&root);
+    assert_eq!(matches.len(), 3);
+    assert_eq!(matches[0].path, content.join("alpha.bin"));
+    assert_eq!(matches[0].size_on_disk, Some(70_000));
+    assert!(matches[0].is_complete());
+    assert_eq!(matches[1].size_on_disk, None);
+    assert!(matches[1].is_complete());
+    assert_eq!(matches[2].path, content.join("omega.bin"));
+    assert_eq!(matches[2].size_on_disk, None);
+    assert!(!matches[2].is_complete());
+    assert_eq!(matches.iter().map(FileMatch::present_bytes).sum::<u64>(), 70_000);
+}
+
+#[test]
+fn test_match_existing_truncated_and_oversized_files() {
+    let torrent = Torrent::from_file(ZERO_LENGTH_TORRENT_PATH).unwrap();
+    let root = write_zero_length_data("match_existing_truncated", false);
+    let content = root.join("zero-length").join("content");
+    std::fs::write(content.join("alpha.bin"), [0; 100]).unwrap();
+    std::fs::write(content.join("omega.bin"), [0; 5000]).unwrap();
+
+    let matches = torrent.match_existing(&root);
+    assert_eq!(matches[0].present_bytes(), 100);
+    assert!(!matches[0].is_complete());
+    assert_eq!(matches[2].present_bytes(), 1000);
+    assert!(!matches[2].is_complete());
+}
+
+#[test]
+fn test_match_existing_skips_padding_files() {
+    let torrent = Torrent::from_file(ATTRIBUTES_TORRENT_PATH).unwrap();
+    let matches = torrent.match_existing(Path::new("missing-directory"));
+    assert_eq!(matches.len(), 3);
+    assert!(
+        matches
+            .iter()
+            .all(|file_match| !file_match.path.starts_with("missing-directory/attributes/.pad"))
+    );
+}
+
+// Largest and smallest file tests
+
+#[test]
+fn test_multi_file_largest_and_smallest_file() {
+    let torrent = Torrent::from_file("tests/fixtures/multi-file.torrent").unwrap();
+    let largest = torrent.largest_file().unwrap();
+    assert_eq!(largest.path(), &["data", "part1.bin"]);
+    assert_eq!(largest.length(), 300_000);
+    let smallest = torrent.smallest_file().unwrap();
+    assert_eq!(smallest.path(), &["README.txt"]);
+}
+
+#[test]
+fn test_zero_length_file_not_smallest() {
+    let torrent = Torrent::from_file(ZERO_LENGTH_TORRENT_PATH).unwrap();
+    let smallest = torrent.smallest_file().unwrap();
+    assert_eq!(smallest.path(), &["content", "omega.bin"]);
+}
+
+#[test]
+fn test_single_file_largest_file_is_torrent_name() {
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    let largest = torrent.largest_file().unwrap();
+    assert_eq!(largest.path(), &[ubuntu::NAME]);
+    assert_eq!(largest.length(), ubuntu::TOTAL_SIZE);
+}
+
+// Same content tests
+
+#[test]
+fn test_same_content_after_repiecing() {
+    let original = Torrent::from_file("tests/fixtures/multi-file.torrent").unwrap();
+    let mut repieced = Torrent::from_file("tests/fixtures/multi-file.torrent").unwrap();
+    repieced.info_mut().piece_length *= 2;
+    repieced.info_mut().pieces = repieced.info.pieces()[..repieced.info.pieces().len() / 2]
+        .to_vec()
+        .into();
+
+    assert_ne!(original.info_hash().unwrap(), repieced.info_hash().unwrap());
+    assert!(original.same_content(&repieced));
+    assert!(original.same_content(&original));
+}
+
+#[test]
+fn test_different_content() {
+    let original = Torrent::from_file("tests/fixtures/multi-file.torrent").unwrap();
+    let repack = Torrent::from_file("tests/fixtures/multi-file-repack.torrent").unwrap();
+    let ubuntu = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    assert!(!original.same_content(&repack));
+    assert!(!original.same_content(&ubuntu));
+}
+
+// Torrent file discovery tests
+
+#[test]
+fn test_find_in_dir() {
+    let files = Torrent::find_in_dir(Path::new("tests"), false, false);
+    assert_eq!(
+        files,
+        vec![
+            PathBuf::from("tests/pop-os_24.04_amd64_nvidia_22.iso.torrent"),
+            PathBuf::from("tests/ubuntu-24.04.3-desktop-amd64.iso.torrent"),
+        ]
+    );
+}
+
+#[test]
+fn test_find_in_dir_recursive() {
+    let files = Torrent::find_in_dir(Path::new("tests"), true, false);
+    assert!(files.len() > 2);
+    assert!(files.contains(&PathBuf::from(V2_TORRENT_PATH)));
+    assert!(files.contains(&PathBuf::from(UBUNTU_TORRENT_PATH)));
+
+    let fixtures = Torrent::find_in_dir(Path::new("tests/fixtures"), false, false);
+    assert!(fixtures.iter().all(|path| path.starts_with("tests/fixtures")));
+    assert!(fixtures.is_sorted_by_key(|path| path.to_string_lossy().to_lowercase()));
+}
+
+#[test]
+fn test_find_in_dir_skips_hidden() {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("find_hidden");
+    if root.exists() {
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+    std::fs::create_dir_all(root.join(".hidden")).unwrap();
+    std::fs::copy(UBUNTU_TORRENT_PATH, root.join("visible.torrent")).unwrap();
+    std::fs::copy(UBUNTU_TORRENT_PATH, root.join(".hidden/secret.torrent")).unwrap();
+    std::fs::copy(UBUNTU_TORRENT_PATH, root.join(".dotfile.torrent")).unwrap();
+
+    assert_eq!(
+        Torrent::find_in_dir(&root, true, false),
+        vec![root.join("visible.torrent")]
+    );
+    assert_eq!(
+        Torrent::find_in_dir(&root, true, true),
+        vec![
+            root.join(".dotfile.torrent"),
+            root.join(".hidden/secret.torrent"),
+            root.join("visible.torrent"),
+        ]
+    );
+}
+
+// BEP 47 file attribute tests
+
+#[test]
+fn test_file_attributes_and_symlink_path() {
+    let torrent = Torrent::from_file(ATTRIBUTES_TORRENT_PATH).unwrap();
+    let files = torrent.files().as_ref().unwrap();
+    assert_eq!(
+        files[0].attributes(),
+        FileAttributes {
+            executable: true,
+            ..FileAttributes::default()
+        }
+    );
+    assert!(files[1].attributes().symlink);
+    assert_eq!(
+        files[1].symlink_path(),
+        Some(["bin".to_string(), "run.sh".to_string()].as_slice())
+    );
+    assert!(files[2].is_padding());
+    assert!(files[3].attributes().hidden);
+    assert!(files[3].symlink_path().is_none());
+}
+
+#[test]
+fn test_file_offsets_with_padding() {
+    let torrent = Torrent::from_file(ATTRIBUTES_TORRENT_PATH).unwrap();
+    assert_eq!(torrent.file_offset(0), Some(0));
+    assert_eq!(torrent.file_offset(1), Some(1200));
+    assert_eq!(torrent.file_offset(2), Some(1200));
+    assert_eq!(torrent.file_offset(3), Some(16_384));
+    assert_eq!(torrent.file_piece_range(3), Some(1..2));
+}
+
+#[test]
+fn test_multi_file_offsets() {
+    let torrent = Torrent::from_file("tests/fixtures/multi-file.torrent").unwrap();
+    let offsets: Vec<Option<u64>> = (0..4).map(|index| torrent.file_offset(index)).collect();
+    assert_eq!(offsets, vec![Some(0), Some(1000), Some(301_000), None]);
+}
+
+#[test]
+fn test_file_attributes_roundtrip() {
+    let data = std::fs::read(ATTRIBUTES_TORRENT_PATH).unwrap();
+    let torrent = Torrent::from_buf(&data).unwrap();
+    assert_eq!(torrent.to_bytes().unwrap(), data);
+}
+
 // Struct default and construction tests
 
 #[test]
@@ -369,3 +1292,503 @@ fn test_torrent_default() {
     assert_eq!(torrent.num_files(), 0);
     assert_eq!(torrent.total_size(), 0);
 }
+
+#[test]
+fn test_summary_matches_full_parse() {
+    let files = Torrent::find_in_dir(Path::new("tests"), true, true);
+    assert!(files.len() > 2);
+    for file in files {
+        let torrent = Torrent::from_file(&file).unwrap();
+        let summary = TorrentSummary::from_file(&file).unwrap();
+        assert_eq!(summary.name(), torrent.name(), "{}", file.display());
+        assert_eq!(
+            summary.content_name_or_source(&file),
+            torrent.content_name_or_source(&file),
+            "{}",
+            file.display()
+        );
+        assert_eq!(summary.creation_date(), torrent.creation_date(), "{}", file.display());
+        assert_eq!(summary.num_files(), torrent.num_files(), "{}", file.display());
+        assert_eq!(
+            summary.total_size_bytes(),
+            torrent.total_size_bytes(),
+            "{}",
+            file.display()
+        );
+    }
+}
+
+#[test]
+fn test_metadata_only_matches_full_parse() {
+    for file in Torrent::find_in_dir(Path::new("tests"), true, true) {
+        let torrent = Torrent::from_file(&file).unwrap();
+        let metadata = Torrent::from_file_metadata_only(&file).unwrap();
+        assert_eq!(metadata.name(), torrent.name(), "{}", file.display());
+        assert_eq!(metadata.total_size(), torrent.total_size(), "{}", file.display());
+        assert_eq!(metadata.num_files(), torrent.num_files(), "{}", file.display());
+        assert_eq!(metadata.trackers(), torrent.trackers(), "{}", file.display());
+        assert_eq!(metadata.creation_date(), torrent.creation_date(), "{}", file.display());
+        assert!(metadata.info.pieces.is_empty(), "{}", file.display());
+        assert!(metadata.piece_layers.is_none(), "{}", file.display());
+        // Every other field is decoded the same way as in the full parse
+        assert_eq!(
+            metadata.to_bytes().unwrap(),
+            torrent.clone_metadata().to_bytes().unwrap(),
+            "{}",
+            file.display()
+        );
+    }
+}
+
+#[test]
+fn test_metadata_only_ubuntu() {
+    let torrent = Torrent::from_file_metadata_only(UBUNTU_TORRENT_PATH).unwrap();
+    assert_eq!(torrent.name().as_deref(), Some(ubuntu::NAME));
+    assert_eq!(torrent.comment().as_deref(), Some(ubuntu::COMMENT));
+    assert_eq!(torrent.total_size(), ubuntu::TOTAL_SIZE);
+    assert_eq!(torrent.info.piece_length, ubuntu::PIECE_LENGTH);
+    assert_eq!(torrent.num_pieces(), 0);
+}
+
+#[test]
+fn test_clone_metadata_skips_pieces() {
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    let copy = torrent.clone_metadata();
+    assert!(copy.info.pieces.is_empty());
+    assert!(!torrent.info.pieces.is_empty());
+    assert_eq!(copy.name(), torrent.name());
+    assert_eq!(copy.total_size(), torrent.total_size());
+    assert_eq!(copy.trackers(), torrent.trackers());
+    assert_eq!(copy.info.piece_length, torrent.info.piece_length);
+
+    let v2 = Torrent::from_file(V2_TORRENT_PATH).unwrap().clone_metadata();
+    assert!(v2.piece_layers.is_none());
+    assert_eq!(v2.num_files(), 3);
+}
+
+#[test]
+fn test_summary_ubuntu() {
+    let summary = TorrentSummary::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    assert_eq!(summary.name().as_deref(), Some(ubuntu::NAME));
+    assert_eq!(summary.num_files(), ubuntu::NUM_FILES);
+    assert_eq!(summary.total_size_bytes(), ubuntu::TOTAL_SIZE as u64);
+}
+
+#[test]
+fn test_summary_invalid_data() {
+    assert!(TorrentSummary::from_buf(b"not a torrent").is_err());
+}
+
+#[test]
+fn test_raw_value_info_name() {
+    let Value::Dict(root) = Torrent::raw_value(UBUNTU_TORRENT_PATH).unwrap() else {
+        panic!("Torrent root is not a dictionary");
+    };
+    let Some(Value::Dict(info)) = root.get(b"info".as_slice()) else {
+        panic!("Missing info dictionary");
+    };
+    let Some(Value::Bytes(name)) = info.get(b"name".as_slice()) else {
+        panic!("Missing info name");
+    };
+    assert_eq!(name.as_slice(), ubuntu::NAME.as_bytes());
+    assert!(matches!(root.get(b"announce".as_slice()), Some(Value::Bytes(_))));
+}
+
+#[test]
+fn test_raw_value_from_buf_invalid() {
+    assert!(Torrent::raw_value_from_buf(b"d4:info").is_err());
+    assert!(matches!(Torrent::raw_value_from_buf(b"i42e").unwrap(), Value::Int(42)));
+}
+
+#[test]
+fn test_mangled_pieces_lenient_parse() {
+    let torrent = Torrent::from_file("tests/fixtures/mangled-pieces.torrent").unwrap();
+    assert!(!torrent.pieces_are_valid_length());
+    assert_eq!(torrent.name().as_deref(), Some("mangled-pieces.bin"));
+    assert_eq!(torrent.num_pieces(), 1);
+}
+
+#[test]
+fn test_mangled_pieces_strict_parse() {
+    let bytes = Torrent::read_bytes(Path::new("tests/fixtures/mangled-pieces.torrent")).unwrap();
+    let result = Torrent::from_buf_strict(&bytes);
+    assert!(matches!(result, Err(torrentinfo::errors::Error::InvalidPieces(30))));
+}
+
+#[test]
+fn test_strict_parse_accepts_valid_torrents() {
+    let bytes = Torrent::read_bytes(Path::new(UBUNTU_TORRENT_PATH)).unwrap();
+    let torrent = Torrent::from_buf_strict(&bytes).unwrap();
+    assert!(torrent.pieces_are_valid_length());
+
+    // v2-only torrents have no v1 piece hashes at all
+    let bytes = Torrent::read_bytes(Path::new(V2_TORRENT_PATH)).unwrap();
+    assert!(Torrent::from_buf_strict(&bytes).is_ok());
+}
+
+/// Bencode a torrent with the given info dictionary entries
+fn strict_torrent(info: &str) -> Vec<u8> {
+    format!("d8:announce32:https://tracker.example/announce4:infod{info}ee").into_bytes()
+}
+
+const STRICT_PIECES: &str = "6:pieces20:aaaaaaaaaaaaaaaaaaaa";
+
+#[test]
+fn test_strict_parse_accepts_spec_torrent() {
+    let buf = strict_torrent(&format!("6:lengthi1e4:name1:a12:piece lengthi16384e{STRICT_PIECES}"));
+    assert!(Torrent::from_buf_strict(&buf).is_ok());
+}
+
+#[test]
+fn test_strict_parse_rejects_non_power_of_two_piece_length() {
+    let buf = strict_torrent(&format!("6:lengthi1e4:name1:a12:piece lengthi10000e{STRICT_PIECES}"));
+    assert!(Torrent::from_buf(&buf).is_ok());
+    assert!(matches!(
+        Torrent::from_buf_strict(&buf),
+        Err(torrentinfo::errors::Error::InvalidPieceLength(10000))
+    ));
+}
+
+#[test]
+fn test_strict_parse_rejects_both_length_and_files() {
+    let buf = strict_torrent(&format!(
+        "5:filesld6:lengthi1e4:pathl1:beee6:lengthi1e4:name1:a12:piece lengthi16384e{STRICT_PIECES}"
+    ));
+    assert!(Torrent::from_buf(&buf).is_ok());
+    assert!(matches!(
+        Torrent::from_buf_strict(&buf),
+        Err(torrentinfo::errors::Error::InvalidFiles("both `length` and `files`"))
+    ));
+}
+
+#[test]
+fn test_strict_parse_rejects_neither_length_nor_files() {
+    let buf = strict_torrent(&format!("4:name1:a12:piece lengthi16384e{STRICT_PIECES}"));
+    assert!(Torrent::from_buf(&buf).is_ok());
+    assert!(matches!(
+        Torrent::from_buf_strict(&buf),
+        Err(torrentinfo::errors::Error::InvalidFiles("neither `length` nor `files`"))
+    ));
+}
+
+#[test]
+fn test_strict_parse_rejects_missing_name() {
+    let buf = strict_torrent(&format!("6:lengthi1e12:piece lengthi16384e{STRICT_PIECES}"));
+    assert!(Torrent::from_buf(&buf).is_ok());
+    assert!(matches!(
+        Torrent::from_buf_strict(&buf),
+        Err(torrentinfo::errors::Error::MissingField("name"))
+    ));
+}
+
+#[test]
+fn test_strict_parse_rejects_missing_pieces() {
+    let buf = strict_torrent("6:lengthi1e4:name1:a12:piece lengthi16384e");
+    assert!(Torrent::from_buf(&buf).is_ok());
+    assert!(matches!(
+        Torrent::from_buf_strict(&buf),
+        Err(torrentinfo::errors::Error::MissingField("pieces"))
+    ));
+}
+
+#[test]
+fn test_strict_parse_rejects_negative_integer() {
+    let mut buf = b"d13:creation datei-5e".to_vec();
+    buf.extend_from_slice(&strict_torrent(&format!("6:lengthi1e4:name1:a12:piece lengthi16384e{STRICT_PIECES}"))[1..]);
+    assert!(Torrent::from_buf(&buf).is_ok());
+    let result = Torrent::from_buf_strict(&buf);
+    assert!(matches!(result, Err(torrentinfo::errors::Error::NegativeInteger(ref key)) if key == "creation date"));
+
+    let buf = strict_torrent(&format!("6:lengthi-1e4:name1:a12:piece lengthi16384e{STRICT_PIECES}"));
+    let result = Torrent::from_buf_strict(&buf);
+    assert!(matches!(result, Err(torrentinfo::errors::Error::NegativeInteger(ref key)) if key == "length"));
+}
+
+#[test]
+fn test_tracker_schemes_ubuntu() {
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    let schemes = torrent.tracker_schemes();
+    assert_eq!(schemes.len(), 1);
+    assert_eq!(schemes.get("https"), Some(&2));
+}
+
+#[test]
+fn test_tracker_schemes_popos() {
+    let torrent = Torrent::from_file(POPOS_TORRENT_PATH).unwrap();
+    let schemes = torrent.tracker_schemes();
+    assert_eq!(schemes.get("udp"), Some(&9));
+    assert_eq!(schemes.get("http"), Some(&1));
+    assert_eq!(schemes.get("https"), None);
+}
+
+#[test]
+fn test_tracker_schemes_trackerless() {
+    let torrent = Torrent::from_file("tests/fixtures/trackerless.torrent").unwrap();
+    assert!(torrent.tracker_schemes().is_empty());
+}
+
+#[test]
+fn test_iter_files_single_file_uses_name() {
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    let files: Vec<_> = torrent.iter_files().collect();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path(), &[ubuntu::NAME]);
+}
+
+#[test]
+fn test_iter_files_skips_padding() {
+    let torrent = Torrent::from_file(ATTRIBUTES_TORRENT_PATH).unwrap();
+    assert!(torrent.iter_files().all(|file| !file.is_padding()));
+    assert!(torrent.iter_files().count() < torrent.num_files());
+}
+
+#[test]
+fn test_dht_nodes_with_string_ports_and_alternate_keys() {
+    let torrent = Torrent::from_file(DHT_NODES_TORRENT_PATH).unwrap();
+    let expected = vec![
+        ("router.example.org", 6881),
+        ("192.0.2.1", 6881),
+        ("198.51.100.7", 51413),
+        ("dht.example.org", 6882),
+    ];
+    assert_eq!(torrent.nodes(), expected);
+    assert_eq!(torrent.num_nodes(), 4);
+    assert!(torrent.is_trackerless_capable());
+
+    let roundtrip = Torrent::from_buf(&torrent.to_bytes().unwrap()).unwrap();
+    assert_eq!(roundtrip.nodes(), expected);
+}
+
+#[test]
+fn test_files_normalized_single_file() {
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    let files = torrent.files_normalized();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path(), &[ubuntu::NAME]);
+    assert_eq!(files[0].length(), ubuntu::TOTAL_SIZE);
+}
+
+#[test]
+fn test_files_normalized_multi_file_includes_padding() {
+    let torrent = Torrent::from_file(ATTRIBUTES_TORRENT_PATH).unwrap();
+    let files = torrent.files_normalized();
+    assert_eq!(files.len(), torrent.num_files());
+    assert!(files.iter().any(File::is_padding));
+    assert_eq!(files.iter().map(File::length).sum::<i64>(), torrent.total_size());
+}
+
+#[test]
+fn test_files_normalized_v2_only() {
+    let torrent = Torrent::from_file(V2_TORRENT_PATH).unwrap();
+    let paths: Vec<String> = torrent
+        .files_normalized()
+        .iter()
+        .map(|file| file.path().join("/"))
+        .collect();
+    assert_eq!(paths, vec!["docs/guide.txt", "empty.dat", "video.mkv"]);
+}
+
+#[test]
+fn test_contains_extension() {
+    let torrent = Torrent::from_file(MIXED_EXTENSIONS_TORRENT_PATH).unwrap();
+    assert!(torrent.contains_extension(&["nfo"]));
+    assert!(torrent.contains_extension(&[".srt"]));
+    assert!(torrent.contains_extension(&["txt", "JPG"]));
+    assert!(!torrent.contains_extension(&["iso", "en"]));
+
+    let single = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    assert!(single.contains_extension(&["iso"]));
+    assert!(!single.contains_extension(&["nfo"]));
+}
+
+#[test]
+fn test_peek_matches_full_parse() {
+    let mut paths = Torrent::find_in_dir(Path::new("tests/fixtures"), false, false);
+    paths.extend([PathBuf::from(UBUNTU_TORRENT_PATH), PathBuf::from(POPOS_TORRENT_PATH)]);
+    for path in &paths {
+        let torrent = Torrent::from_file(path).unwrap();
+        let peek = Torrent::peek(path).unwrap();
+        assert_eq!(
+            peek.content_name_or_source(path),
+            torrent.content_name_or_source(path),
+            "{}",
+            path.display()
+        );
+        assert_eq!(
+            peek.total_size_bytes(),
+            torrent.total_size_bytes(),
+            "{}",
+            path.display()
+        );
+        assert_eq!(peek.num_files(), torrent.num_files(), "{}", path.display());
+        assert_eq!(peek.creation_date(), torrent.creation_date(), "{}", path.display());
+        assert_eq!(
+            peek.info_hash(),
+            Some(torrent.info_hash().unwrap().as_slice()),
+            "{}",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn test_peek_ubuntu() {
+    let peek = Torrent::peek(UBUNTU_TORRENT_PATH).unwrap();
+    assert_eq!(peek.name().as_deref(), Some(ubuntu::NAME));
+    assert_eq!(peek.info_hash().map(to_hex).as_deref(), Some(ubuntu::INFO_HASH));
+    assert_eq!(peek.num_files(), 1);
+}
+
+#[test]
+fn test_summary_from_torrent_matches_peek() {
+    let torrent = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    let peek = Torrent::peek(UBUNTU_TORRENT_PATH).unwrap();
+    let summary = TorrentSummary::from_torrent(torrent.clone(), true).unwrap();
+    assert_eq!(summary.info_hash(), peek.info_hash());
+    assert_eq!(summary.name(), peek.name());
+    assert_eq!(summary.total_size_bytes(), peek.total_size_bytes());
+    assert!(
+        TorrentSummary::from_torrent(torrent, false)
+            .unwrap()
+            .info_hash()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_peek_rejects_missing_info() {
+    assert!(Torrent::peek_buf(b"d8:announce3:urle").is_err());
+    assert!(Torrent::peek_buf(b"li1ee").is_err());
+}
+
+#[test]
+fn test_tracker_count_and_backup_trackers() {
+    let popos = Torrent::from_file(POPOS_TORRENT_PATH).unwrap();
+    assert_eq!(popos.tracker_count(), 10);
+    assert!(popos.has_backup_trackers());
+
+    let ubuntu = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    assert_eq!(ubuntu.tracker_count(), 2);
+    assert!(ubuntu.has_backup_trackers());
+
+    let trackerless = Torrent::from_file("tests/fixtures/trackerless.torrent").unwrap();
+    assert_eq!(trackerless.tracker_count(), 0);
+    assert!(!trackerless.has_backup_trackers());
+}
+
+#[test]
+fn test_single_tracker_has_no_backup() {
+    let torrent = Torrent::from_file(ATTRIBUTES_TORRENT_PATH).unwrap();
+    assert_eq!(torrent.tracker_count(), 1);
+    assert!(!torrent.has_backup_trackers());
+    assert_eq!(torrent.num_nodes(), 0);
+}
+
+#[test]
+fn test_duplicate_tiers_are_one_tracker() {
+    let buf = b"d8:announce32:https://tracker.example/announce13:announce-listll32:https://tracker.example/announceel32:HTTPS://Tracker.example/announceee4:infod6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces0:ee";
+    let torrent = Torrent::from_buf(buf).unwrap();
+    assert_eq!(torrent.tracker_count(), 1);
+    assert!(!torrent.has_backup_trackers());
+}
+
+#[test]
+fn test_missing_info_is_reported_as_missing_field() {
+    let result = Torrent::from_file(MISSING_INFO_PATH);
+    assert!(matches!(result, Err(torrentinfo::errors::Error::MissingField("info"))));
+    let strict = Torrent::from_buf_strict(&Torrent::read_bytes(Path::new(MISSING_INFO_PATH)).unwrap());
+    assert!(matches!(strict, Err(torrentinfo::errors::Error::MissingField("info"))));
+    let summary = TorrentSummary::from_file(MISSING_INFO_PATH);
+    assert!(matches!(summary, Err(torrentinfo::errors::Error::MissingField("info"))));
+}
+
+#[test]
+fn test_missing_piece_length_is_reported_as_missing_field() {
+    let result = Torrent::from_buf(b"d4:infod6:lengthi1e4:name1:a6:pieces0:ee");
+    assert!(matches!(
+        result,
+        Err(torrentinfo::errors::Error::MissingField("piece length"))
+    ));
+}
+
+#[test]
+fn test_other_decode_errors_are_not_missing_fields() {
+    let result = Torrent::from_buf(b"d4:infod6:lengthi1e4:name1:a12:piece length3:abc6:pieces0:ee");
+    assert!(matches!(result, Err(torrentinfo::errors::Error::Decode(_))));
+    assert!(matches!(
+        Torrent::from_buf(b"i42e"),
+        Err(torrentinfo::errors::Error::Decode(_))
+    ));
+}
+
+#[test]
+fn test_io_error_chain_includes_cause() {
+    let error = Torrent::from_file("tests/fixtures/does-not-exist.torrent").unwrap_err();
+    let source = std::error::Error::source(&error).expect("IO error should have a source");
+    let io_error = source
+        .downcast_ref::<std::io::Error>()
+        .expect("Source should be an IO error");
+    assert_eq!(io_error.kind(), std::io::ErrorKind::NotFound);
+    let expected = format!("IO error: {io_error}");
+
+    assert_eq!(format!("{:#}", anyhow::Error::from(error)), expected);
+}
+
+#[test]
+fn test_decode_error_chain_includes_cause() {
+    let error = Torrent::from_buf(b"not bencode").unwrap_err();
+    let source = std::error::Error::source(&error).expect("Decode error should have a source");
+    assert!(source.downcast_ref::<serde_bencode::Error>().is_some());
+    let expected = format!("Failed to decode bencode: {source}");
+
+    assert_eq!(format!("{:#}", anyhow::Error::from(error)), expected);
+}
+
+#[test]
+fn test_bom_prefixed_torrent_parses() {
+    let bytes = Torrent::read_bytes(Path::new(BOM_PREFIXED_TORRENT_PATH)).unwrap();
+    assert!(bytes.starts_with(b"\xef\xbb\xbf"));
+    assert!(bytes.ends_with(b"\r\n"));
+    let clean = &bytes[3..bytes.len() - 2];
+
+    let torrent = Torrent::from_buf(&bytes).expect("BOM-prefixed torrent should parse");
+    assert_eq!(torrent.name().as_deref(), Some("bom-prefixed.bin"));
+    assert_eq!(torrent.total_size(), 30000);
+    let info_hash = torrent.info_hash().unwrap();
+    assert_eq!(info_hash, Torrent::from_buf(clean).unwrap().info_hash().unwrap());
+    assert_eq!(torrent.to_bytes().unwrap(), clean);
+
+    assert_eq!(Torrent::info_hash_from_bytes(&bytes).unwrap(), info_hash);
+    assert_eq!(
+        Torrent::peek_buf(&bytes).unwrap().info_hash(),
+        Some(info_hash.as_slice())
+    );
+    assert_eq!(TorrentSummary::from_buf(&bytes).unwrap().total_size_bytes(), 30000);
+}
+
+#[test]
+fn test_bom_prefixed_torrent_rejected_in_strict_mode() {
+    let bytes = Torrent::read_bytes(Path::new(BOM_PREFIXED_TORRENT_PATH)).unwrap();
+    assert!(matches!(
+        Torrent::from_buf_strict(&bytes),
+        Err(torrentinfo::errors::Error::Decode(_))
+    ));
+}
+
+#[test]
+fn test_hybrid_has_v1_and_v2_metadata() {
+    let hybrid = Torrent::from_file("tests/fixtures/hybrid.torrent").unwrap();
+    assert!(hybrid.info().is_v1());
+    assert!(hybrid.info().is_v2());
+
+    let v2_only = Torrent::from_file(V2_TORRENT_PATH).unwrap();
+    assert!(!v2_only.info().is_v1());
+    assert!(v2_only.info().is_v2());
+
+    let v1_only = Torrent::from_file(UBUNTU_TORRENT_PATH).unwrap();
+    assert!(v1_only.info().is_v1());
+    assert!(!v1_only.info().is_v2());
+
+    let empty_pieces = Torrent::from_file("tests/fixtures/empty-pieces.torrent").unwrap();
+    assert!(empty_pieces.info().is_v1());
+}
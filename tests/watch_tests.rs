@@ -0,0 +1,147 @@
+//! Integration tests for the directory watch mode, requires the `watch` feature
+
+#![cfg(feature = "watch")]
+
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Path to the Ubuntu test torrent file
+const UBUNTU_TORRENT_PATH: &str = "tests/ubuntu-24.04.3-desktop-amd64.iso.torrent";
+
+/// Maximum time to wait for the watcher to print a torrent
+const OUTPUT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Time for the watcher to start before files are added
+const STARTUP_DELAY: Duration = Duration::from_secs(1);
+
+/// Watch process that is killed when dropped
+struct Watcher {
+    child: Child,
+    lines: mpsc::Receiver<String>,
+}
+
+impl Watcher {
+    /// Start watching the directory and forward stdout lines to a channel
+    fn start(dir: &PathBuf) -> Self {
+        Self::start_with_args(dir, &[])
+    }
+
+    /// Start watching the directory with extra command line arguments
+    fn start_with_args(dir: &PathBuf, args: &[&str]) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_torrentinfo"))
+            .args(["--nocolour", "--watch"])
+            .args(args)
+            .arg(dir)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("Failed to start torrentinfo");
+        let stdout = child.stdout.take().expect("Missing stdout");
+        let (sender, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        std::thread::sleep(STARTUP_DELAY);
+        Self { child, lines }
+    }
+
+    /// Wait for a line containing the expected text and return all lines read so far
+    fn wait_for(&self, expected: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        while let Ok(line) = self.lines.recv_timeout(OUTPUT_TIMEOUT) {
+            let found = line.contains(expected);
+            lines.push(line);
+            if found {
+                return lines;
+            }
+        }
+        panic!("Watcher did not print {expected}, output: {lines:?}");
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Create an empty temporary directory for a single test
+fn fresh_temp_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).expect("Failed to remove temporary directory");
+    }
+    std::fs::create_dir_all(&dir).expect("Failed to create temporary directory");
+    dir
+}
+
+#[test]
+fn test_watch_prints_new_torrent() {
+    let dir = fresh_temp_dir("watch_new");
+    let watcher = Watcher::start(&dir);
+
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("ubuntu.torrent")).unwrap();
+    let lines = watcher.wait_for("info hash");
+    assert!(lines.iter().any(|line| line == "ubuntu.torrent"));
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains("ubuntu-24.04.3-desktop-amd64.iso"))
+    );
+}
+
+#[test]
+fn test_watch_retries_partially_written_torrent() {
+    let dir = fresh_temp_dir("watch_partial");
+    let watcher = Watcher::start(&dir);
+
+    let bytes = std::fs::read(UBUNTU_TORRENT_PATH).unwrap();
+    let (head, tail) = bytes.split_at(bytes.len() / 2);
+    let path = dir.join("partial.torrent");
+    std::fs::write(&path, head).unwrap();
+    std::thread::sleep(Duration::from_millis(700));
+    let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+    std::io::Write::write_all(&mut file, tail).unwrap();
+    drop(file);
+
+    let lines = watcher.wait_for("info hash");
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains("d160b8d8ea35a5b4e52837468fc8f03d55cef1f7"))
+    );
+}
+
+#[test]
+fn test_watch_ignores_other_files() {
+    let dir = fresh_temp_dir("watch_other");
+    let watcher = Watcher::start(&dir);
+
+    std::fs::write(dir.join("notes.txt"), "not a torrent").unwrap();
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("ubuntu.torrent")).unwrap();
+    let lines = watcher.wait_for("info hash");
+    assert!(!lines.iter().any(|line| line.contains("notes.txt")));
+}
+
+#[test]
+fn test_watch_recursive_skips_hidden_directories() {
+    let dir = fresh_temp_dir("watch_hidden");
+    let hidden = dir.join(".hidden");
+    std::fs::create_dir_all(&hidden).unwrap();
+    let watcher = Watcher::start_with_args(&dir, &["--recursive"]);
+
+    std::fs::copy(UBUNTU_TORRENT_PATH, hidden.join("hidden.torrent")).unwrap();
+    std::thread::sleep(Duration::from_millis(700));
+    std::fs::copy(UBUNTU_TORRENT_PATH, dir.join("visible.torrent")).unwrap();
+    let lines = watcher.wait_for("info hash");
+    assert!(lines.iter().any(|line| line == "visible.torrent"));
+    assert!(!lines.iter().any(|line| line.contains("hidden.torrent")));
+}